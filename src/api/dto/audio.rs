@@ -1,14 +1,18 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::domain::entities::speech::{SpeechFormat, SpeechRequest};
 use crate::domain::entities::transcription::{
-    ResponseFormat, TimestampGranularity, TranscriptionResponse, TranscriptionSegment,
+    ResponseFormat, SubtitleOptions, TimestampGranularity, TranscriptionProviderKind,
+    TranscriptionResponse, TranscriptionSegment,
     TranscriptionUsage, TranscriptionWord,
 };
+use crate::domain::entities::transcription_job::{TranscriptionJob, TranscriptionJobStatus};
 
 /// Audio transcription request DTO
 #[derive(Debug, Deserialize)]
 pub struct TranscribeRequestDto {
+    pub provider: Option<TranscriptionProviderKind>,
     pub model: Option<String>,
     pub language: Option<String>,
     pub prompt: Option<String>,
@@ -16,6 +20,12 @@ pub struct TranscribeRequestDto {
     pub temperature: Option<f32>,
     pub timestamp_granularities: Option<String>,
     pub llm_api_key_id: Option<String>,
+    pub webhook_url: Option<String>,     // Async jobs only
+    pub webhook_secret: Option<String>,  // Async jobs only; signs webhook deliveries
+    pub max_line_length: Option<usize>,  // srt/vtt/tsv only
+    pub max_cue_chars: Option<usize>,    // srt/vtt/tsv only
+    pub highlight_words: Option<bool>,   // srt/vtt only
+    pub diarize: Option<bool>,
 }
 
 impl TranscribeRequestDto {
@@ -30,6 +40,14 @@ impl TranscribeRequestDto {
                 .collect()
         })
     }
+
+    pub fn subtitle_options(&self) -> SubtitleOptions {
+        SubtitleOptions {
+            max_line_length: self.max_line_length,
+            max_cue_chars: self.max_cue_chars,
+            highlight_words: self.highlight_words.unwrap_or(false),
+        }
+    }
 }
 
 /// Response format DTO
@@ -41,6 +59,39 @@ pub enum ResponseFormatDto {
     Srt,
     VerboseJson,
     Vtt,
+    Tsv,
+}
+
+impl std::str::FromStr for ResponseFormatDto {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "json" => Ok(ResponseFormatDto::Json),
+            "text" => Ok(ResponseFormatDto::Text),
+            "srt" => Ok(ResponseFormatDto::Srt),
+            "verbose_json" => Ok(ResponseFormatDto::VerboseJson),
+            "vtt" => Ok(ResponseFormatDto::Vtt),
+            "tsv" => Ok(ResponseFormatDto::Tsv),
+            other => Err(format!(
+                "Invalid response_format '{}': expected one of json, text, srt, verbose_json, vtt, tsv",
+                other
+            )),
+        }
+    }
+}
+
+impl ResponseFormatDto {
+    /// Content-Type for formats returned as plain bodies instead of JSON
+    pub fn plain_content_type(&self) -> Option<&'static str> {
+        match self {
+            ResponseFormatDto::Text => Some("text/plain; charset=utf-8"),
+            ResponseFormatDto::Srt => Some("application/x-subrip"),
+            ResponseFormatDto::Vtt => Some("text/vtt"),
+            ResponseFormatDto::Tsv => Some("text/tab-separated-values; charset=utf-8"),
+            ResponseFormatDto::Json | ResponseFormatDto::VerboseJson => None,
+        }
+    }
 }
 
 impl From<ResponseFormatDto> for ResponseFormat {
@@ -51,6 +102,7 @@ impl From<ResponseFormatDto> for ResponseFormat {
             ResponseFormatDto::Srt => ResponseFormat::Srt,
             ResponseFormatDto::VerboseJson => ResponseFormat::VerboseJson,
             ResponseFormatDto::Vtt => ResponseFormat::Vtt,
+            ResponseFormatDto::Tsv => ResponseFormat::Tsv,
         }
     }
 }
@@ -91,6 +143,57 @@ impl From<TranscriptionResponse> for TranscribeResponseDto {
     }
 }
 
+/// Asynchronous transcription job DTO
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TranscriptionJobDto {
+    pub id: String,
+    pub object: String,
+    pub status: TranscriptionJobStatusDto,
+    pub file_name: String,
+    pub model: Option<String>,
+    pub result: Option<TranscribeResponseDto>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
+
+impl From<TranscriptionJob> for TranscriptionJobDto {
+    fn from(job: TranscriptionJob) -> Self {
+        Self {
+            id: job.job_id,
+            object: "transcription.job".to_string(),
+            status: job.status.into(),
+            file_name: job.file_name,
+            model: job.model,
+            result: job.result.map(TranscribeResponseDto::from),
+            error: job.error,
+            created_at: job.created_at.to_rfc3339(),
+            completed_at: job.completed_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+/// Transcription job status DTO
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionJobStatusDto {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl From<TranscriptionJobStatus> for TranscriptionJobStatusDto {
+    fn from(status: TranscriptionJobStatus) -> Self {
+        match status {
+            TranscriptionJobStatus::Queued => TranscriptionJobStatusDto::Queued,
+            TranscriptionJobStatus::Running => TranscriptionJobStatusDto::Running,
+            TranscriptionJobStatus::Succeeded => TranscriptionJobStatusDto::Succeeded,
+            TranscriptionJobStatus::Failed => TranscriptionJobStatusDto::Failed,
+        }
+    }
+}
+
 /// Transcription segment DTO
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TranscriptionSegmentDto {
@@ -103,6 +206,8 @@ pub struct TranscriptionSegmentDto {
     pub avg_logprob: Option<f32>,
     pub compression_ratio: Option<f32>,
     pub no_speech_prob: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
 }
 
 impl From<TranscriptionSegment> for TranscriptionSegmentDto {
@@ -117,6 +222,7 @@ impl From<TranscriptionSegment> for TranscriptionSegmentDto {
             avg_logprob: segment.avg_logprob,
             compression_ratio: segment.compression_ratio,
             no_speech_prob: segment.no_speech_prob,
+            speaker: segment.speaker,
         }
     }
 }
@@ -127,6 +233,8 @@ pub struct TranscriptionWordDto {
     pub word: String,
     pub start: f32,
     pub end: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
 }
 
 impl From<TranscriptionWord> for TranscriptionWordDto {
@@ -135,6 +243,7 @@ impl From<TranscriptionWord> for TranscriptionWordDto {
             word: word.word,
             start: word.start,
             end: word.end,
+            speaker: word.speaker,
         }
     }
 }
@@ -155,4 +264,96 @@ impl From<TranscriptionUsage> for TranscriptionUsageDto {
             estimated_cost_usd: usage.estimated_cost_usd,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Text-to-speech request DTO
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SpeechRequestDto {
+    /// TTS model identifier (e.g., "tts-1", "tts-1-hd", "gpt-4o-mini-tts")
+    pub model: String,
+
+    /// Text to synthesize (max 4096 characters)
+    pub input: String,
+
+    /// Voice to use (e.g., "alloy", "echo", "nova")
+    pub voice: String,
+
+    /// Audio format (default: mp3)
+    #[serde(default)]
+    pub response_format: Option<SpeechFormatDto>,
+
+    /// Playback speed (0.25-4.0, default: 1.0)
+    #[serde(default)]
+    pub speed: Option<f32>,
+
+    /// Specific LLM API key to use instead of the project default
+    #[serde(default)]
+    pub llm_api_key_id: Option<String>,
+}
+
+impl SpeechRequestDto {
+    /// Validate the request
+    pub fn validate(&self) -> Result<(), String> {
+        if self.model.is_empty() {
+            return Err("model cannot be empty".to_string());
+        }
+
+        if self.input.is_empty() {
+            return Err("input cannot be empty".to_string());
+        }
+
+        if self.input.chars().count() > 4096 {
+            return Err("input cannot exceed 4096 characters".to_string());
+        }
+
+        if self.voice.is_empty() {
+            return Err("voice cannot be empty".to_string());
+        }
+
+        if let Some(speed) = self.speed {
+            if !(0.25..=4.0).contains(&speed) {
+                return Err("speed must be between 0.25 and 4.0".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<SpeechRequestDto> for SpeechRequest {
+    fn from(dto: SpeechRequestDto) -> Self {
+        Self {
+            model: dto.model,
+            input: dto.input,
+            voice: dto.voice,
+            response_format: dto.response_format.map(SpeechFormat::from).unwrap_or_default(),
+            speed: dto.speed,
+            llm_api_key_id: dto.llm_api_key_id,
+        }
+    }
+}
+
+/// Speech audio format DTO
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeechFormatDto {
+    Mp3,
+    Opus,
+    Aac,
+    Flac,
+    Wav,
+    Pcm,
+}
+
+impl From<SpeechFormatDto> for SpeechFormat {
+    fn from(dto: SpeechFormatDto) -> Self {
+        match dto {
+            SpeechFormatDto::Mp3 => SpeechFormat::Mp3,
+            SpeechFormatDto::Opus => SpeechFormat::Opus,
+            SpeechFormatDto::Aac => SpeechFormat::Aac,
+            SpeechFormatDto::Flac => SpeechFormat::Flac,
+            SpeechFormatDto::Wav => SpeechFormat::Wav,
+            SpeechFormatDto::Pcm => SpeechFormat::Pcm,
+        }
+    }
+}
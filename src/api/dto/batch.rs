@@ -0,0 +1,219 @@
+//! OpenAI Files and Batch API DTOs
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::entities::batch::{BatchError, BatchJob, BatchStatus};
+use crate::domain::entities::file::StoredFile;
+
+/// File object
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FileObjectDto {
+    /// File ID (`file-...`)
+    pub id: String,
+    /// Always `file`
+    pub object: String,
+    pub bytes: usize,
+    /// Unix timestamp in seconds
+    pub created_at: i64,
+    pub filename: String,
+    pub purpose: String,
+    /// Always `processed`; files are validated on upload
+    pub status: String,
+}
+
+impl From<StoredFile> for FileObjectDto {
+    fn from(file: StoredFile) -> Self {
+        Self {
+            id: file.file_id,
+            object: "file".to_string(),
+            bytes: file.bytes,
+            created_at: file.created_at.timestamp(),
+            filename: file.filename,
+            purpose: file.purpose,
+            status: "processed".to_string(),
+        }
+    }
+}
+
+/// List of files
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FileListDto {
+    /// Always `list`
+    pub object: String,
+    pub data: Vec<FileObjectDto>,
+}
+
+/// Delete file result
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FileDeletedDto {
+    pub id: String,
+    /// Always `file`
+    pub object: String,
+    pub deleted: bool,
+}
+
+/// Create batch request
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateBatchRequestDto {
+    /// ID of a file uploaded with purpose `batch`
+    pub input_file_id: String,
+    /// `/v1/chat/completions` or `/v1/embeddings`
+    pub endpoint: String,
+    /// Only `24h` is supported
+    pub completion_window: String,
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// Batch object
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchDto {
+    /// Batch ID (`batch_...`)
+    pub id: String,
+    /// Always `batch`
+    pub object: String,
+    pub endpoint: String,
+    pub errors: Option<BatchErrorsDto>,
+    pub input_file_id: String,
+    pub completion_window: String,
+    pub status: BatchStatusDto,
+    /// File with the successful responses, once finalized
+    pub output_file_id: Option<String>,
+    /// File with the failed requests, once finalized
+    pub error_file_id: Option<String>,
+    /// Unix timestamps in seconds
+    pub created_at: i64,
+    pub in_progress_at: Option<i64>,
+    pub expires_at: Option<i64>,
+    pub finalizing_at: Option<i64>,
+    pub completed_at: Option<i64>,
+    pub failed_at: Option<i64>,
+    pub expired_at: Option<i64>,
+    pub cancelling_at: Option<i64>,
+    pub cancelled_at: Option<i64>,
+    pub request_counts: BatchRequestCountsDto,
+    pub metadata: HashMap<String, String>,
+    /// Gateway extension: discounted cost so far
+    pub x_llmhub: BatchCostDto,
+}
+
+impl From<BatchJob> for BatchDto {
+    fn from(batch: BatchJob) -> Self {
+        let errors = (!batch.errors.is_empty()).then(|| BatchErrorsDto {
+            object: "list".to_string(),
+            data: batch.errors.into_iter().map(BatchErrorDto::from).collect(),
+        });
+
+        Self {
+            id: batch.batch_id,
+            object: "batch".to_string(),
+            endpoint: batch.endpoint,
+            errors,
+            input_file_id: batch.input_file_id,
+            completion_window: batch.completion_window,
+            status: batch.status.into(),
+            output_file_id: batch.output_file_id,
+            error_file_id: batch.error_file_id,
+            created_at: batch.created_at.timestamp(),
+            in_progress_at: batch.in_progress_at.map(|t| t.timestamp()),
+            expires_at: Some(batch.expires_at.timestamp()),
+            finalizing_at: batch.finalizing_at.map(|t| t.timestamp()),
+            completed_at: batch.completed_at.map(|t| t.timestamp()),
+            failed_at: batch.failed_at.map(|t| t.timestamp()),
+            expired_at: batch.expired_at.map(|t| t.timestamp()),
+            cancelling_at: batch.cancelling_at.map(|t| t.timestamp()),
+            cancelled_at: batch.cancelled_at.map(|t| t.timestamp()),
+            request_counts: BatchRequestCountsDto {
+                total: batch.request_counts.total,
+                completed: batch.request_counts.completed,
+                failed: batch.request_counts.failed,
+            },
+            metadata: batch.metadata,
+            x_llmhub: BatchCostDto {
+                cost: batch.cost_usd,
+            },
+        }
+    }
+}
+
+/// Batch status DTO
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatusDto {
+    Validating,
+    InProgress,
+    Finalizing,
+    Completed,
+    Failed,
+    Expired,
+    Cancelling,
+    Cancelled,
+}
+
+impl From<BatchStatus> for BatchStatusDto {
+    fn from(status: BatchStatus) -> Self {
+        match status {
+            BatchStatus::Validating => BatchStatusDto::Validating,
+            BatchStatus::InProgress => BatchStatusDto::InProgress,
+            BatchStatus::Finalizing => BatchStatusDto::Finalizing,
+            BatchStatus::Completed => BatchStatusDto::Completed,
+            BatchStatus::Failed => BatchStatusDto::Failed,
+            BatchStatus::Expired => BatchStatusDto::Expired,
+            BatchStatus::Cancelling => BatchStatusDto::Cancelling,
+            BatchStatus::Cancelled => BatchStatusDto::Cancelled,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchErrorsDto {
+    /// Always `list`
+    pub object: String,
+    pub data: Vec<BatchErrorDto>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchErrorDto {
+    pub code: String,
+    pub message: String,
+    pub param: Option<String>,
+    /// Input file line, when the error concerns one line
+    pub line: Option<u32>,
+}
+
+impl From<BatchError> for BatchErrorDto {
+    fn from(error: BatchError) -> Self {
+        Self {
+            code: error.code,
+            message: error.message,
+            param: None,
+            line: error.line,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchRequestCountsDto {
+    pub total: u32,
+    pub completed: u32,
+    pub failed: u32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchCostDto {
+    /// Cost in USD after the batch discount
+    pub cost: f64,
+}
+
+/// List of batches, newest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchListDto {
+    /// Always `list`
+    pub object: String,
+    pub data: Vec<BatchDto>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
@@ -1,26 +1,217 @@
-/// Chat Completions API DTOs
-/// Based on CID specification: cid/rest-api/gateway/chat.yaml
-/// OpenAI-compatible chat completions API
+//! Chat Completions API DTOs
+//! Based on CID specification: cid/rest-api/gateway/chat.yaml
+//! OpenAI-compatible chat completions API
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use utoipa::ToSchema;
 
+use crate::domain::services::vision;
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ChatRole {
     System,
     User,
     Assistant,
+    Tool,
+    /// Roles this gateway does not know, kept verbatim
+    #[serde(untagged)]
+    Other(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ChatMessage {
     pub role: ChatRole,
-    pub content: String,
+    /// Text or content parts; null on assistant messages that only call tools
+    pub content: Option<ChatContent>,
+    /// Optional participant name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Tool calls requested by the assistant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// ID of the tool call a `tool` message answers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    /// Refusal message generated by the model instead of content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<String>,
+}
+
+/// Message content: a plain string or an array of typed parts
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ChatContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+    InputAudio { input_audio: InputAudio },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImageUrl {
+    /// `https://` URL or `data:image/...;base64,` URL
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<ImageDetail>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageDetail {
+    Auto,
+    Low,
+    High,
+}
+
+impl ImageUrl {
+    /// Estimated prompt tokens, from the dimensions in a data URL header
+    /// (remote images assume 1024x1024)
+    pub fn estimated_tokens(&self) -> u32 {
+        let dimensions = vision::split_data_url(&self.url)
+            .and_then(|(_, data)| vision::decode_header(data))
+            .and_then(|header| vision::image_dimensions(&header));
+        let low_detail = matches!(self.detail, Some(ImageDetail::Low));
+        vision::image_tokens(dimensions.unwrap_or((1024, 1024)), low_detail)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InputAudio {
+    /// Base64-encoded audio
+    pub data: String,
+    /// `wav` or `mp3`
+    pub format: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolType {
+    Function,
+}
+
+/// Tool the model may call
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChatTool {
+    pub r#type: ToolType,
+    pub function: FunctionDefinition,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FunctionDefinition {
+    /// Function name (a-z, A-Z, 0-9, underscores and dashes, max 64)
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema of the arguments
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub parameters: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+/// `"none"`, `"auto"`, `"required"` or a specific function
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ToolChoice {
+    Mode(ToolChoiceMode),
+    Function(NamedToolChoice),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolChoiceMode {
+    None,
+    Auto,
+    Required,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NamedToolChoice {
+    pub r#type: ToolType,
+    pub function: ToolChoiceFunction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ToolChoiceFunction {
+    pub name: String,
+}
+
+/// Tool call made by the assistant
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ToolCall {
+    pub id: String,
+    pub r#type: ToolType,
+    pub function: FunctionCall,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FunctionCall {
+    pub name: String,
+    /// Arguments as a JSON string, as generated by the model
+    pub arguments: String,
+}
+
+/// Requested output format
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatResponseFormat {
+    Text,
+    /// Any valid JSON object
+    JsonObject,
+    /// JSON matching a schema
+    JsonSchema { json_schema: JsonSchemaFormat },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JsonSchemaFormat {
+    /// Schema name (letters, digits, `_` and `-`, up to 64 characters)
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema the output must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub schema: Option<serde_json::Value>,
+    /// Enforce the schema exactly (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+/// One stop sequence or a list of up to 4
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum StopSequences {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StreamOptions {
+    /// Send a final chunk carrying usage for the whole request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_usage: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Minimal,
+    Low,
+    Medium,
+    High,
 }
 
 /// Chat completion request
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct ChatCompletionRequest {
     /// Model identifier (e.g., "gpt-4", "claude-3-opus", "gemini-pro")
     pub model: String,
@@ -38,7 +229,6 @@ pub struct ChatCompletionRequest {
 
     /// Whether to stream responses (default: false)
     #[serde(default)]
-    #[allow(dead_code)]
     pub stream: bool,
 
     /// Nucleus sampling parameter (0-1, default: 1)
@@ -52,6 +242,77 @@ pub struct ChatCompletionRequest {
     /// Presence penalty (-2 to 2, default: 0)
     #[serde(default)]
     pub presence_penalty: f32,
+
+    /// Tools the model may call
+    pub tools: Option<Vec<ChatTool>>,
+
+    /// How the model picks tools (default: "auto" when tools are given)
+    pub tool_choice: Option<ToolChoice>,
+
+    /// Whether the model may call several tools in one turn (default: true)
+    pub parallel_tool_calls: Option<bool>,
+
+    /// Output format; emulated and validated by the gateway for models
+    /// without native support
+    pub response_format: Option<ChatResponseFormat>,
+
+    /// Number of choices to generate (1-128, default: 1)
+    pub n: Option<u32>,
+
+    /// Sequences at which generation stops
+    pub stop: Option<StopSequences>,
+
+    /// Seed for best-effort deterministic sampling
+    pub seed: Option<i64>,
+
+    /// Return log probabilities of the output tokens
+    pub logprobs: Option<bool>,
+
+    /// Most likely alternatives to return per token (0-20, requires logprobs)
+    pub top_logprobs: Option<u32>,
+
+    /// Bias (-100 to 100) keyed by token ID
+    pub logit_bias: Option<HashMap<String, f32>>,
+
+    /// End-user identifier, forwarded for abuse monitoring
+    pub user: Option<String>,
+
+    /// Maximum tokens to generate, including reasoning tokens
+    pub max_completion_tokens: Option<u32>,
+
+    /// Streaming options (requires stream)
+    pub stream_options: Option<StreamOptions>,
+
+    /// Reasoning effort for reasoning models
+    pub reasoning_effort: Option<ReasoningEffort>,
+
+    /// Gateway extension: shorten the conversation to fit the model's context
+    /// window instead of failing with `context_length_exceeded`
+    #[serde(default)]
+    pub truncation: Option<TruncationStrategy>,
+
+    /// Fields not modelled here; forwarded upstream only when
+    /// `chat.forward_unknown_fields` is enabled
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// How an oversize conversation is shortened. System and developer messages
+/// and the latest turn are always kept; tool results go with their call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationStrategy {
+    /// Drop the oldest turns first
+    DropOldest,
+    /// Keep the first and latest turns, dropping from the middle outwards
+    MiddleOut,
+}
+
+/// Decoded size of a base64 string, without decoding it
+fn base64_len(data: &str) -> usize {
+    let padding = data.bytes().rev().take_while(|b| *b == b'=').count();
+    (data.len() / 4 * 3).saturating_sub(padding)
 }
 
 fn default_temperature() -> f32 {
@@ -69,6 +330,11 @@ pub enum FinishReason {
     Length,
     #[serde(rename = "content_filter")]
     ContentFilter,
+    #[serde(rename = "tool_calls")]
+    ToolCalls,
+    /// Reasons this gateway does not know, kept verbatim
+    #[serde(untagged)]
+    Other(String),
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -77,6 +343,33 @@ pub struct ChatChoice {
     pub message: ChatMessage,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub finish_reason: Option<FinishReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<ChoiceLogprobs>,
+}
+
+/// Log probabilities of the tokens in a choice
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChoiceLogprobs {
+    pub content: Option<Vec<TokenLogprob>>,
+    pub refusal: Option<Vec<TokenLogprob>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TokenLogprob {
+    pub token: String,
+    pub logprob: f64,
+    /// UTF-8 bytes of the token, for tokens that split characters
+    pub bytes: Option<Vec<u8>>,
+    /// Most likely tokens at this position, when `top_logprobs` was requested
+    #[serde(default)]
+    pub top_logprobs: Vec<TopLogprob>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TopLogprob {
+    pub token: String,
+    pub logprob: f64,
+    pub bytes: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -84,6 +377,34 @@ pub struct ChatUsage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens_details: Option<PromptTokensDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completion_tokens_details: Option<CompletionTokensDetails>,
+}
+
+/// Breakdown of prompt tokens
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PromptTokensDetails {
+    /// Tokens served from the provider's prompt cache
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_tokens: Option<u32>,
+}
+
+/// Breakdown of completion tokens
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CompletionTokensDetails {
+    /// Hidden tokens spent reasoning before the answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accepted_prediction_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejected_prediction_tokens: Option<u32>,
 }
 
 /// LLM Hub-specific metadata
@@ -101,6 +422,21 @@ pub struct ChatMetadata {
 
     /// Response time in milliseconds
     pub response_time: u64,
+
+    /// Prompt-injection guardrail result, when the project flags it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_injection: Option<PromptInjectionMetadata>,
+}
+
+/// Prompt-injection score of the request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PromptInjectionMetadata {
+    /// Highest detector score (0-1)
+    pub score: f64,
+    /// Whether the score reached the project's threshold
+    pub flagged: bool,
+    /// Rules that matched, as `rule@role`
+    pub rules: Vec<String>,
 }
 
 /// Chat completion response
@@ -112,6 +448,12 @@ pub struct ChatCompletionResponse {
     pub model: String,
     pub choices: Vec<ChatChoice>,
     pub usage: ChatUsage,
+    /// Backend configuration fingerprint, for reproducibility with `seed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_fingerprint: Option<String>,
+    /// Processing tier that served the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub x_llmhub: Option<ChatMetadata>,
 }
@@ -124,6 +466,31 @@ pub struct ChatDelta {
     pub role: Option<ChatRole>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<String>,
+}
+
+/// Fragment of a streamed tool call; `arguments` arrive in pieces that are
+/// concatenated per `index`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ToolCallDelta {
+    pub index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<ToolType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<FunctionCallDelta>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FunctionCallDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -132,6 +499,8 @@ pub struct ChatChoiceChunk {
     pub delta: ChatDelta,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub finish_reason: Option<FinishReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<ChoiceLogprobs>,
 }
 
 /// Streaming chat completion chunk
@@ -141,7 +510,16 @@ pub struct ChatCompletionChunk {
     pub object: String,
     pub created: i64,
     pub model: String,
+    #[serde(default)]
     pub choices: Vec<ChatChoiceChunk>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<String>,
+    /// Usage for the whole request, on the final chunk when
+    /// `stream_options.include_usage` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<ChatUsage>,
 }
 
 /// Error response
@@ -184,6 +562,207 @@ impl ChatCompletionRequest {
             return Err("presence_penalty must be between -2 and 2".to_string());
         }
 
+        if self.n.is_some_and(|n| !(1..=128).contains(&n)) {
+            return Err("n must be between 1 and 128".to_string());
+        }
+
+        if let Some(stop) = &self.stop {
+            let sequences = match stop {
+                StopSequences::Single(sequence) => std::slice::from_ref(sequence),
+                StopSequences::Multiple(sequences) => sequences.as_slice(),
+            };
+            if sequences.is_empty() || sequences.len() > 4 || sequences.iter().any(|s| s.is_empty()) {
+                return Err("stop must be 1 to 4 non-empty sequences".to_string());
+            }
+        }
+
+        if let Some(top_logprobs) = self.top_logprobs {
+            if top_logprobs > 20 {
+                return Err("top_logprobs must be between 0 and 20".to_string());
+            }
+            if self.logprobs != Some(true) {
+                return Err("top_logprobs requires logprobs to be true".to_string());
+            }
+        }
+
+        for (token, bias) in self.logit_bias.iter().flatten() {
+            if token.parse::<u32>().is_err() {
+                return Err(format!("logit_bias keys must be token IDs, got {:?}", token));
+            }
+            if !(-100.0..=100.0).contains(bias) {
+                return Err("logit_bias values must be between -100 and 100".to_string());
+            }
+        }
+
+        if self.max_tokens == Some(0) || self.max_completion_tokens == Some(0) {
+            return Err("max_tokens and max_completion_tokens must be greater than 0".to_string());
+        }
+
+        if self.stream_options.is_some() && !self.stream {
+            return Err("stream_options requires stream to be true".to_string());
+        }
+
+        self.validate_tools()?;
+        self.validate_content_parts()?;
+        self.validate_response_format()?;
+
+        for (i, message) in self.messages.iter().enumerate() {
+            match message.role {
+                ChatRole::Tool if message.tool_call_id.is_none() => {
+                    return Err(format!("messages[{}]: tool messages require tool_call_id", i));
+                }
+                ChatRole::Assistant if message.content.is_none() && message.tool_calls.is_none() => {
+                    return Err(format!(
+                        "messages[{}]: assistant messages require content or tool_calls",
+                        i
+                    ));
+                }
+                ChatRole::System | ChatRole::User | ChatRole::Tool if message.content.is_none() => {
+                    return Err(format!("messages[{}]: content is required", i));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_content_parts(&self) -> Result<(), String> {
+        for (i, message) in self.messages.iter().enumerate() {
+            let Some(ChatContent::Parts(parts)) = &message.content else {
+                continue;
+            };
+            if parts.is_empty() {
+                return Err(format!("messages[{}]: content parts cannot be empty", i));
+            }
+
+            for part in parts {
+                match part {
+                    ContentPart::Text { .. } => {}
+                    ContentPart::ImageUrl { .. } | ContentPart::InputAudio { .. }
+                        if !matches!(message.role, ChatRole::User) =>
+                    {
+                        return Err(format!(
+                            "messages[{}]: image and audio parts are only allowed in user messages",
+                            i
+                        ));
+                    }
+                    ContentPart::ImageUrl { image_url } => {
+                        if image_url.url.starts_with("data:") {
+                            let (mime, data) = vision::split_data_url(&image_url.url)
+                                .ok_or_else(|| format!("messages[{}]: malformed image data URL", i))?;
+                            if !vision::SUPPORTED_IMAGE_TYPES.contains(&mime) {
+                                return Err(format!(
+                                    "messages[{}]: unsupported image type {}",
+                                    i, mime
+                                ));
+                            }
+                            if base64_len(data) > vision::MAX_IMAGE_BYTES {
+                                return Err(format!(
+                                    "messages[{}]: image exceeds {}MB",
+                                    i,
+                                    vision::MAX_IMAGE_BYTES / (1024 * 1024)
+                                ));
+                            }
+                        } else if !image_url.url.starts_with("https://")
+                            && !image_url.url.starts_with("http://")
+                        {
+                            return Err(format!(
+                                "messages[{}]: image_url must be an http(s) or data URL",
+                                i
+                            ));
+                        }
+                    }
+                    ContentPart::InputAudio { input_audio } => {
+                        if !matches!(input_audio.format.as_str(), "wav" | "mp3") {
+                            return Err(format!(
+                                "messages[{}]: input_audio format must be wav or mp3",
+                                i
+                            ));
+                        }
+                        if base64_len(&input_audio.data) > vision::MAX_INPUT_AUDIO_BYTES {
+                            return Err(format!(
+                                "messages[{}]: input_audio exceeds {}MB",
+                                i,
+                                vision::MAX_INPUT_AUDIO_BYTES / (1024 * 1024)
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Estimated prompt tokens for the images in the request, from the
+    /// dimensions in data URL headers (remote images assume 1024x1024)
+    pub fn estimated_image_tokens(&self) -> u32 {
+        self.messages
+            .iter()
+            .filter_map(|m| match &m.content {
+                Some(ChatContent::Parts(parts)) => Some(parts),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|part| match part {
+                ContentPart::ImageUrl { image_url } => Some(image_url),
+                _ => None,
+            })
+            .map(ImageUrl::estimated_tokens)
+            .sum()
+    }
+
+    fn validate_response_format(&self) -> Result<(), String> {
+        let Some(ChatResponseFormat::JsonSchema { json_schema }) = &self.response_format else {
+            return Ok(());
+        };
+
+        let name = &json_schema.name;
+        if name.is_empty()
+            || name.len() > 64
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!("Invalid json_schema name: {:?}", name));
+        }
+
+        if let Some(schema) = &json_schema.schema {
+            if !schema.is_object() {
+                return Err("json_schema.schema must be an object".to_string());
+            }
+            jsonschema::validator_for(schema)
+                .map_err(|e| format!("Invalid json_schema.schema: {}", e))?;
+        }
+
         Ok(())
     }
+
+    fn validate_tools(&self) -> Result<(), String> {
+        let tools = self.tools.as_deref().unwrap_or_default();
+
+        for tool in tools {
+            let name = &tool.function.name;
+            if name.is_empty()
+                || name.len() > 64
+                || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(format!("Invalid tool function name: {:?}", name));
+            }
+        }
+
+        match &self.tool_choice {
+            Some(ToolChoice::Mode(ToolChoiceMode::Required)) if tools.is_empty() => {
+                Err("tool_choice \"required\" needs at least one tool".to_string())
+            }
+            Some(ToolChoice::Function(choice))
+                if !tools.iter().any(|t| t.function.name == choice.function.name) =>
+            {
+                Err(format!(
+                    "tool_choice names unknown function: {}",
+                    choice.function.name
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
 }
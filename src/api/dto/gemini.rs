@@ -0,0 +1,186 @@
+//! Gemini generateContent API DTOs
+//! Lets Google GenAI SDK clients call any gateway model through `/v1beta`
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// generateContent request
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateContentRequest {
+    /// Conversation turns
+    pub contents: Vec<GeminiContent>,
+
+    /// System prompt
+    pub system_instruction: Option<GeminiContent>,
+
+    /// Function declarations the model may call
+    pub tools: Option<Vec<GeminiTool>>,
+
+    pub tool_config: Option<GeminiToolConfig>,
+
+    pub generation_config: Option<GenerationConfig>,
+
+    /// Accepted for compatibility; other providers apply their own filtering
+    #[schema(value_type = Option<Vec<Object>>)]
+    pub safety_settings: Option<Vec<serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GeminiContent {
+    /// `user` or `model`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    pub parts: Vec<GeminiPart>,
+}
+
+/// One part of a turn; exactly one field is set
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiPart {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inline_data: Option<InlineData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_data: Option<FileData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_call: Option<GeminiFunctionCall>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_response: Option<GeminiFunctionResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineData {
+    pub mime_type: String,
+    /// Base64-encoded bytes
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FileData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    pub file_uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GeminiFunctionCall {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub args: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GeminiFunctionResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    #[schema(value_type = Object)]
+    pub response: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiTool {
+    pub function_declarations: Option<Vec<FunctionDeclaration>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FunctionDeclaration {
+    pub name: String,
+    pub description: Option<String>,
+    /// OpenAPI-style schema for the arguments
+    #[schema(value_type = Option<Object>)]
+    pub parameters: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiToolConfig {
+    pub function_calling_config: Option<FunctionCallingConfig>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionCallingConfig {
+    /// `AUTO`, `ANY` or `NONE`
+    pub mode: Option<String>,
+    pub allowed_function_names: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationConfig {
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    /// Not available on OpenAI models
+    pub top_k: Option<u32>,
+    pub candidate_count: Option<u32>,
+    pub max_output_tokens: Option<u32>,
+    pub stop_sequences: Option<Vec<String>>,
+    pub presence_penalty: Option<f32>,
+    pub frequency_penalty: Option<f32>,
+    pub seed: Option<i64>,
+    /// `application/json` requests JSON output
+    pub response_mime_type: Option<String>,
+    /// OpenAPI-style schema for JSON output
+    #[schema(value_type = Option<Object>)]
+    pub response_schema: Option<serde_json::Value>,
+    /// JSON Schema for JSON output
+    #[schema(value_type = Option<Object>)]
+    pub response_json_schema: Option<serde_json::Value>,
+}
+
+/// generateContent response, also one event of a streamed response
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateContentResponse {
+    pub candidates: Vec<GeminiCandidate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage_metadata: Option<UsageMetadata>,
+    pub model_version: String,
+    pub response_id: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCandidate {
+    pub index: u32,
+    pub content: GeminiContent,
+    /// `STOP`, `MAX_TOKENS`, `SAFETY` or `OTHER`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageMetadata {
+    pub prompt_token_count: u32,
+    pub candidates_token_count: u32,
+    pub total_token_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_content_token_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thoughts_token_count: Option<u32>,
+}
+
+/// Error response in the Google API shape
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GeminiErrorResponse {
+    pub error: GeminiError,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GeminiError {
+    /// HTTP status code
+    pub code: u16,
+    pub message: String,
+    /// Canonical status, e.g. `INVALID_ARGUMENT`
+    pub status: String,
+}
@@ -0,0 +1,167 @@
+//! OpenAI Images API DTOs
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::entities::image::{ImageOperation, ImageRequest};
+
+/// Image generation request
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImageGenerationRequestDto {
+    /// Text description of the image (max 32000 characters)
+    pub prompt: String,
+
+    /// Image model (default: dall-e-2)
+    pub model: Option<String>,
+
+    /// Number of images (1-10; dall-e-3 only supports 1)
+    pub n: Option<u32>,
+
+    /// Image size, e.g. `1024x1024`, `1792x1024` or `auto`
+    pub size: Option<String>,
+
+    /// `standard`/`hd` for dall-e-3, `low`/`medium`/`high`/`auto` for gpt-image-1
+    pub quality: Option<String>,
+
+    /// `url` or `b64_json` (dall-e only; gpt-image-1 always returns b64_json)
+    pub response_format: Option<ImageResponseFormatDto>,
+
+    /// `vivid` or `natural` (dall-e-3)
+    pub style: Option<String>,
+
+    /// `transparent`, `opaque` or `auto` (gpt-image-1)
+    pub background: Option<String>,
+
+    /// `png`, `jpeg` or `webp` (gpt-image-1)
+    pub output_format: Option<String>,
+
+    /// Compression level 0-100 for jpeg and webp (gpt-image-1)
+    pub output_compression: Option<u32>,
+
+    /// `low` or `auto` content moderation (gpt-image-1)
+    pub moderation: Option<String>,
+
+    /// End-user identifier
+    pub user: Option<String>,
+
+    /// Specific LLM API key to use instead of the project default
+    pub llm_api_key_id: Option<String>,
+}
+
+impl ImageGenerationRequestDto {
+    /// Validate the request
+    pub fn validate(&self) -> Result<(), String> {
+        if self.prompt.trim().is_empty() {
+            return Err("prompt cannot be empty".to_string());
+        }
+
+        if self.prompt.chars().count() > 32000 {
+            return Err("prompt cannot exceed 32000 characters".to_string());
+        }
+
+        validate_n(self.n)?;
+
+        if let Some(compression) = self.output_compression {
+            if compression > 100 {
+                return Err("output_compression must be between 0 and 100".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<ImageGenerationRequestDto> for ImageRequest {
+    fn from(dto: ImageGenerationRequestDto) -> Self {
+        let mut options = serde_json::Map::new();
+        let mut set = |key: &str, value: Option<serde_json::Value>| {
+            if let Some(value) = value {
+                options.insert(key.to_string(), value);
+            }
+        };
+        set("response_format", dto.response_format.map(|f| f.as_str().into()));
+        set("style", dto.style.map(Into::into));
+        set("background", dto.background.map(Into::into));
+        set("output_format", dto.output_format.map(Into::into));
+        set("output_compression", dto.output_compression.map(Into::into));
+        set("moderation", dto.moderation.map(Into::into));
+        set("user", dto.user.map(Into::into));
+
+        Self {
+            operation: ImageOperation::Generation,
+            model: dto.model.unwrap_or_else(|| "dall-e-2".to_string()),
+            prompt: Some(dto.prompt),
+            n: dto.n.unwrap_or(1),
+            size: dto.size,
+            quality: dto.quality,
+            options,
+            images: Vec::new(),
+            mask: None,
+            llm_api_key_id: dto.llm_api_key_id,
+        }
+    }
+}
+
+/// Check the requested number of images
+pub fn validate_n(n: Option<u32>) -> Result<(), String> {
+    match n {
+        Some(n) if !(1..=10).contains(&n) => Err("n must be between 1 and 10".to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// How generated images are returned
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageResponseFormatDto {
+    Url,
+    B64Json,
+}
+
+impl ImageResponseFormatDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageResponseFormatDto::Url => "url",
+            ImageResponseFormatDto::B64Json => "b64_json",
+        }
+    }
+}
+
+/// Images response, passed through from the provider
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImagesResponse {
+    /// Unix timestamp in seconds
+    pub created: i64,
+    pub data: Vec<ImageData>,
+    /// Token usage reported by gpt-image-1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub usage: Option<serde_json::Value>,
+    /// Other provider fields (`background`, `output_format`, `size`, ...)
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_llmhub: Option<ImagesMetadata>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImageData {
+    /// Temporary URL of the image, when `response_format` is `url`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Base64-encoded image, when `response_format` is `b64_json`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub b64_json: Option<String>,
+    /// Prompt the model actually used (dall-e-3)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revised_prompt: Option<String>,
+}
+
+/// Gateway metadata for an images response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImagesMetadata {
+    /// Cost in USD
+    pub cost: f64,
+    pub latency_ms: u64,
+}
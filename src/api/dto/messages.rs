@@ -0,0 +1,239 @@
+//! Anthropic Messages API DTOs
+//! Lets Anthropic SDK clients call any gateway model through `/v1/messages`
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Messages request
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MessagesRequest {
+    /// Model identifier; any model the gateway routes, not only Claude
+    pub model: String,
+
+    /// Conversation turns, alternating user and assistant
+    pub messages: Vec<InputMessage>,
+
+    /// System prompt
+    pub system: Option<SystemPrompt>,
+
+    /// Maximum tokens to generate
+    pub max_tokens: u32,
+
+    /// Sampling temperature (0-1)
+    pub temperature: Option<f32>,
+
+    /// Nucleus sampling parameter (0-1)
+    pub top_p: Option<f32>,
+
+    /// Top-k sampling; not available on OpenAI models
+    pub top_k: Option<u32>,
+
+    /// Custom sequences at which generation stops
+    pub stop_sequences: Option<Vec<String>>,
+
+    /// Whether to stream events (default: false)
+    #[serde(default)]
+    pub stream: bool,
+
+    /// Tools the model may use
+    pub tools: Option<Vec<MessagesTool>>,
+
+    /// How the model picks tools
+    pub tool_choice: Option<MessagesToolChoice>,
+
+    pub metadata: Option<MessagesMetadata>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct InputMessage {
+    pub role: MessageRole,
+    pub content: MessageContent,
+}
+
+/// Plain text or an array of content blocks
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+/// System prompt as plain text or text blocks
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum SystemPrompt {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    Image {
+        source: ImageSource,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        #[schema(value_type = Object)]
+        input: serde_json::Value,
+    },
+    ToolResult {
+        tool_use_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        content: Option<ToolResultContent>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        is_error: Option<bool>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImageSource {
+    Base64 { media_type: String, data: String },
+    Url { url: String },
+}
+
+/// Tool output as plain text or content blocks
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ToolResultContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MessagesTool {
+    pub name: String,
+    pub description: Option<String>,
+    /// JSON Schema for the tool input
+    #[schema(value_type = Object)]
+    pub input_schema: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum MessagesToolChoice {
+    Auto {
+        #[serde(default)]
+        disable_parallel_tool_use: bool,
+    },
+    /// Use at least one tool
+    Any {
+        #[serde(default)]
+        disable_parallel_tool_use: bool,
+    },
+    /// Use the named tool
+    Tool {
+        name: String,
+        #[serde(default)]
+        disable_parallel_tool_use: bool,
+    },
+    None,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MessagesMetadata {
+    /// End-user identifier
+    pub user_id: Option<String>,
+}
+
+/// Messages response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MessagesResponse {
+    pub id: String,
+    /// Always `message`
+    pub r#type: String,
+    pub role: MessageRole,
+    pub content: Vec<ContentBlock>,
+    pub model: String,
+    /// `end_turn`, `max_tokens`, `stop_sequence`, `tool_use` or `refusal`
+    pub stop_reason: Option<String>,
+    pub stop_sequence: Option<String>,
+    pub usage: MessagesUsage,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MessagesUsage {
+    /// Input tokens not read from the prompt cache
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_read_input_tokens: Option<u32>,
+}
+
+/// Error response in the Anthropic shape
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MessagesErrorResponse {
+    /// Always `error`
+    pub r#type: String,
+    pub error: MessagesError,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MessagesError {
+    pub r#type: String,
+    pub message: String,
+}
+
+// Streaming event structures
+
+/// Server-sent event of a streamed message; the SSE event name is the `type`
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageStreamEvent {
+    MessageStart { message: MessagesResponse },
+    ContentBlockStart { index: u32, content_block: ContentBlock },
+    ContentBlockDelta { index: u32, delta: ContentBlockDelta },
+    ContentBlockStop { index: u32 },
+    MessageDelta { delta: MessageDelta, usage: MessageDeltaUsage },
+    MessageStop,
+    Error { error: MessagesError },
+}
+
+impl MessageStreamEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::MessageStart { .. } => "message_start",
+            Self::ContentBlockStart { .. } => "content_block_start",
+            Self::ContentBlockDelta { .. } => "content_block_delta",
+            Self::ContentBlockStop { .. } => "content_block_stop",
+            Self::MessageDelta { .. } => "message_delta",
+            Self::MessageStop => "message_stop",
+            Self::Error { .. } => "error",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlockDelta {
+    TextDelta { text: String },
+    /// Fragment of a tool_use block's JSON input
+    InputJsonDelta { partial_json: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct MessageDelta {
+    pub stop_reason: Option<String>,
+    pub stop_sequence: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MessageDeltaUsage {
+    pub output_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_read_input_tokens: Option<u32>,
+}
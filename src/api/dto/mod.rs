@@ -1,14 +1,57 @@
 pub mod audio;
+pub mod batch;
 pub mod chat;
+pub mod gemini;
 pub mod health;
+pub mod images;
+pub mod messages;
+pub mod moderation;
+pub mod responses;
+pub mod tokenize;
 
 pub use audio::{
-    ResponseFormatDto, TimestampGranularityDto, TranscribeRequestDto, TranscribeResponseDto,
+    ResponseFormatDto, SpeechFormatDto, SpeechRequestDto, TimestampGranularityDto,
+    TranscribeRequestDto, TranscribeResponseDto, TranscriptionJobDto, TranscriptionJobStatusDto,
     TranscriptionSegmentDto, TranscriptionUsageDto, TranscriptionWordDto,
 };
+pub use batch::{
+    BatchCostDto, BatchDto, BatchErrorDto, BatchErrorsDto, BatchListDto, BatchRequestCountsDto,
+    BatchStatusDto, CreateBatchRequestDto, FileDeletedDto, FileListDto, FileObjectDto,
+};
 pub use chat::{
     ChatChoice, ChatChoiceChunk, ChatCompletionChunk, ChatCompletionRequest,
-    ChatCompletionResponse, ChatDelta, ChatError, ChatErrorResponse, ChatMessage, ChatMetadata,
-    ChatRole, ChatUsage, FinishReason,
+    ChatCompletionResponse, ChatContent, ChatDelta, ChatError, ChatErrorResponse, ChatMessage,
+    ChatMetadata, ChatResponseFormat, ChatRole, ChatTool, ChatUsage, ChoiceLogprobs,
+    CompletionTokensDetails, ContentPart, FinishReason, FunctionCall, FunctionCallDelta,
+    FunctionDefinition, ImageDetail, ImageUrl, InputAudio, JsonSchemaFormat, NamedToolChoice,
+    PromptInjectionMetadata, PromptTokensDetails, ReasoningEffort, StopSequences, StreamOptions,
+    TokenLogprob, TopLogprob, ToolCall, ToolCallDelta, ToolChoice, ToolChoiceFunction,
+    ToolChoiceMode, ToolType, TruncationStrategy,
+};
+pub use gemini::{
+    FileData, FunctionCallingConfig, FunctionDeclaration, GeminiCandidate, GeminiContent,
+    GeminiError, GeminiErrorResponse, GeminiFunctionCall, GeminiFunctionResponse, GeminiPart,
+    GeminiTool, GeminiToolConfig, GenerateContentRequest, GenerateContentResponse,
+    GenerationConfig, InlineData, UsageMetadata,
+};
+pub use health::{DetailedHealthResponse, HealthResponse};
+pub use images::{
+    ImageData, ImageGenerationRequestDto, ImageResponseFormatDto, ImagesMetadata, ImagesResponse,
+};
+pub use messages::{
+    ContentBlock, ContentBlockDelta, ImageSource, InputMessage, MessageContent, MessageDelta,
+    MessageDeltaUsage, MessageRole, MessageStreamEvent, MessagesError, MessagesErrorResponse,
+    MessagesMetadata, MessagesRequest, MessagesResponse, MessagesTool, MessagesToolChoice,
+    MessagesUsage, SystemPrompt, ToolResultContent,
+};
+pub use moderation::{
+    ModerationInput, ModerationInputPart, ModerationRequest, ModerationResponse, ModerationResult,
+};
+pub use responses::{
+    IncompleteDetails, InputContentPart, InputItem, InputItemContent, InputItemMessage,
+    InputTokensDetails, OutputContent, OutputItem, OutputTokensDetails, ReasoningConfig,
+    ResponseDeleted, ResponseEvent, ResponseInput, ResponseObject, ResponseStreamEvent,
+    ResponseTextConfig, ResponseTextFormat, ResponsesRequest, ResponsesTool, ResponsesToolChoice,
+    ResponsesTruncation, ResponsesUsage,
 };
-pub use health::{DetailedHealthResponse, HealthResponse};
\ No newline at end of file
+pub use tokenize::{TokenizeInput, TokenizeRequest, TokenizeResponse};
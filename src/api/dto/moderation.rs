@@ -0,0 +1,60 @@
+//! OpenAI Moderations API DTOs
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::chat::ImageUrl;
+
+/// Moderation request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ModerationRequest {
+    /// Text, texts or multimodal parts to classify
+    pub input: ModerationInput,
+
+    /// Moderation model (default: omni-moderation-latest)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Specific LLM API key to use instead of the project default
+    #[serde(default, skip_serializing)]
+    pub llm_api_key_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ModerationInput {
+    Text(String),
+    Texts(Vec<String>),
+    Parts(Vec<ModerationInputPart>),
+}
+
+/// Multimodal input part (omni-moderation models)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModerationInputPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+}
+
+/// Moderation response, passed through from the provider
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ModerationResponse {
+    pub id: String,
+    pub model: String,
+    /// One result per input
+    pub results: Vec<ModerationResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ModerationResult {
+    pub flagged: bool,
+    /// Whether each category was flagged, e.g. `harassment`, `self-harm/intent`
+    pub categories: HashMap<String, bool>,
+    /// Model confidence per category (0-1)
+    pub category_scores: HashMap<String, f64>,
+    /// Input types (`text`, `image`) that contributed to each category
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_applied_input_types: Option<HashMap<String, Vec<String>>>,
+}
@@ -0,0 +1,351 @@
+//! OpenAI Responses API DTOs
+//! Lets clients on the newer OpenAI SDK wire format call any gateway model
+//! through `/v1/responses`
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::chat::{ImageDetail, ReasoningEffort, ToolChoiceMode, ToolType};
+
+/// Create response request
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResponsesRequest {
+    /// Model identifier
+    pub model: String,
+
+    /// Text prompt or input items
+    pub input: ResponseInput,
+
+    /// System instructions; not carried over by `previous_response_id`
+    pub instructions: Option<String>,
+
+    /// Continue the conversation of a stored response
+    pub previous_response_id: Option<String>,
+
+    /// Tools the model may call
+    pub tools: Option<Vec<ResponsesTool>>,
+
+    pub tool_choice: Option<ResponsesToolChoice>,
+
+    pub parallel_tool_calls: Option<bool>,
+
+    /// Sampling temperature (0-2)
+    pub temperature: Option<f32>,
+
+    /// Nucleus sampling parameter (0-1)
+    pub top_p: Option<f32>,
+
+    /// Maximum tokens to generate, including reasoning tokens
+    pub max_output_tokens: Option<u32>,
+
+    /// Output format
+    pub text: Option<ResponseTextConfig>,
+
+    pub reasoning: Option<ReasoningConfig>,
+
+    /// Whether to stream events (default: false)
+    #[serde(default)]
+    pub stream: bool,
+
+    /// Whether to store the response for later retrieval and chaining (default: true)
+    pub store: Option<bool>,
+
+    pub metadata: Option<HashMap<String, String>>,
+
+    /// End-user identifier
+    pub user: Option<String>,
+
+    /// `auto` drops the oldest turns when the conversation exceeds the
+    /// model's context window; `disabled` (default) fails instead
+    pub truncation: Option<ResponsesTruncation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponsesTruncation {
+    Auto,
+    Disabled,
+}
+
+/// Plain text or an array of input items
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ResponseInput {
+    Text(String),
+    Items(Vec<InputItem>),
+}
+
+/// Conversation item; messages may omit `type`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputItem {
+    Message(InputItemMessage),
+    /// Tool call made by the model in an earlier turn
+    FunctionCall {
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
+    /// Result of a tool call
+    FunctionCallOutput { call_id: String, output: String },
+    #[serde(untagged)]
+    EasyMessage(InputItemMessage),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InputItemMessage {
+    /// `user`, `assistant`, `system` or `developer`
+    pub role: String,
+    pub content: InputItemContent,
+}
+
+/// Plain text or an array of content parts
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum InputItemContent {
+    Text(String),
+    Parts(Vec<InputContentPart>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputContentPart {
+    InputText { text: String },
+    InputImage {
+        /// `https://` URL or `data:image/...;base64,` URL
+        image_url: Option<String>,
+        /// Uploaded file; not supported
+        file_id: Option<String>,
+        detail: Option<ImageDetail>,
+    },
+    /// Assistant text from an earlier turn
+    OutputText { text: String },
+    Refusal { refusal: String },
+}
+
+/// Tool definition; only `function` tools are supported
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesTool {
+    Function {
+        name: String,
+        description: Option<String>,
+        #[schema(value_type = Option<Object>)]
+        parameters: Option<serde_json::Value>,
+        strict: Option<bool>,
+    },
+    #[serde(untagged)]
+    #[schema(value_type = Object)]
+    Other(serde_json::Value),
+}
+
+/// `none`, `auto`, `required` or a specific function
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ResponsesToolChoice {
+    Mode(ToolChoiceMode),
+    Function { r#type: ToolType, name: String },
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResponseTextConfig {
+    pub format: Option<ResponseTextFormat>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseTextFormat {
+    Text,
+    JsonObject,
+    JsonSchema {
+        name: String,
+        description: Option<String>,
+        #[schema(value_type = Object)]
+        schema: serde_json::Value,
+        strict: Option<bool>,
+    },
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReasoningConfig {
+    pub effort: Option<ReasoningEffort>,
+}
+
+/// Response object
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ResponseObject {
+    /// Response ID (`resp_...`), usable as `previous_response_id`
+    pub id: String,
+    /// Always `response`
+    pub object: String,
+    /// Unix timestamp in seconds
+    pub created_at: i64,
+    /// `in_progress`, `completed`, `incomplete` or `failed`
+    pub status: String,
+    pub model: String,
+    pub output: Vec<OutputItem>,
+    pub instructions: Option<String>,
+    pub previous_response_id: Option<String>,
+    pub incomplete_details: Option<IncompleteDetails>,
+    pub usage: Option<ResponsesUsage>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    pub store: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputItem {
+    Message {
+        id: String,
+        /// Always `assistant`
+        role: String,
+        status: String,
+        content: Vec<OutputContent>,
+    },
+    FunctionCall {
+        id: String,
+        /// ID to answer with a `function_call_output` item
+        call_id: String,
+        name: String,
+        /// JSON-encoded arguments
+        arguments: String,
+        status: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputContent {
+    OutputText {
+        text: String,
+        #[schema(value_type = Vec<Object>)]
+        annotations: Vec<serde_json::Value>,
+    },
+    Refusal { refusal: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IncompleteDetails {
+    /// `max_output_tokens` or `content_filter`
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ResponsesUsage {
+    pub input_tokens: u32,
+    pub input_tokens_details: InputTokensDetails,
+    pub output_tokens: u32,
+    pub output_tokens_details: OutputTokensDetails,
+    pub total_tokens: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InputTokensDetails {
+    pub cached_tokens: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OutputTokensDetails {
+    pub reasoning_tokens: u32,
+}
+
+/// Delete response result
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseDeleted {
+    pub id: String,
+    /// Always `response`
+    pub object: String,
+    pub deleted: bool,
+}
+
+// Streaming event structures
+
+/// Server-sent event of a streamed response; the SSE event name is the `type`
+#[derive(Debug, Serialize)]
+pub struct ResponseStreamEvent {
+    pub sequence_number: u64,
+    #[serde(flatten)]
+    pub event: ResponseEvent,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum ResponseEvent {
+    #[serde(rename = "response.created")]
+    Created { response: ResponseObject },
+    #[serde(rename = "response.in_progress")]
+    InProgress { response: ResponseObject },
+    #[serde(rename = "response.output_item.added")]
+    OutputItemAdded { output_index: u32, item: OutputItem },
+    #[serde(rename = "response.content_part.added")]
+    ContentPartAdded {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        part: OutputContent,
+    },
+    #[serde(rename = "response.output_text.delta")]
+    OutputTextDelta {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        delta: String,
+    },
+    #[serde(rename = "response.output_text.done")]
+    OutputTextDone {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        text: String,
+    },
+    #[serde(rename = "response.content_part.done")]
+    ContentPartDone {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        part: OutputContent,
+    },
+    #[serde(rename = "response.function_call_arguments.delta")]
+    FunctionCallArgumentsDelta {
+        item_id: String,
+        output_index: u32,
+        delta: String,
+    },
+    #[serde(rename = "response.function_call_arguments.done")]
+    FunctionCallArgumentsDone {
+        item_id: String,
+        output_index: u32,
+        arguments: String,
+    },
+    #[serde(rename = "response.output_item.done")]
+    OutputItemDone { output_index: u32, item: OutputItem },
+    #[serde(rename = "response.completed")]
+    Completed { response: ResponseObject },
+    #[serde(rename = "response.incomplete")]
+    Incomplete { response: ResponseObject },
+    #[serde(rename = "error")]
+    Error { code: String, message: String },
+}
+
+impl ResponseEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::Created { .. } => "response.created",
+            Self::InProgress { .. } => "response.in_progress",
+            Self::OutputItemAdded { .. } => "response.output_item.added",
+            Self::ContentPartAdded { .. } => "response.content_part.added",
+            Self::OutputTextDelta { .. } => "response.output_text.delta",
+            Self::OutputTextDone { .. } => "response.output_text.done",
+            Self::ContentPartDone { .. } => "response.content_part.done",
+            Self::FunctionCallArgumentsDelta { .. } => "response.function_call_arguments.delta",
+            Self::FunctionCallArgumentsDone { .. } => "response.function_call_arguments.done",
+            Self::OutputItemDone { .. } => "response.output_item.done",
+            Self::Completed { .. } => "response.completed",
+            Self::Incomplete { .. } => "response.incomplete",
+            Self::Error { .. } => "error",
+        }
+    }
+}
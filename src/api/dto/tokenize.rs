@@ -0,0 +1,56 @@
+//! Token counting API DTOs
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::chat::{ChatMessage, ChatTool};
+
+/// Tokenize request: either raw `input` text or a chat `messages` prompt
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TokenizeRequest {
+    /// Model whose tokenizer and context window apply
+    pub model: String,
+
+    /// Text or texts to count
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<TokenizeInput>,
+
+    /// Chat messages to count as a prompt, framing included
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub messages: Option<Vec<ChatMessage>>,
+
+    /// Tool definitions sent with `messages`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ChatTool>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum TokenizeInput {
+    Text(String),
+    Texts(Vec<String>),
+}
+
+/// Token counts, computed exactly as the gateway does for context windows and
+/// `tokens_per_minute` limits
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TokenizeResponse {
+    /// Always `tokenize`
+    pub object: String,
+    pub model: String,
+    /// Vocabulary used: `o200k_base` or `cl100k_base`
+    pub tokenizer: String,
+    /// False when the model's own tokenizer isn't available and the count is
+    /// approximated
+    pub exact: bool,
+    /// Total tokens; for `messages`, the prompt tokens the request is billed for
+    pub token_count: usize,
+    /// Tokens per input text or per message
+    pub counts: Vec<usize>,
+    /// Model context window, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<usize>,
+    /// Token ids, for a single text input on an exact tokenizer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<Vec<u32>>,
+}
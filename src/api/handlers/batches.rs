@@ -0,0 +1,159 @@
+//! OpenAI Batch-compatible handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::dto::{BatchDto, BatchListDto, CreateBatchRequestDto};
+use crate::domain::entities::Project;
+use crate::shared::error::AppError;
+use crate::AppState;
+
+/// Create a batch
+///
+/// Queues the requests of an uploaded `batch` file. The gateway runs them
+/// on its worker pool within the project's rate limits and writes the
+/// results to `output_file_id` and `error_file_id`.
+#[utoipa::path(
+    post,
+    path = "/v1/batches",
+    tag = "Batches",
+    request_body = CreateBatchRequestDto,
+    responses(
+        (status = 200, description = "Batch queued", body = BatchDto),
+        (status = 400, description = "Bad request - invalid endpoint, window or input file"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Input file not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn create_batch(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Json(request): Json<CreateBatchRequestDto>,
+) -> Result<Json<BatchDto>, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let batch = state.batch_service
+        .create_batch(
+            project_id,
+            request.input_file_id,
+            request.endpoint,
+            &request.completion_window,
+            request.metadata.unwrap_or_default(),
+        )
+        .await?;
+
+    Ok(Json(BatchDto::from(batch)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListBatchesQuery {
+    pub after: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// List batches
+#[utoipa::path(
+    get,
+    path = "/v1/batches",
+    tag = "Batches",
+    params(
+        ("after" = Option<String>, Query, description = "Batch ID to continue after"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of batches (1-100, default 20)")
+    ),
+    responses(
+        (status = 200, description = "Batches, newest first", body = BatchListDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "after batch not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn list_batches(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Query(query): Query<ListBatchesQuery>,
+) -> Result<Json<BatchListDto>, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let (batches, has_more) = state.batch_service
+        .list_batches(&project_id, query.after.as_deref(), limit)
+        .await?;
+
+    let data: Vec<BatchDto> = batches.into_iter().map(BatchDto::from).collect();
+    Ok(Json(BatchListDto {
+        object: "list".to_string(),
+        first_id: data.first().map(|b| b.id.clone()),
+        last_id: data.last().map(|b| b.id.clone()),
+        data,
+        has_more,
+    }))
+}
+
+/// Get a batch
+#[utoipa::path(
+    get,
+    path = "/v1/batches/{batch_id}",
+    tag = "Batches",
+    params(
+        ("batch_id" = String, Path, description = "Batch ID")
+    ),
+    responses(
+        (status = 200, description = "Batch state and progress", body = BatchDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Batch not found")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn get_batch(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Path(batch_id): Path<String>,
+) -> Result<Json<BatchDto>, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let batch = state.batch_service.get_batch(&project_id, &batch_id).await?;
+
+    Ok(Json(BatchDto::from(batch)))
+}
+
+/// Cancel a batch
+///
+/// A queued batch is cancelled immediately; a running one moves to
+/// `cancelling` and keeps the results gathered so far.
+#[utoipa::path(
+    post,
+    path = "/v1/batches/{batch_id}/cancel",
+    tag = "Batches",
+    params(
+        ("batch_id" = String, Path, description = "Batch ID")
+    ),
+    responses(
+        (status = 200, description = "Batch cancelling or cancelled", body = BatchDto),
+        (status = 400, description = "Batch already finished"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Batch not found")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn cancel_batch(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Path(batch_id): Path<String>,
+) -> Result<Json<BatchDto>, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let batch = state.batch_service.cancel_batch(&project_id, &batch_id).await?;
+
+    Ok(Json(BatchDto::from(batch)))
+}
@@ -1,12 +1,31 @@
-/// Chat completions handler
-/// Based on CID specification: cid/rest-api/gateway/chat.yaml
+//! Chat completions handler
+//! Based on CID specification: cid/rest-api/gateway/chat.yaml
 
-use axum::{extract::State, http::StatusCode, response::Json};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+    Extension,
+};
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+use std::convert::Infallible;
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{debug, error, info, warn};
 
-use crate::api::dto::{ChatCompletionRequest, ChatCompletionResponse, ChatErrorResponse, ChatError};
+use crate::api::dto::{
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatError, ChatErrorResponse,
+    StreamOptions,
+};
+use crate::domain::entities::guardrail::{ModerationAction, ModerationPolicy};
+use crate::domain::entities::Project;
+use crate::domain::services::chat_guardrails::{policy_violation, GuardedRequest};
+use crate::domain::services::moderation::{self, ModerationService};
 use crate::domain::services::providers::OpenAIProvider;
+use crate::domain::services::structured_output::{self, EmulatedFormat};
 use crate::shared::error::AppError;
 use crate::AppState;
 
@@ -19,9 +38,10 @@ use crate::AppState;
     tag = "Chat Completions",
     request_body = ChatCompletionRequest,
     responses(
-        (status = 200, description = "Chat completion successful", body = ChatCompletionResponse),
+        (status = 200, description = "Chat completion successful; a text/event-stream of ChatCompletionChunk when stream is true", body = ChatCompletionResponse),
         (status = 400, description = "Bad request - invalid parameters", body = ChatErrorResponse),
         (status = 401, description = "Unauthorized - invalid API key", body = ChatErrorResponse),
+        (status = 422, description = "Output never matched the requested response_format", body = ChatErrorResponse),
         (status = 429, description = "Rate limit exceeded", body = ChatErrorResponse),
         (status = 500, description = "Internal server error", body = ChatErrorResponse),
         (status = 503, description = "Service unavailable - all providers down", body = ChatErrorResponse)
@@ -31,10 +51,16 @@ use crate::AppState;
     )
 )]
 pub async fn create_chat_completion(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
     Json(request): Json<ChatCompletionRequest>,
-) -> Result<Json<ChatCompletionResponse>, (StatusCode, Json<ChatErrorResponse>)> {
-    info!("Chat completion request: model={}, messages={}", request.model, request.messages.len());
+) -> Result<Response, (StatusCode, Json<ChatErrorResponse>)> {
+    info!(
+        "Chat completion request: model={}, messages={}, estimated image tokens={}",
+        request.model,
+        request.messages.len(),
+        request.estimated_image_tokens()
+    );
 
     // Validate request
     if let Err(e) = request.validate() {
@@ -51,64 +77,257 @@ pub async fn create_chat_completion(
         ));
     }
 
+    match dispatch(&state, &project, "/v1/chat/completions", request).await {
+        Ok(ChatOutput::Complete(response)) => {
+            info!("Chat completion successful: id={}, usage={} tokens",
+                response.id, response.usage.total_tokens);
+
+            Ok(Json::<ChatCompletionResponse>(*response).into_response())
+        }
+        Ok(ChatOutput::Stream(chunks)) => {
+            // Errors after the stream has started can only be reported in-band
+            let events = chunks
+                .map(|chunk| {
+                    let event = match chunk {
+                        Ok(chunk) => Event::default().json_data(chunk),
+                        Err(e) => {
+                            error!("Chat completion stream interrupted: {}", e);
+                            let (_, Json(body)) = chat_error(e);
+                            Event::default().json_data(body)
+                        }
+                    };
+                    Ok::<_, Infallible>(event.unwrap_or_default())
+                })
+                .chain(futures::stream::once(async {
+                    Ok(Event::default().data("[DONE]"))
+                }));
+
+            Ok(Sse::new(events).keep_alive(KeepAlive::default()).into_response())
+        }
+        Err(e) => {
+            error!("Chat completion failed: {}", e);
+            Err(chat_error(e))
+        }
+    }
+}
+
+/// Provider output for a chat request
+pub(crate) enum ChatOutput {
+    Complete(Box<ChatCompletionResponse>),
+    Stream(BoxStream<'static, Result<ChatCompletionChunk, AppError>>),
+}
+
+/// Route a validated chat request to its provider and log its usage; shared
+/// by every ingress API that is translated to chat completions
+pub(crate) async fn dispatch(
+    state: &AppState,
+    project: &Project,
+    path: &str,
+    mut request: ChatCompletionRequest,
+) -> Result<ChatOutput, AppError> {
+    if !request.extra.is_empty() && !state.config.chat.forward_unknown_fields {
+        debug!("Ignoring unknown chat parameters: {:?}", request.extra.keys().collect::<Vec<_>>());
+        request.extra.clear();
+    }
+
     // TODO: Get project from authentication context
     // For now, we'll use a default OpenAI API key from environment
     let openai_api_key = std::env::var("OPENAI_API_KEY").map_err(|_| {
         error!("OPENAI_API_KEY not set");
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ChatErrorResponse {
-                error: ChatError {
-                    r#type: "configuration_error".to_string(),
-                    message: "OpenAI API key not configured".to_string(),
-                    code: "missing_api_key".to_string(),
-                },
-            }),
-        )
+        AppError::ConfigError("OpenAI API key not configured".to_string())
     })?;
 
     // TODO: Implement intelligent routing based on model
     // For now, route all requests to OpenAI
     let provider = OpenAIProvider::new();
 
-    // Call provider
-    match provider.chat_completion(&openai_api_key, &request).await {
-        Ok(response) => {
-            info!("Chat completion successful: id={}, usage={} tokens",
-                response.id, response.usage.total_tokens);
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let guarded = state
+        .chat_guardrails
+        .check(&project_id, path, &openai_api_key, &mut request)
+        .await?;
+    if guarded.input_filtered {
+        return Ok(if request.stream {
+            ChatOutput::Stream(stream::iter([Ok(moderation::filtered_chunk(&request))]).boxed())
+        } else {
+            ChatOutput::Complete(Box::new(moderation::filtered_response(&request)))
+        });
+    }
 
-            // TODO: Log usage to database for cost tracking
+    // Tokens are reserved only for requests that passed the guardrails, and
+    // the recorder corrects the estimate to actual usage
+    let tokens_per_minute = project.rate_limits.as_ref().and_then(|limits| limits.tokens_per_minute);
+    let reservation = state.token_rate_limiter.reserve(
+        &project_id,
+        tokens_per_minute,
+        guarded.reserved_tokens(&request),
+    )?;
+    let guarded = guarded.with_token_reservation(reservation);
 
-            Ok(Json(response))
+    if request.stream {
+        // Emulated formats are validated on the whole reply, which streaming can't wait for
+        if EmulatedFormat::for_request(&request)?.is_some() {
+            return Err(AppError::BadRequest(format!(
+                "response_format requires stream=false on model {}",
+                request.model
+            )));
         }
-        Err(e) => {
-            error!("Chat completion failed: {}", e);
 
-            let (status, error_type, code) = match &e {
-                AppError::ExternalApiError(msg) if msg.contains("401") || msg.contains("authentication") => {
-                    (StatusCode::UNAUTHORIZED, "authentication_error", "invalid_api_key")
-                }
-                AppError::ExternalApiError(msg) if msg.contains("429") || msg.contains("rate_limit") => {
-                    (StatusCode::TOO_MANY_REQUESTS, "rate_limit_error", "rate_limit_exceeded")
-                }
-                AppError::ExternalApiError(msg) if msg.contains("400") => {
-                    (StatusCode::BAD_REQUEST, "invalid_request_error", "invalid_request")
+        // Usage is always requested so that every stream is logged; it arrives on
+        // the final chunk, which is withheld from clients that didn't ask for it
+        let client_usage = request
+            .stream_options
+            .as_ref()
+            .and_then(|options| options.include_usage)
+            .unwrap_or(false);
+        request.stream_options = Some(StreamOptions {
+            include_usage: Some(true),
+        });
+
+        let GuardedRequest { recorder, vault, moderation, .. } = guarded;
+        let mut finish_reason = None;
+        let chunks = provider
+            .chat_completion_stream(&openai_api_key, &request)
+            .await?
+            .inspect(move |chunk| {
+                let Ok(chunk) = chunk else {
+                    return;
+                };
+                if let Some(reason) = chunk.choices.iter().find_map(|c| c.finish_reason.clone()) {
+                    finish_reason = Some(reason);
                 }
-                _ => {
-                    (StatusCode::INTERNAL_SERVER_ERROR, "api_error", "provider_error")
+                if let Some(usage) = &chunk.usage {
+                    recorder.record(usage, finish_reason.as_ref());
                 }
-            };
-
-            Err((
-                status,
-                Json(ChatErrorResponse {
-                    error: ChatError {
-                        r#type: error_type.to_string(),
-                        message: e.to_string(),
-                        code: code.to_string(),
-                    },
-                }),
-            ))
+            })
+            .boxed();
+        let chunks = match moderation.filter(|p| p.output) {
+            Some(policy) => moderate_stream(
+                state.moderation_service.clone(),
+                openai_api_key,
+                policy,
+                chunks,
+            ),
+            None => chunks,
+        };
+        let chunks = match vault {
+            Some(vault) => vault.restore_stream(chunks),
+            None => chunks,
+        };
+        let chunks = if client_usage {
+            chunks
+        } else {
+            chunks
+                .try_filter_map(|mut chunk| async move {
+                    if chunk.usage.take().is_some() && chunk.choices.is_empty() {
+                        return Ok(None);
+                    }
+                    Ok(Some(chunk))
+                })
+                .boxed()
+        };
+        return Ok(ChatOutput::Stream(chunks));
+    }
+
+    let mut response =
+        structured_output::chat_completion(&provider, &openai_api_key, &request, &guarded.recorder).await?;
+    state.chat_guardrails.finish(&guarded, &openai_api_key, &mut response).await?;
+
+    Ok(ChatOutput::Complete(Box::new(response)))
+}
+
+/// Buffer a streamed reply and release it only once it passes moderation
+fn moderate_stream(
+    service: Arc<ModerationService>,
+    api_key: String,
+    policy: ModerationPolicy,
+    chunks: BoxStream<'static, Result<ChatCompletionChunk, AppError>>,
+) -> BoxStream<'static, Result<ChatCompletionChunk, AppError>> {
+    stream::once(async move {
+        let chunks: Vec<ChatCompletionChunk> = match chunks.try_collect().await {
+            Ok(chunks) => chunks,
+            Err(e) => return vec![Err(e)],
+        };
+        let categories = match service.check(&api_key, &policy, moderation::chunk_texts(&chunks)).await {
+            Ok(categories) => categories,
+            Err(e) => return vec![Err(e)],
+        };
+        if categories.is_empty() {
+            return chunks.into_iter().map(Ok).collect();
+        }
+
+        warn!("Chat reply blocked by moderation: {}", categories.join(", "));
+        match policy.action {
+            ModerationAction::Reject => vec![Err(policy_violation("Output", &categories))],
+            ModerationAction::ContentFilter => {
+                moderation::filter_chunks(chunks).into_iter().map(Ok).collect()
+            }
+        }
+    })
+    .flat_map(stream::iter)
+    .boxed()
+}
+
+/// Map a provider error to an OpenAI-style error response
+pub(crate) fn chat_error(e: AppError) -> (StatusCode, Json<ChatErrorResponse>) {
+    let (status, error_type, code) = match &e {
+        AppError::ExternalApiError(msg) if msg.contains("401") || msg.contains("authentication") => {
+            (StatusCode::UNAUTHORIZED, "authentication_error", "invalid_api_key")
+        }
+        AppError::ExternalApiError(msg) if msg.contains("429") || msg.contains("rate_limit") => {
+            (StatusCode::TOO_MANY_REQUESTS, "rate_limit_error", "rate_limit_exceeded")
+        }
+        AppError::RateLimitError(_) => {
+            (StatusCode::TOO_MANY_REQUESTS, "rate_limit_error", "rate_limit_exceeded")
         }
+        AppError::ConfigError(_) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "configuration_error", "missing_api_key")
+        }
+        AppError::BadRequest(_) | AppError::ValidationError(_) => {
+            (StatusCode::BAD_REQUEST, "invalid_request_error", "invalid_request")
+        }
+        AppError::ContentPolicyViolation(_) => {
+            (StatusCode::BAD_REQUEST, "invalid_request_error", "content_policy_violation")
+        }
+        AppError::ContextLengthExceeded(_) => {
+            (StatusCode::BAD_REQUEST, "invalid_request_error", "context_length_exceeded")
+        }
+        AppError::NotFound(_) => {
+            (StatusCode::NOT_FOUND, "invalid_request_error", "not_found")
+        }
+        AppError::StructuredOutputError(_) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, "invalid_response_error", "response_format_not_met")
+        }
+        AppError::ExternalApiError(msg) if msg.contains("400") => {
+            (StatusCode::BAD_REQUEST, "invalid_request_error", "invalid_request")
+        }
+        _ => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "api_error", "provider_error")
+        }
+    };
+
+    (
+        status,
+        Json(ChatErrorResponse {
+            error: ChatError {
+                r#type: error_type.to_string(),
+                message: e.to_string(),
+                code: code.to_string(),
+            },
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_gateway_rate_limits_to_429() {
+        let (status, Json(body)) =
+            chat_error(AppError::RateLimitError("Project exceeded 1000 tokens per minute".to_string()));
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body.error.r#type, "rate_limit_error");
+        assert_eq!(body.error.code, "rate_limit_exceeded");
     }
 }
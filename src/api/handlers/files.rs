@@ -0,0 +1,212 @@
+//! OpenAI Files-compatible handlers
+
+use axum::{
+    extract::{Multipart, Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::dto::{FileDeletedDto, FileListDto, FileObjectDto};
+use crate::domain::entities::Project;
+use crate::shared::error::AppError;
+use crate::shared::utils::UploadSpool;
+use crate::AppState;
+
+/// Upload a file
+///
+/// Files with purpose `batch` must be JSONL batch input; every line is
+/// validated on upload.
+#[utoipa::path(
+    post,
+    path = "/v1/files",
+    tag = "Files",
+    request_body(content = String, content_type = "multipart/form-data", description = "`file` and `purpose` form fields"),
+    responses(
+        (status = 200, description = "File stored", body = FileObjectDto),
+        (status = 400, description = "Bad request - invalid purpose or batch input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 413, description = "File too large"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn upload_file(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    mut multipart: Multipart,
+) -> Result<Json<FileObjectDto>, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let max_file_bytes = state.config.batch.max_file_size_mb as usize * 1024 * 1024;
+
+    let mut upload = None;
+    let mut filename = String::new();
+    let mut purpose = None;
+    while let Some(mut field) = multipart.next_field().await.map_err(|e| {
+        AppError::BadRequest(format!("Failed to read multipart field: {}", e))
+    })? {
+        match field.name().unwrap_or_default() {
+            "file" => {
+                filename = field.file_name().unwrap_or("file").to_string();
+                let mut spool = UploadSpool::new(max_file_bytes)?;
+                while let Some(chunk) = field.chunk().await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read file data: {}", e)))?
+                {
+                    spool.write(&chunk).await?;
+                }
+                upload = Some(spool.finish().await?);
+            }
+            "purpose" => {
+                purpose = Some(field.text().await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read purpose: {}", e)))?);
+            }
+            _ => {}
+        }
+    }
+
+    let upload = upload.ok_or_else(|| AppError::BadRequest("Missing file".to_string()))?;
+    let purpose = purpose.ok_or_else(|| AppError::BadRequest("Missing purpose".to_string()))?;
+    let file = state.batch_service
+        .upload_file(project_id, filename, purpose, &upload.data)
+        .await?;
+
+    Ok(Json(FileObjectDto::from(file)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListFilesQuery {
+    pub purpose: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// List files
+#[utoipa::path(
+    get,
+    path = "/v1/files",
+    tag = "Files",
+    params(
+        ("purpose" = Option<String>, Query, description = "Only return files with this purpose"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of files (1-10000, default 10000)")
+    ),
+    responses(
+        (status = 200, description = "Files, newest first", body = FileListDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn list_files(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Query(query): Query<ListFilesQuery>,
+) -> Result<Json<FileListDto>, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let limit = query.limit.unwrap_or(10000).clamp(1, 10000);
+    let files = state.batch_service
+        .list_files(&project_id, query.purpose.as_deref(), limit)
+        .await?;
+
+    Ok(Json(FileListDto {
+        object: "list".to_string(),
+        data: files.into_iter().map(FileObjectDto::from).collect(),
+    }))
+}
+
+/// Get a file
+#[utoipa::path(
+    get,
+    path = "/v1/files/{file_id}",
+    tag = "Files",
+    params(
+        ("file_id" = String, Path, description = "File ID")
+    ),
+    responses(
+        (status = 200, description = "File metadata", body = FileObjectDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "File not found")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn get_file(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Path(file_id): Path<String>,
+) -> Result<Json<FileObjectDto>, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let file = state.batch_service.get_file(&project_id, &file_id).await?;
+
+    Ok(Json(FileObjectDto::from(file)))
+}
+
+/// Download file content
+#[utoipa::path(
+    get,
+    path = "/v1/files/{file_id}/content",
+    tag = "Files",
+    params(
+        ("file_id" = String, Path, description = "File ID")
+    ),
+    responses(
+        (status = 200, description = "File content", content_type = "application/octet-stream"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "File not found")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn get_file_content(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Path(file_id): Path<String>,
+) -> Result<Response, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let (file, content) = state.batch_service.file_content(&project_id, &file_id).await?;
+
+    let content_type = if file.filename.ends_with(".jsonl") {
+        "application/jsonl"
+    } else {
+        "application/octet-stream"
+    };
+    Ok(([(header::CONTENT_TYPE, content_type)], content).into_response())
+}
+
+/// Delete a file
+#[utoipa::path(
+    delete,
+    path = "/v1/files/{file_id}",
+    tag = "Files",
+    params(
+        ("file_id" = String, Path, description = "File ID")
+    ),
+    responses(
+        (status = 200, description = "File deleted", body = FileDeletedDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "File not found")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn delete_file(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Path(file_id): Path<String>,
+) -> Result<Json<FileDeletedDto>, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    state.batch_service.delete_file(&project_id, &file_id).await?;
+
+    Ok(Json(FileDeletedDto {
+        id: file_id,
+        object: "file".to_string(),
+        deleted: true,
+    }))
+}
@@ -0,0 +1,171 @@
+//! Gemini generateContent-compatible handler
+
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+    Extension,
+};
+use futures::StreamExt;
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tracing::{error, info};
+
+use crate::api::dto::{
+    GeminiError, GeminiErrorResponse, GenerateContentRequest, GenerateContentResponse,
+};
+use crate::api::handlers::chat::{chat_error, dispatch, ChatOutput};
+use crate::domain::entities::Project;
+use crate::domain::services::gemini::{self, GeminiStream};
+use crate::shared::error::AppError;
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct GeminiQuery {
+    /// `sse` streams server-sent events instead of a JSON array
+    alt: Option<String>,
+}
+
+/// Generate content
+///
+/// Gemini-compatible API for `{model}:generateContent` and
+/// `{model}:streamGenerateContent`. Requests are translated to chat
+/// completions and routed like `/v1/chat/completions`, so Google GenAI SDK
+/// clients can use any gateway model. The project key may be sent as
+/// `x-goog-api-key` or the `key` query parameter.
+#[utoipa::path(
+    post,
+    path = "/v1beta/models/{model_action}",
+    tag = "Gemini",
+    params(
+        ("model_action" = String, Path, description = "Model and method, e.g. `gpt-4o:generateContent` or `gpt-4o:streamGenerateContent`"),
+        ("alt" = Option<String>, Query, description = "`sse` to stream server-sent events; streams are otherwise a JSON array")
+    ),
+    request_body = GenerateContentRequest,
+    responses(
+        (status = 200, description = "Content generated; streamed as GenerateContentResponse events for streamGenerateContent", body = GenerateContentResponse),
+        (status = 400, description = "Bad request - invalid parameters", body = GeminiErrorResponse),
+        (status = 401, description = "Unauthorized - invalid API key", body = GeminiErrorResponse),
+        (status = 404, description = "Unknown method", body = GeminiErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = GeminiErrorResponse),
+        (status = 500, description = "Internal server error", body = GeminiErrorResponse)
+    ),
+    security(
+        ("projectApiKey" = [])
+    )
+)]
+pub async fn generate_content(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Path(model_action): Path<String>,
+    Query(query): Query<GeminiQuery>,
+    Json(request): Json<GenerateContentRequest>,
+) -> Result<Response, (StatusCode, Json<GeminiErrorResponse>)> {
+    let (model, stream) = match model_action.rsplit_once(':') {
+        Some((model, "generateContent")) => (model.to_string(), false),
+        Some((model, "streamGenerateContent")) => (model.to_string(), true),
+        _ => {
+            return Err(error_response(
+                StatusCode::NOT_FOUND,
+                format!("Unknown method: {}", model_action),
+            ))
+        }
+    };
+    info!("Gemini request: model={}, contents={}, stream={}", model, request.contents.len(), stream);
+
+    let chat_request = gemini::to_chat_request(model, request, stream).map_err(gemini_error)?;
+    if let Err(e) = chat_request.validate() {
+        error!("Invalid Gemini request: {}", e);
+        return Err(error_response(StatusCode::BAD_REQUEST, e));
+    }
+
+    let path = format!("/v1beta/models/{}", model_action);
+    match dispatch(&state, &project, &path, chat_request).await {
+        Ok(ChatOutput::Complete(response)) => {
+            let response = gemini::from_chat_response(*response);
+            info!("Gemini content generated: id={}", response.response_id);
+            Ok(Json::<GenerateContentResponse>(response).into_response())
+        }
+        Ok(ChatOutput::Stream(chunks)) => {
+            let mut stream = GeminiStream::new();
+            let responses = chunks
+                .map(Some)
+                .chain(futures::stream::once(async { None }))
+                .filter_map(move |chunk| {
+                    let item = match chunk {
+                        Some(Ok(chunk)) => stream.push(chunk).map(serde_json::to_value),
+                        Some(Err(e)) => {
+                            // Errors after the stream has started can only be reported in-band
+                            error!("Gemini stream interrupted: {}", e);
+                            let (_, Json(body)) = gemini_error(e);
+                            Some(serde_json::to_value(body))
+                        }
+                        None => Some(serde_json::to_value(stream.finish())),
+                    };
+                    futures::future::ready(item.and_then(Result::ok))
+                });
+
+            if query.alt.as_deref() == Some("sse") {
+                let events = responses.map(|response| {
+                    Ok::<_, Infallible>(Event::default().json_data(response).unwrap_or_default())
+                });
+                return Ok(Sse::new(events).keep_alive(KeepAlive::default()).into_response());
+            }
+
+            // Without alt=sse the stream is one JSON array, sent element by element
+            let body = responses
+                .enumerate()
+                .map(|(i, response)| {
+                    let separator = if i == 0 { "[" } else { ",\r\n" };
+                    format!("{}{}", separator, response)
+                })
+                .chain(futures::stream::once(async { "]".to_string() }))
+                .map(Ok::<_, Infallible>);
+
+            Ok((
+                [(header::CONTENT_TYPE, "application/json")],
+                Body::from_stream(body),
+            )
+                .into_response())
+        }
+        Err(e) => {
+            error!("Gemini request failed: {}", e);
+            Err(gemini_error(e))
+        }
+    }
+}
+
+fn error_response(status: StatusCode, message: String) -> (StatusCode, Json<GeminiErrorResponse>) {
+    let canonical = match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => "INVALID_ARGUMENT",
+        StatusCode::UNAUTHORIZED => "UNAUTHENTICATED",
+        StatusCode::FORBIDDEN => "PERMISSION_DENIED",
+        StatusCode::NOT_FOUND => "NOT_FOUND",
+        StatusCode::TOO_MANY_REQUESTS => "RESOURCE_EXHAUSTED",
+        StatusCode::SERVICE_UNAVAILABLE => "UNAVAILABLE",
+        StatusCode::GATEWAY_TIMEOUT => "DEADLINE_EXCEEDED",
+        _ => "INTERNAL",
+    };
+
+    (
+        status,
+        Json(GeminiErrorResponse {
+            error: GeminiError {
+                code: status.as_u16(),
+                message,
+                status: canonical.to_string(),
+            },
+        }),
+    )
+}
+
+/// Map an error to a Google-style error response
+fn gemini_error(e: AppError) -> (StatusCode, Json<GeminiErrorResponse>) {
+    let (status, Json(body)) = chat_error(e);
+    error_response(status, body.error.message)
+}
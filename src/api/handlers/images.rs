@@ -0,0 +1,205 @@
+use axum::{
+    extract::{Multipart, State},
+    Extension, Json,
+};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::api::dto::images::validate_n;
+use crate::api::dto::{ImageGenerationRequestDto, ImagesResponse};
+use crate::domain::entities::image::{ImageOperation, ImageRequest, ImageUpload};
+use crate::domain::entities::Project;
+use crate::shared::error::AppError;
+use crate::shared::utils::UploadSpool;
+use crate::AppState;
+
+/// Form fields forwarded to the provider as given
+const EDIT_OPTION_FIELDS: [&str; 6] = [
+    "response_format",
+    "background",
+    "output_format",
+    "output_compression",
+    "input_fidelity",
+    "user",
+];
+
+/// Image generation handler
+///
+/// OpenAI-compatible; images are returned as URLs or `b64_json` as the
+/// provider sends them, with the per-image cost in `x_llmhub`
+#[utoipa::path(
+    post,
+    path = "/v1/images/generations",
+    tag = "Images",
+    request_body = ImageGenerationRequestDto,
+    responses(
+        (status = 200, description = "Images generated", body = ImagesResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 429, description = "Project rate limit exceeded"),
+        (status = 500, description = "Internal server error"),
+        (status = 502, description = "Provider error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn create_image(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Json(request_dto): Json<ImageGenerationRequestDto>,
+) -> Result<Json<ImagesResponse>, AppError> {
+    request_dto.validate().map_err(AppError::ValidationError)?;
+
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let rate_limits = project.rate_limits.clone().unwrap_or_default();
+    let request = ImageRequest::from(request_dto);
+
+    info!("Image generation request: model={}, n={}", request.model, request.n);
+
+    let response = state.image_service.create(project_id, &rate_limits, request).await?;
+    Ok(Json(response))
+}
+
+/// Image edit handler
+///
+/// Multipart form with `image` (or several `image[]`), optional `mask`,
+/// `prompt` and the generation parameters
+#[utoipa::path(
+    post,
+    path = "/v1/images/edits",
+    tag = "Images",
+    request_body(content = String, content_type = "multipart/form-data", description = "`image`, `mask`, `prompt`, `model`, `n`, `size`, `quality`, `response_format` and other generation fields"),
+    responses(
+        (status = 200, description = "Edited images", body = ImagesResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 413, description = "Image too large"),
+        (status = 415, description = "Not a PNG, JPEG or WebP image"),
+        (status = 429, description = "Project rate limit exceeded"),
+        (status = 502, description = "Provider error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn create_image_edit(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    multipart: Multipart,
+) -> Result<Json<ImagesResponse>, AppError> {
+    image_form(state, project, multipart, ImageOperation::Edit).await
+}
+
+/// Image variation handler
+///
+/// Multipart form with a single `image`; dall-e-2 only upstream
+#[utoipa::path(
+    post,
+    path = "/v1/images/variations",
+    tag = "Images",
+    request_body(content = String, content_type = "multipart/form-data", description = "`image`, `model`, `n`, `size`, `response_format` and `user`"),
+    responses(
+        (status = 200, description = "Image variations", body = ImagesResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 413, description = "Image too large"),
+        (status = 415, description = "Not a PNG, JPEG or WebP image"),
+        (status = 429, description = "Project rate limit exceeded"),
+        (status = 502, description = "Provider error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn create_image_variation(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    multipart: Multipart,
+) -> Result<Json<ImagesResponse>, AppError> {
+    image_form(state, project, multipart, ImageOperation::Variation).await
+}
+
+/// Read an edit or variation form, spooling images under the project's file size limit
+async fn image_form(
+    state: Arc<AppState>,
+    project: Project,
+    mut multipart: Multipart,
+    operation: ImageOperation,
+) -> Result<Json<ImagesResponse>, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let rate_limits = project.rate_limits.clone().unwrap_or_default();
+    let max_file_bytes = rate_limits.max_file_size_mb as usize * 1024 * 1024;
+
+    let mut request = ImageRequest {
+        operation,
+        model: "dall-e-2".to_string(),
+        prompt: None,
+        n: 1,
+        size: None,
+        quality: None,
+        options: serde_json::Map::new(),
+        images: Vec::new(),
+        mask: None,
+        llm_api_key_id: None,
+    };
+
+    while let Some(mut field) = multipart.next_field().await.map_err(|e| {
+        AppError::BadRequest(format!("Failed to read multipart field: {}", e))
+    })? {
+        let field_name = field
+            .name()
+            .ok_or_else(|| AppError::BadRequest("Missing field name".to_string()))?
+            .to_string();
+
+        if matches!(field_name.as_str(), "image" | "image[]" | "mask") {
+            let file_name = field.file_name().unwrap_or("image.png").to_string();
+            let mut spool = UploadSpool::new(max_file_bytes)?;
+            while let Some(chunk) = field.chunk().await
+                .map_err(|e| AppError::BadRequest(format!("Failed to read {}: {}", field_name, e)))?
+            {
+                spool.write(&chunk).await?;
+            }
+            let upload = ImageUpload {
+                file_name,
+                data: spool.finish().await?.data,
+            };
+            if field_name == "mask" {
+                request.mask = Some(upload);
+            } else {
+                request.images.push(upload);
+            }
+            continue;
+        }
+
+        let value = field.text().await
+            .map_err(|e| AppError::BadRequest(format!("Failed to read {}: {}", field_name, e)))?;
+        match field_name.as_str() {
+            "model" => request.model = value,
+            "prompt" => request.prompt = Some(value),
+            "n" => {
+                let n = value.parse().map_err(|_| AppError::BadRequest(format!("Invalid n: {}", value)))?;
+                validate_n(Some(n)).map_err(AppError::ValidationError)?;
+                request.n = n;
+            }
+            "size" => request.size = Some(value),
+            "quality" => request.quality = Some(value),
+            "llm_api_key_id" => request.llm_api_key_id = Some(value),
+            name if EDIT_OPTION_FIELDS.contains(&name) => {
+                request.options.insert(field_name, value.into());
+            }
+            _ => {}
+        }
+    }
+
+    info!(
+        "Image {:?} request: model={}, images={}, n={}",
+        operation,
+        request.model,
+        request.images.len(),
+        request.n
+    );
+
+    let response = state.image_service.create(project_id, &rate_limits, request).await?;
+    Ok(Json(response))
+}
@@ -0,0 +1,126 @@
+//! Anthropic Messages-compatible handler
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+    Extension,
+};
+use futures::StreamExt;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tracing::{error, info};
+
+use crate::api::dto::{
+    MessageStreamEvent, MessagesError, MessagesErrorResponse, MessagesRequest, MessagesResponse,
+};
+use crate::api::handlers::chat::{chat_error, dispatch, ChatOutput};
+use crate::domain::entities::Project;
+use crate::domain::services::messages::{self, MessageStream};
+use crate::shared::error::AppError;
+use crate::AppState;
+
+/// Create a message
+///
+/// Anthropic Messages-compatible API. Requests are translated to chat
+/// completions and routed like `/v1/chat/completions`, so Anthropic SDK clients
+/// can use any gateway model. The project key may be sent as `x-api-key`.
+#[utoipa::path(
+    post,
+    path = "/v1/messages",
+    tag = "Messages",
+    request_body = MessagesRequest,
+    responses(
+        (status = 200, description = "Message created; a text/event-stream of message events when stream is true", body = MessagesResponse),
+        (status = 400, description = "Bad request - invalid parameters", body = MessagesErrorResponse),
+        (status = 401, description = "Unauthorized - invalid API key", body = MessagesErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = MessagesErrorResponse),
+        (status = 500, description = "Internal server error", body = MessagesErrorResponse)
+    ),
+    security(
+        ("projectApiKey" = [])
+    )
+)]
+pub async fn create_message(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Json(request): Json<MessagesRequest>,
+) -> Result<Response, (StatusCode, Json<MessagesErrorResponse>)> {
+    info!("Messages request: model={}, messages={}", request.model, request.messages.len());
+
+    let chat_request = messages::to_chat_request(request).map_err(messages_error)?;
+    if let Err(e) = chat_request.validate() {
+        error!("Invalid messages request: {}", e);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(error_response("invalid_request_error", e)),
+        ));
+    }
+
+    match dispatch(&state, &project, "/v1/messages", chat_request).await {
+        Ok(ChatOutput::Complete(response)) => {
+            let message = messages::from_chat_response(*response);
+            info!("Message created: id={}", message.id);
+            Ok(Json::<MessagesResponse>(message).into_response())
+        }
+        Ok(ChatOutput::Stream(chunks)) => {
+            let mut stream = MessageStream::new();
+            let events = chunks
+                .map(Some)
+                .chain(futures::stream::once(async { None }))
+                .flat_map(move |chunk| {
+                    let events = match chunk {
+                        Some(Ok(chunk)) => stream.push(chunk),
+                        Some(Err(e)) => {
+                            // Errors after the stream has started can only be reported in-band
+                            error!("Message stream interrupted: {}", e);
+                            let (_, Json(body)) = messages_error(e);
+                            vec![MessageStreamEvent::Error { error: body.error }]
+                        }
+                        None => stream.finish(),
+                    };
+                    futures::stream::iter(events)
+                })
+                .map(|event| {
+                    let sse = Event::default().event(event.event_type()).json_data(&event);
+                    Ok::<_, Infallible>(sse.unwrap_or_default())
+                });
+
+            Ok(Sse::new(events).keep_alive(KeepAlive::default()).into_response())
+        }
+        Err(e) => {
+            error!("Messages request failed: {}", e);
+            Err(messages_error(e))
+        }
+    }
+}
+
+fn error_response(error_type: &str, message: String) -> MessagesErrorResponse {
+    MessagesErrorResponse {
+        r#type: "error".to_string(),
+        error: MessagesError {
+            r#type: error_type.to_string(),
+            message,
+        },
+    }
+}
+
+/// Map an error to an Anthropic-style error response
+fn messages_error(e: AppError) -> (StatusCode, Json<MessagesErrorResponse>) {
+    let (status, Json(body)) = chat_error(e);
+    let error_type = match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => "invalid_request_error",
+        StatusCode::UNAUTHORIZED => "authentication_error",
+        StatusCode::FORBIDDEN => "permission_error",
+        StatusCode::NOT_FOUND => "not_found_error",
+        StatusCode::PAYLOAD_TOO_LARGE => "request_too_large",
+        StatusCode::TOO_MANY_REQUESTS => "rate_limit_error",
+        StatusCode::SERVICE_UNAVAILABLE => "overloaded_error",
+        _ => "api_error",
+    };
+
+    (status, Json(error_response(error_type, body.error.message)))
+}
@@ -1,5 +1,15 @@
+pub mod batches;
 pub mod chat;
+pub mod files;
+pub mod gemini;
 pub mod health;
+pub mod images;
+pub mod messages;
+pub mod moderation;
+pub mod realtime;
+pub mod responses;
+pub mod speech;
+pub mod tokenize;
 pub mod transcription;
 
 pub use chat::create_chat_completion;
\ No newline at end of file
@@ -0,0 +1,48 @@
+use axum::{extract::State, Extension, Json};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::api::dto::{ModerationInput, ModerationRequest, ModerationResponse};
+use crate::domain::entities::Project;
+use crate::shared::error::AppError;
+use crate::AppState;
+
+/// Moderation handler
+///
+/// OpenAI-compatible; classifies text and images with the project's OpenAI key
+#[utoipa::path(
+    post,
+    path = "/v1/moderations",
+    tag = "Moderations",
+    request_body = ModerationRequest,
+    responses(
+        (status = 200, description = "Moderation results", body = ModerationResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error"),
+        (status = 502, description = "Provider error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn create_moderation(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Json(request): Json<ModerationRequest>,
+) -> Result<Json<ModerationResponse>, AppError> {
+    let empty = match &request.input {
+        ModerationInput::Text(text) => text.is_empty(),
+        ModerationInput::Texts(texts) => texts.is_empty(),
+        ModerationInput::Parts(parts) => parts.is_empty(),
+    };
+    if empty {
+        return Err(AppError::ValidationError("input must not be empty".to_string()));
+    }
+
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    info!("Moderation request: model={:?}", request.model);
+
+    let response = state.moderation_service.moderate(project_id, request).await?;
+    Ok(Json(response))
+}
@@ -0,0 +1,139 @@
+use axum::{
+    extract::{
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    response::Response,
+    Extension,
+};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio_tungstenite::tungstenite::Message as UpstreamMessage;
+use utoipa::IntoParams;
+
+use crate::domain::entities::Project;
+use crate::domain::services::realtime::{RealtimeSession, UpstreamSocket};
+use crate::shared::error::AppError;
+use crate::AppState;
+
+/// Realtime session query parameters
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RealtimeParams {
+    /// Realtime model (defaults to `realtime.default_model`)
+    pub model: Option<String>,
+    /// Stored LLM API key to use instead of the project default
+    pub llm_api_key_id: Option<String>,
+}
+
+/// Realtime speech WebSocket proxy
+///
+/// Opens an OpenAI Realtime session with the project's stored key and relays
+/// events in both directions. Browser clients may pass the project key as an
+/// `openai-insecure-api-key.<key>` subprotocol.
+#[utoipa::path(
+    get,
+    path = "/v1/realtime",
+    tag = "Realtime",
+    params(RealtimeParams),
+    responses(
+        (status = 101, description = "Switching to the WebSocket protocol"),
+        (status = 401, description = "Unauthorized"),
+        (status = 429, description = "Too many open realtime sessions for the project"),
+        (status = 502, description = "Upstream connection failed")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn realtime_session(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Query(params): Query<RealtimeParams>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+
+    // Connect upstream before upgrading so failures surface as HTTP errors
+    let (session, upstream) = state
+        .realtime_service
+        .open(project_id, params.model, params.llm_api_key_id)
+        .await?;
+
+    Ok(ws
+        .protocols(["realtime"])
+        .on_upgrade(move |socket| relay(state, session, socket, upstream)))
+}
+
+/// Relay messages until either side closes or the session limit is reached
+async fn relay(
+    state: Arc<AppState>,
+    mut session: RealtimeSession,
+    client: WebSocket,
+    upstream: UpstreamSocket,
+) {
+    let (mut client_tx, mut client_rx) = client.split();
+    let (mut upstream_tx, mut upstream_rx) = upstream.split();
+
+    let deadline = tokio::time::sleep(state.realtime_service.max_session_duration());
+    tokio::pin!(deadline);
+
+    let close_reason = loop {
+        tokio::select! {
+            _ = &mut deadline => {
+                let _ = client_tx
+                    .send(Message::Close(Some(CloseFrame {
+                        code: close_code::POLICY,
+                        reason: "Session duration limit reached".into(),
+                    })))
+                    .await;
+                break "duration_limit";
+            }
+            message = client_rx.next() => {
+                let forwarded = match message {
+                    Some(Ok(Message::Text(text))) => {
+                        session.usage.observe_client_event(&text);
+                        UpstreamMessage::Text(text)
+                    }
+                    Some(Ok(Message::Binary(data))) => UpstreamMessage::Binary(data),
+                    // Pings are answered by axum
+                    Some(Ok(Message::Ping(_) | Message::Pong(_))) => continue,
+                    Some(Ok(Message::Close(_))) | None => break "client_closed",
+                    Some(Err(_)) => break "client_error",
+                };
+                if upstream_tx.send(forwarded).await.is_err() {
+                    break "upstream_error";
+                }
+            }
+            message = upstream_rx.next() => {
+                let forwarded = match message {
+                    Some(Ok(UpstreamMessage::Text(text))) => {
+                        session.usage.observe_server_event(&text);
+                        Message::Text(text)
+                    }
+                    Some(Ok(UpstreamMessage::Binary(data))) => Message::Binary(data),
+                    Some(Ok(UpstreamMessage::Close(frame))) => {
+                        let _ = client_tx
+                            .send(Message::Close(frame.map(|f| CloseFrame {
+                                code: f.code.into(),
+                                reason: f.reason.into_owned().into(),
+                            })))
+                            .await;
+                        break "upstream_closed";
+                    }
+                    Some(Ok(_)) => continue,
+                    None => break "upstream_closed",
+                    Some(Err(_)) => break "upstream_error",
+                };
+                if client_tx.send(forwarded).await.is_err() {
+                    break "client_error";
+                }
+            }
+        }
+    };
+
+    let _ = upstream_tx.send(UpstreamMessage::Close(None)).await;
+    let _ = client_tx.close().await;
+
+    state.realtime_service.close(session, close_reason);
+}
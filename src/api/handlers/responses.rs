@@ -0,0 +1,227 @@
+//! OpenAI Responses-compatible handlers
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+    Extension,
+};
+use futures::StreamExt;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tracing::{error, info};
+
+use crate::api::dto::{
+    ChatError, ChatErrorResponse, ChatMessage, ResponseDeleted, ResponseObject, ResponsesRequest,
+};
+use crate::api::handlers::chat::{chat_error, dispatch, ChatOutput};
+use crate::domain::entities::stored_response::StoredResponse;
+use crate::domain::entities::Project;
+use crate::domain::repositories::ResponseRepository;
+use crate::domain::services::responses::{self, ResponseStream};
+use crate::AppState;
+
+/// Create a response
+///
+/// OpenAI Responses-compatible API. Requests are translated to chat
+/// completions and routed like `/v1/chat/completions`. Responses are stored
+/// per project unless `store` is false, so `previous_response_id` can
+/// continue a conversation on any model.
+#[utoipa::path(
+    post,
+    path = "/v1/responses",
+    tag = "Responses",
+    request_body = ResponsesRequest,
+    responses(
+        (status = 200, description = "Response created; a text/event-stream of response events when stream is true", body = ResponseObject),
+        (status = 400, description = "Bad request - invalid parameters", body = ChatErrorResponse),
+        (status = 401, description = "Unauthorized - invalid API key", body = ChatErrorResponse),
+        (status = 404, description = "previous_response_id not found", body = ChatErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ChatErrorResponse),
+        (status = 500, description = "Internal server error", body = ChatErrorResponse)
+    ),
+    security(
+        ("projectApiKey" = [])
+    )
+)]
+pub async fn create_response(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Json(request): Json<ResponsesRequest>,
+) -> Result<Response, (StatusCode, Json<ChatErrorResponse>)> {
+    info!("Responses request: model={}, previous_response_id={:?}", request.model, request.previous_response_id);
+
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let history = match &request.previous_response_id {
+        Some(previous) => {
+            state
+                .response_repo
+                .find_by_id(&project_id, previous)
+                .await
+                .map_err(chat_error)?
+                .conversation
+        }
+        None => Vec::new(),
+    };
+
+    let prepared = responses::prepare(request, history).map_err(chat_error)?;
+    if let Err(e) = prepared.chat_request.validate() {
+        error!("Invalid responses request: {}", e);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChatErrorResponse {
+                error: ChatError {
+                    r#type: "invalid_request_error".to_string(),
+                    message: e,
+                    code: "invalid_request".to_string(),
+                },
+            }),
+        ));
+    }
+
+    let context = prepared.context;
+    let conversation = prepared.conversation;
+    match dispatch(&state, &project, "/v1/responses", prepared.chat_request).await {
+        Ok(ChatOutput::Complete(response)) => {
+            let response = responses::from_chat_response(&context, *response);
+            info!("Response created: id={}, status={}", response.id, response.status);
+
+            // Stored before replying so the client can chain on it immediately
+            if response.store {
+                store(state.response_repo.as_ref(), project_id, &response, conversation).await;
+            }
+            Ok(Json(response).into_response())
+        }
+        Ok(ChatOutput::Stream(chunks)) => {
+            let repo = state.response_repo.clone();
+            let mut stream = ResponseStream::new(context);
+            let events = chunks
+                .map(Some)
+                .chain(futures::stream::once(async { None }))
+                .flat_map(move |chunk| {
+                    let events = match chunk {
+                        Some(Ok(chunk)) => stream.push(chunk),
+                        Some(Err(e)) => {
+                            // Errors after the stream has started can only be reported in-band
+                            error!("Response stream interrupted: {}", e);
+                            let (_, Json(body)) = chat_error(e);
+                            vec![stream.error(&body.error.code, body.error.message)]
+                        }
+                        None => {
+                            let events = stream.finish();
+                            if let Some(response) = stream.response().filter(|r| r.store).cloned() {
+                                let repo = repo.clone();
+                                let project_id = project_id.clone();
+                                let conversation = conversation.clone();
+                                tokio::spawn(async move {
+                                    store(repo.as_ref(), project_id, &response, conversation).await;
+                                });
+                            }
+                            events
+                        }
+                    };
+                    futures::stream::iter(events)
+                })
+                .map(|event| {
+                    let sse = Event::default().event(event.event.event_type()).json_data(&event);
+                    Ok::<_, Infallible>(sse.unwrap_or_default())
+                });
+
+            Ok(Sse::new(events).keep_alive(KeepAlive::default()).into_response())
+        }
+        Err(e) => {
+            error!("Responses request failed: {}", e);
+            Err(chat_error(e))
+        }
+    }
+}
+
+/// Keep a response and its conversation for `previous_response_id`
+async fn store(
+    repo: &dyn ResponseRepository,
+    project_id: String,
+    response: &ResponseObject,
+    mut conversation: Vec<ChatMessage>,
+) {
+    conversation.extend(responses::output_message(&response.output));
+    let stored = StoredResponse::new(project_id, response.clone(), conversation);
+    if let Err(e) = repo.create(&stored).await {
+        error!("Failed to store response {}: {}", response.id, e);
+    }
+}
+
+/// Get a response
+///
+/// Retrieve a stored response by ID
+#[utoipa::path(
+    get,
+    path = "/v1/responses/{response_id}",
+    tag = "Responses",
+    params(
+        ("response_id" = String, Path, description = "Response ID")
+    ),
+    responses(
+        (status = 200, description = "Stored response", body = ResponseObject),
+        (status = 401, description = "Unauthorized - invalid API key", body = ChatErrorResponse),
+        (status = 404, description = "Response not found", body = ChatErrorResponse)
+    ),
+    security(
+        ("projectApiKey" = [])
+    )
+)]
+pub async fn get_response(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Path(response_id): Path<String>,
+) -> Result<Json<ResponseObject>, (StatusCode, Json<ChatErrorResponse>)> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let stored = state
+        .response_repo
+        .find_by_id(&project_id, &response_id)
+        .await
+        .map_err(chat_error)?;
+
+    Ok(Json(stored.response))
+}
+
+/// Delete a response
+///
+/// Delete a stored response; it can no longer be used as `previous_response_id`
+#[utoipa::path(
+    delete,
+    path = "/v1/responses/{response_id}",
+    tag = "Responses",
+    params(
+        ("response_id" = String, Path, description = "Response ID")
+    ),
+    responses(
+        (status = 200, description = "Response deleted", body = ResponseDeleted),
+        (status = 401, description = "Unauthorized - invalid API key", body = ChatErrorResponse),
+        (status = 404, description = "Response not found", body = ChatErrorResponse)
+    ),
+    security(
+        ("projectApiKey" = [])
+    )
+)]
+pub async fn delete_response(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Path(response_id): Path<String>,
+) -> Result<Json<ResponseDeleted>, (StatusCode, Json<ChatErrorResponse>)> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    state
+        .response_repo
+        .delete(&project_id, &response_id)
+        .await
+        .map_err(chat_error)?;
+
+    info!("Response deleted: id={}", response_id);
+    Ok(Json(ResponseDeleted {
+        id: response_id,
+        object: "response".to_string(),
+        deleted: true,
+    }))
+}
@@ -0,0 +1,65 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::api::dto::SpeechRequestDto;
+use crate::domain::entities::speech::SpeechRequest;
+use crate::domain::entities::Project;
+use crate::shared::error::AppError;
+use crate::AppState;
+
+/// Text-to-speech handler
+///
+/// Streams synthesized audio back to the client as it arrives from the provider
+#[utoipa::path(
+    post,
+    path = "/v1/audio/speech",
+    tag = "Audio",
+    request_body = SpeechRequestDto,
+    responses(
+        (status = 200, description = "Audio stream", content_type = "audio/mpeg"),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error"),
+        (status = 502, description = "Provider error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn create_speech(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Json(request_dto): Json<SpeechRequestDto>,
+) -> Result<Response, AppError> {
+    request_dto.validate().map_err(AppError::ValidationError)?;
+
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+
+    info!(
+        "Speech request: model={}, voice={}, characters={}",
+        request_dto.model,
+        request_dto.voice,
+        request_dto.input.chars().count()
+    );
+
+    let request = SpeechRequest::from(request_dto);
+    let content_type = request.response_format.content_type();
+
+    let upstream = state
+        .speech_service
+        .synthesize(project_id, request)
+        .await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, content_type)],
+        Body::from_stream(upstream.bytes_stream()),
+    )
+        .into_response())
+}
@@ -0,0 +1,63 @@
+use axum::Json;
+use tracing::info;
+
+use crate::api::dto::{TokenizeInput, TokenizeRequest, TokenizeResponse};
+use crate::domain::services::tokenizer::{self, TokenizerFamily};
+use crate::shared::error::AppError;
+
+/// Tokenize handler
+///
+/// Counts tokens locally with the model's tokenizer; nothing is sent upstream
+#[utoipa::path(
+    post,
+    path = "/v1/tokenize",
+    tag = "Tokenize",
+    request_body = TokenizeRequest,
+    responses(
+        (status = 200, description = "Token counts", body = TokenizeResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn tokenize(Json(request): Json<TokenizeRequest>) -> Result<Json<TokenizeResponse>, AppError> {
+    let family = TokenizerFamily::for_model(&request.model);
+    info!("Tokenize request: model={}, tokenizer={}", request.model, family.name());
+
+    let (token_count, counts, tokens) = match (&request.input, &request.messages) {
+        (Some(input), None) => {
+            let texts = match input {
+                TokenizeInput::Text(text) => vec![text.as_str()],
+                TokenizeInput::Texts(texts) => texts.iter().map(String::as_str).collect(),
+            };
+            let counts: Vec<usize> = texts.iter().map(|text| family.count(text)).collect();
+            let tokens = match (input, family.is_exact()) {
+                (TokenizeInput::Text(text), true) => Some(family.encode(text)),
+                _ => None,
+            };
+            (counts.iter().sum(), counts, tokens)
+        }
+        (None, Some(messages)) => {
+            let prompt = tokenizer::count_chat(&request.model, messages, request.tools.as_deref());
+            (prompt.total(), prompt.messages, None)
+        }
+        _ => {
+            return Err(AppError::ValidationError(
+                "Exactly one of input or messages is required".to_string(),
+            ))
+        }
+    };
+
+    Ok(Json(TokenizeResponse {
+        object: "tokenize".to_string(),
+        tokenizer: family.name().to_string(),
+        exact: family.is_exact(),
+        token_count,
+        counts,
+        context_window: tokenizer::context_window(&request.model),
+        tokens,
+        model: request.model,
+    }))
+}
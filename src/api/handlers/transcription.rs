@@ -1,21 +1,38 @@
-use axum::{extract::State, Extension, Json};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
 use axum::extract::Multipart;
 use std::sync::Arc;
 
-use crate::api::dto::{TranscribeRequestDto, TranscribeResponseDto};
+use crate::api::dto::{TranscribeRequestDto, TranscribeResponseDto, TranscriptionJobDto};
 use crate::domain::entities::{Project, RateLimits};
 use crate::domain::entities::transcription::TranscriptionRequest;
+use crate::domain::services::transcription_job::JobWebhook;
 use crate::shared::error::AppError;
+use crate::shared::utils::{SpooledUpload, UploadSpool};
 use crate::AppState;
 
 /// Audio transcription handler
+///
+/// OpenAI-compatible; also served at the legacy `/v1/audio/transcribe` path.
+/// `text`, `srt`, `vtt` and `tsv` response formats are returned as plain
+/// bodies; subtitles are rendered locally from segment and word timestamps.
 #[utoipa::path(
     post,
-    path = "/v1/audio/transcribe",
+    path = "/v1/audio/transcriptions",
     tag = "Audio",
     request_body(content = TranscribeResponseDto, content_type = "multipart/form-data"),
     responses(
-        (status = 200, description = "Transcription successful", body = TranscribeResponseDto),
+        (status = 200, description = "Transcription successful", content(
+            ("application/json" = TranscribeResponseDto),
+            ("text/plain" = String),
+            ("application/x-subrip" = String),
+            ("text/vtt" = String),
+            ("text/tab-separated-values" = String)
+        )),
         (status = 400, description = "Bad request"),
         (status = 401, description = "Unauthorized"),
         (status = 413, description = "File too large"),
@@ -28,11 +45,147 @@ use crate::AppState;
 pub async fn transcribe_audio(
     State(state): State<Arc<AppState>>,
     Extension(project): Extension<Project>,
+    multipart: Multipart,
+) -> Result<Response, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let form = read_transcription_form(multipart, &project).await?;
+
+    let plain_content_type = form
+        .dto
+        .response_format
+        .as_ref()
+        .and_then(|f| f.plain_content_type());
+    let (transcription_request, _) = form.into_request();
+
+    // Perform transcription
+    let response = state.transcription_service
+        .transcribe(project_id, transcription_request)
+        .await?;
+
+    match plain_content_type {
+        Some(content_type) => {
+            Ok(([(header::CONTENT_TYPE, content_type)], response.text).into_response())
+        }
+        None => Ok(Json(TranscribeResponseDto::from(response)).into_response()),
+    }
+}
+
+/// Submit an asynchronous transcription job
+///
+/// Accepts the same form as `/v1/audio/transcriptions` plus optional
+/// `webhook_url` and `webhook_secret`. Poll the returned job or wait for the
+/// webhook, which is signed with `X-LLMHub-Signature` when a secret is given.
+#[utoipa::path(
+    post,
+    path = "/v1/audio/transcriptions/jobs",
+    tag = "Audio",
+    request_body(content = TranscribeResponseDto, content_type = "multipart/form-data"),
+    responses(
+        (status = 202, description = "Job queued", body = TranscriptionJobDto),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 413, description = "File too large"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn create_transcription_job(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    multipart: Multipart,
+) -> Result<(StatusCode, Json<TranscriptionJobDto>), AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let form = read_transcription_form(multipart, &project).await?;
+    let (transcription_request, webhook) = form.into_request();
+
+    let job = state.transcription_job_service
+        .submit(project_id, transcription_request, webhook)
+        .await?;
+
+    Ok((StatusCode::ACCEPTED, Json(TranscriptionJobDto::from(job))))
+}
+
+/// Get an asynchronous transcription job
+#[utoipa::path(
+    get,
+    path = "/v1/audio/transcriptions/jobs/{job_id}",
+    tag = "Audio",
+    params(
+        ("job_id" = String, Path, description = "Job ID returned on submission")
+    ),
+    responses(
+        (status = 200, description = "Job state", body = TranscriptionJobDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Job not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("ApiKey" = [])
+    )
+)]
+pub async fn get_transcription_job(
+    State(state): State<Arc<AppState>>,
+    Extension(project): Extension<Project>,
+    Path(job_id): Path<String>,
+) -> Result<Json<TranscriptionJobDto>, AppError> {
+    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
+    let job = state.transcription_job_service.get(&project_id, &job_id).await?;
+
+    Ok(Json(TranscriptionJobDto::from(job)))
+}
+
+/// Parsed transcription multipart form
+struct TranscriptionForm {
+    upload: SpooledUpload,
+    file_name: String,
+    dto: TranscribeRequestDto,
+}
+
+impl TranscriptionForm {
+    fn into_request(self) -> (TranscriptionRequest, Option<JobWebhook>) {
+        let timestamp_granularities = self.dto.parse_timestamp_granularities();
+        let subtitle_options = self.dto.subtitle_options();
+        let webhook = self.dto.webhook_url.map(|url| JobWebhook {
+            url,
+            secret: self.dto.webhook_secret,
+        });
+
+        let request = TranscriptionRequest {
+            file_data: self.upload.data,
+            file_hash: Some(self.upload.sha256),
+            file_name: self.file_name,
+            model: self.dto.model,
+            language: self.dto.language,
+            prompt: self.dto.prompt,
+            response_format: self.dto.response_format.map(|f| f.into()),
+            temperature: self.dto.temperature,
+            timestamp_granularities,
+            llm_api_key_id: self.dto.llm_api_key_id,
+            subtitle_options,
+            provider: self.dto.provider,
+            diarize: self.dto.diarize.unwrap_or(false),
+        };
+
+        (request, webhook)
+    }
+}
+
+/// Read the multipart form, spooling the file to disk and enforcing the
+/// project's file size limit as it arrives
+async fn read_transcription_form(
     mut multipart: Multipart,
-) -> Result<Json<TranscribeResponseDto>, AppError> {
-    let mut file_data = Vec::new();
+    project: &Project,
+) -> Result<TranscriptionForm, AppError> {
+    let default_rate_limits = RateLimits::default();
+    let rate_limits = project.rate_limits.as_ref().unwrap_or(&default_rate_limits);
+    let max_file_bytes = rate_limits.max_file_size_mb as usize * 1024 * 1024;
+
+    let mut upload = None;
     let mut file_name = String::new();
     let mut request_dto = TranscribeRequestDto {
+        provider: None,
         model: None,
         language: None,
         prompt: None,
@@ -40,16 +193,18 @@ pub async fn transcribe_audio(
         temperature: None,
         timestamp_granularities: None,
         llm_api_key_id: None,
+        webhook_url: None,
+        webhook_secret: None,
+        max_line_length: None,
+        max_cue_chars: None,
+        highlight_words: None,
+        diarize: None,
     };
 
-    let project_id = project.id.as_ref().map(|id| id.to_hex()).unwrap_or_default();
-
     // Parse multipart form data
-    while let Some(field) = multipart.next_field().await.map_err(|e| {
+    while let Some(mut field) = multipart.next_field().await.map_err(|e| {
         AppError::BadRequest(format!("Failed to read multipart field: {}", e))
     })? {
-
-
         let field_name = field
             .name()
             .ok_or_else(|| AppError::BadRequest("Missing field name".to_string()))?
@@ -57,13 +212,19 @@ pub async fn transcribe_audio(
 
         match field_name.as_str() {
             "file" => {
-                file_name = field
-                    .file_name()
-                    .unwrap_or("audio.wav")
-                    .to_string();
-                file_data = field.bytes().await
+                file_name = field.file_name().unwrap_or("audio").to_string();
+                let mut spool = UploadSpool::new(max_file_bytes)?;
+                while let Some(chunk) = field.chunk().await
                     .map_err(|e| AppError::BadRequest(format!("Failed to read file data: {}", e)))?
-                    .to_vec();
+                {
+                    spool.write(&chunk).await?;
+                }
+                upload = Some(spool.finish().await?);
+            }
+            "provider" => {
+                let provider = field.text().await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read provider: {}", e)))?;
+                request_dto.provider = Some(provider.parse().map_err(AppError::BadRequest)?);
             }
             "model" => {
                 request_dto.model = Some(field.text().await
@@ -80,22 +241,60 @@ pub async fn transcribe_audio(
             "response_format" => {
                 let format_str = field.text().await
                     .map_err(|e| AppError::BadRequest(format!("Failed to read response_format: {}", e)))?;
-                request_dto.response_format = serde_json::from_str(&format!("\"{}\"", format_str))
-                    .ok();
+                request_dto.response_format = Some(format_str.parse().map_err(AppError::BadRequest)?);
             }
             "temperature" => {
                 let temp_str = field.text().await
                     .map_err(|e| AppError::BadRequest(format!("Failed to read temperature: {}", e)))?;
                 request_dto.temperature = temp_str.parse().ok();
             }
-            "timestamp_granularities" => {
-                request_dto.timestamp_granularities = Some(field.text().await
-                    .map_err(|e| AppError::BadRequest(format!("Failed to read timestamp_granularities: {}", e)))?);
+            // The OpenAI SDKs send one `timestamp_granularities[]` field per value
+            "timestamp_granularities" | "timestamp_granularities[]" => {
+                let value = field.text().await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read timestamp_granularities: {}", e)))?;
+                request_dto.timestamp_granularities = Some(match request_dto.timestamp_granularities.take() {
+                    Some(previous) => format!("{},{}", previous, value),
+                    None => value,
+                });
             }
             "llm_api_key_id" => {
                 request_dto.llm_api_key_id = Some(field.text().await
                     .map_err(|e| AppError::BadRequest(format!("Failed to read llm_api_key_id: {}", e)))?);
             }
+            "webhook_url" => {
+                request_dto.webhook_url = Some(field.text().await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read webhook_url: {}", e)))?);
+            }
+            "webhook_secret" => {
+                request_dto.webhook_secret = Some(field.text().await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read webhook_secret: {}", e)))?);
+            }
+            "max_line_length" | "max_cue_chars" => {
+                let value = field.text().await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read {}: {}", field_name, e)))?;
+                let limit = value.trim().parse::<usize>().ok().filter(|n| *n > 0).ok_or_else(|| {
+                    AppError::BadRequest(format!("{} must be a positive integer", field_name))
+                })?;
+                if field_name == "max_line_length" {
+                    request_dto.max_line_length = Some(limit);
+                } else {
+                    request_dto.max_cue_chars = Some(limit);
+                }
+            }
+            "highlight_words" => {
+                let value = field.text().await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read highlight_words: {}", e)))?;
+                request_dto.highlight_words = Some(value.trim().parse().map_err(|_| {
+                    AppError::BadRequest("highlight_words must be true or false".to_string())
+                })?);
+            }
+            "diarize" => {
+                let value = field.text().await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read diarize: {}", e)))?;
+                request_dto.diarize = Some(value.trim().parse().map_err(|_| {
+                    AppError::BadRequest("diarize must be true or false".to_string())
+                })?);
+            }
             _ => {
                 // Ignore unknown fields
             }
@@ -103,41 +302,13 @@ pub async fn transcribe_audio(
     }
 
     // Validate file data
-    if file_data.is_empty() {
-        return Err(AppError::BadRequest("No file provided".to_string()));
-    }
+    let upload = upload
+        .filter(|u| !u.data.is_empty())
+        .ok_or_else(|| AppError::BadRequest("No file provided".to_string()))?;
 
-    // Check file size against project limits
-    let file_size_mb = file_data.len() as f32 / (1024.0 * 1024.0);
-    let default_rate_limits = RateLimits::default();
-    let rate_limits = project.rate_limits.as_ref().unwrap_or(&default_rate_limits);
-    if file_size_mb > rate_limits.max_file_size_mb as f32 {
-        return Err(AppError::BadRequest(format!(
-            "File size {:.2}MB exceeds limit of {}MB",
-            file_size_mb, rate_limits.max_file_size_mb
-        )));
-    }
-
-    // Parse timestamp granularities before moving request_dto
-    let timestamp_granularities = request_dto.parse_timestamp_granularities();
-
-    // Create transcription request
-    let transcription_request = TranscriptionRequest {
-        file_data,
+    Ok(TranscriptionForm {
+        upload,
         file_name,
-        model: request_dto.model,
-        language: request_dto.language,
-        prompt: request_dto.prompt,
-        response_format: request_dto.response_format.map(|f| f.into()),
-        temperature: request_dto.temperature,
-        timestamp_granularities,
-        llm_api_key_id: request_dto.llm_api_key_id,
-    };
-
-    // Perform transcription
-    let response = state.transcription_service
-        .transcribe(project_id, transcription_request)
-        .await?;
-
-    Ok(Json(TranscribeResponseDto::from(response)))
-}
\ No newline at end of file
+        dto: request_dto,
+    })
+}
@@ -12,23 +12,64 @@ use crate::shared::error::AppError;
 /// Authentication middleware for Project API keys
 pub async fn authenticate(
     State(repo): State<Arc<dyn ProjectRepository>>,
-    mut req: Request,
+    req: Request,
     next: Next,
 ) -> Result<Response, AppError> {
-    // Extract Authorization header
-    let auth_header = req
-        .headers()
-        .get("Authorization")
-        .and_then(|h| h.to_str().ok())
-        .ok_or_else(|| AppError::AuthenticationError("Missing Authorization header".to_string()))?;
+    authenticate_with(repo, req, next, |_| None).await
+}
+
+/// Anthropic Messages ingress: Anthropic SDKs send the key as `x-api-key`
+pub async fn authenticate_anthropic(
+    State(repo): State<Arc<dyn ProjectRepository>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    authenticate_with(repo, req, next, |req| header_key(req, "x-api-key")).await
+}
+
+/// Gemini ingress: Google GenAI SDKs send the key as `x-goog-api-key` or `?key=`
+pub async fn authenticate_gemini(
+    State(repo): State<Arc<dyn ProjectRepository>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    authenticate_with(repo, req, next, |req| {
+        header_key(req, "x-goog-api-key").or_else(|| query_key(req))
+    })
+    .await
+}
+
+/// Realtime ingress: browsers cannot set headers on WebSocket handshakes
+pub async fn authenticate_realtime(
+    State(repo): State<Arc<dyn ProjectRepository>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    authenticate_with(repo, req, next, websocket_protocol_key).await
+}
 
-    // Extract Bearer token
-    let api_key = auth_header
-        .strip_prefix("Bearer ")
-        .ok_or_else(|| {
-            AppError::AuthenticationError("Invalid Authorization header format".to_string())
-        })?
-        .trim();
+/// Authenticate with the Bearer token, or with the ingress-specific
+/// `fallback` when there is no Authorization header
+async fn authenticate_with(
+    repo: Arc<dyn ProjectRepository>,
+    mut req: Request,
+    next: Next,
+    fallback: impl Fn(&Request) -> Option<String>,
+) -> Result<Response, AppError> {
+    // Extract Bearer token from the Authorization header
+    let authorization = req.headers().get("Authorization").and_then(|h| h.to_str().ok());
+    let api_key = match authorization {
+        Some(auth_header) => auth_header
+            .strip_prefix("Bearer ")
+            .ok_or_else(|| {
+                AppError::AuthenticationError("Invalid Authorization header format".to_string())
+            })?
+            .trim()
+            .to_string(),
+        None => fallback(&req).ok_or_else(|| {
+            AppError::AuthenticationError("Missing Authorization header".to_string())
+        })?,
+    };
 
     if api_key.is_empty() {
         return Err(AppError::AuthenticationError(
@@ -44,7 +85,7 @@ pub async fn authenticate(
     }
 
     // Fetch project from database
-    let project = repo.find_by_api_key(api_key).await?;
+    let project = repo.find_by_api_key(&api_key).await?;
 
     // Check if project is active
     if !project.is_active() {
@@ -59,6 +100,35 @@ pub async fn authenticate(
     Ok(next.run(req).await)
 }
 
+/// API key passed in a header other than Authorization
+fn header_key(req: &Request, name: &str) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|h| h.to_str().ok())
+        .map(|key| key.trim().to_string())
+}
+
+/// API key passed as the `key` query parameter
+fn query_key(req: &Request) -> Option<String> {
+    req.uri()
+        .query()?
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("key="))
+        .map(str::to_string)
+}
+
+/// API key passed as an `openai-insecure-api-key.<key>` WebSocket subprotocol,
+/// as OpenAI's browser realtime clients do
+fn websocket_protocol_key(req: &Request) -> Option<String> {
+    req.headers()
+        .get("Sec-WebSocket-Protocol")?
+        .to_str()
+        .ok()?
+        .split(',')
+        .find_map(|p| p.trim().strip_prefix("openai-insecure-api-key."))
+        .map(str::to_string)
+}
+
 /// Extract project from request extensions
 #[allow(dead_code)]
 pub fn extract_project(req: &Request) -> Result<&Project, AppError> {
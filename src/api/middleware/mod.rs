@@ -1,5 +1,5 @@
 pub mod auth;
 pub mod cors;
 
-pub use auth::authenticate;
+pub use auth::{authenticate, authenticate_anthropic, authenticate_gemini, authenticate_realtime};
 pub use cors::cors_layer;
\ No newline at end of file
@@ -1,9 +1,20 @@
-use axum::{routing::post, Router};
+use axum::{
+    routing::{get, post},
+    Router,
+};
 
-use crate::api::handlers::transcription::transcribe_audio;
+use crate::api::handlers::speech::create_speech;
+use crate::api::handlers::transcription::{
+    create_transcription_job, get_transcription_job, transcribe_audio,
+};
 
 /// Audio API router
 pub fn audio_router() -> Router<std::sync::Arc<crate::AppState>> {
     Router::new()
+        .route("/transcriptions", post(transcribe_audio))
+        .route("/transcriptions/jobs", post(create_transcription_job))
+        .route("/transcriptions/jobs/:job_id", get(get_transcription_job))
+        // Legacy alias kept for existing clients
         .route("/transcribe", post(transcribe_audio))
+        .route("/speech", post(create_speech))
 }
\ No newline at end of file
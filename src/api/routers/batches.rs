@@ -0,0 +1,18 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use std::sync::Arc;
+
+use crate::api::handlers::batches::{cancel_batch, create_batch, get_batch, list_batches};
+use crate::AppState;
+
+/// Create the batches router
+///
+/// Provides the OpenAI Batch-compatible API
+pub fn batches_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", post(create_batch).get(list_batches))
+        .route("/:batch_id", get(get_batch))
+        .route("/:batch_id/cancel", post(cancel_batch))
+}
@@ -0,0 +1,18 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use std::sync::Arc;
+
+use crate::api::handlers::files::{delete_file, get_file, get_file_content, list_files, upload_file};
+use crate::AppState;
+
+/// Create the files router
+///
+/// Provides the OpenAI Files-compatible API
+pub fn files_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", post(upload_file).get(list_files))
+        .route("/:file_id", get(get_file).delete(delete_file))
+        .route("/:file_id/content", get(get_file_content))
+}
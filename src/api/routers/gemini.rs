@@ -0,0 +1,12 @@
+use axum::{routing::post, Router};
+use std::sync::Arc;
+
+use crate::api::handlers::gemini::generate_content;
+use crate::AppState;
+
+/// Create the Gemini router
+///
+/// Provides the Gemini generateContent-compatible API
+pub fn gemini_router() -> Router<Arc<AppState>> {
+    Router::new().route("/models/:model_action", post(generate_content))
+}
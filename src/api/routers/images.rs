@@ -0,0 +1,11 @@
+use axum::{routing::post, Router};
+
+use crate::api::handlers::images::{create_image, create_image_edit, create_image_variation};
+
+/// Images API router
+pub fn images_router() -> Router<std::sync::Arc<crate::AppState>> {
+    Router::new()
+        .route("/generations", post(create_image))
+        .route("/edits", post(create_image_edit))
+        .route("/variations", post(create_image_variation))
+}
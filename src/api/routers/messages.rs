@@ -0,0 +1,12 @@
+use axum::{routing::post, Router};
+use std::sync::Arc;
+
+use crate::api::handlers::messages::create_message;
+use crate::AppState;
+
+/// Create the messages router
+///
+/// Provides the Anthropic Messages-compatible API
+pub fn messages_router() -> Router<Arc<AppState>> {
+    Router::new().route("/", post(create_message))
+}
@@ -1,21 +1,59 @@
 pub mod audio;
+pub mod batches;
 pub mod chat;
+pub mod files;
+pub mod gemini;
 pub mod health;
+pub mod images;
+pub mod messages;
+pub mod moderation;
+pub mod realtime;
+pub mod responses;
+pub mod tokenize;
 
 #[allow(unused_imports)]
 use utoipa::OpenApi;
 
 use crate::api::dto::{
-    ChatChoice, ChatChoiceChunk, ChatCompletionChunk, ChatCompletionRequest,
-    ChatCompletionResponse, ChatDelta, ChatError, ChatErrorResponse, ChatMessage, ChatMetadata,
-    ChatRole, ChatUsage, DetailedHealthResponse, FinishReason, HealthResponse, ResponseFormatDto,
-    TimestampGranularityDto, TranscribeResponseDto, TranscriptionSegmentDto, TranscriptionUsageDto,
-    TranscriptionWordDto,
+    BatchCostDto, BatchDto, BatchErrorDto, BatchErrorsDto, BatchListDto, BatchRequestCountsDto,
+    BatchStatusDto, ChatChoice, ChatChoiceChunk, ChatCompletionChunk, ChatCompletionRequest,
+    ChatCompletionResponse, ChatContent, ChatDelta, ChatError, ChatErrorResponse, ChatMessage,
+    ChatMetadata, ChatResponseFormat, ChatRole, ChatTool, ChatUsage, ChoiceLogprobs,
+    CompletionTokensDetails, ContentBlock, ContentPart, CreateBatchRequestDto,
+    DetailedHealthResponse, FileData, FileDeletedDto, FileListDto, FileObjectDto, FinishReason,
+    FunctionCall, FunctionCallDelta, FunctionCallingConfig, FunctionDeclaration, FunctionDefinition,
+    GeminiCandidate, GeminiContent, GeminiError, GeminiErrorResponse, GeminiFunctionCall,
+    GeminiFunctionResponse, GeminiPart, GeminiTool, GeminiToolConfig, GenerateContentRequest,
+    GenerateContentResponse, GenerationConfig, HealthResponse, ImageData, ImageDetail,
+    ImageGenerationRequestDto, ImageResponseFormatDto, ImageSource, ImageUrl, ImagesMetadata,
+    ImagesResponse, IncompleteDetails, InlineData, InputAudio, InputContentPart, InputItem,
+    InputItemContent, InputItemMessage, InputMessage, InputTokensDetails, JsonSchemaFormat,
+    MessageContent, MessageRole, MessagesError, MessagesErrorResponse, MessagesMetadata,
+    MessagesRequest, MessagesResponse, MessagesTool, MessagesToolChoice, MessagesUsage,
+    ModerationInput, ModerationInputPart, ModerationRequest, ModerationResponse, ModerationResult,
+    NamedToolChoice, OutputContent, OutputItem, OutputTokensDetails, PromptInjectionMetadata,
+    PromptTokensDetails, ReasoningConfig, ReasoningEffort, ResponseDeleted, ResponseFormatDto,
+    ResponseInput, ResponseObject, ResponseTextConfig, ResponseTextFormat, ResponsesRequest,
+    ResponsesTool, ResponsesToolChoice, ResponsesTruncation, ResponsesUsage, SpeechFormatDto,
+    SpeechRequestDto, StopSequences, StreamOptions, SystemPrompt, TimestampGranularityDto,
+    TokenLogprob, TokenizeInput, TokenizeRequest, TokenizeResponse, ToolCall, ToolCallDelta,
+    ToolChoice, ToolChoiceFunction, ToolChoiceMode, ToolResultContent, ToolType, TopLogprob,
+    TranscribeResponseDto, TranscriptionJobDto, TranscriptionJobStatusDto, TranscriptionSegmentDto,
+    TranscriptionUsageDto, TranscriptionWordDto, TruncationStrategy, UsageMetadata,
 };
 
 pub use audio::audio_router;
+pub use batches::batches_router;
 pub use chat::chat_router;
+pub use files::files_router;
+pub use gemini::gemini_router;
 pub use health::health_router;
+pub use images::images_router;
+pub use messages::messages_router;
+pub use moderation::moderations_router;
+pub use realtime::realtime_router;
+pub use responses::responses_router;
+pub use tokenize::tokenize_router;
 
 /// OpenAPI documentation
 #[derive(utoipa::OpenApi)]
@@ -24,37 +62,177 @@ pub use health::health_router;
         crate::api::handlers::health::health_check,
         crate::api::handlers::health::detailed_health_check,
         crate::api::handlers::transcription::transcribe_audio,
+        crate::api::handlers::transcription::create_transcription_job,
+        crate::api::handlers::transcription::get_transcription_job,
+        crate::api::handlers::speech::create_speech,
+        crate::api::handlers::images::create_image,
+        crate::api::handlers::images::create_image_edit,
+        crate::api::handlers::images::create_image_variation,
         crate::api::handlers::chat::create_chat_completion,
+        crate::api::handlers::messages::create_message,
+        crate::api::handlers::gemini::generate_content,
+        crate::api::handlers::responses::create_response,
+        crate::api::handlers::responses::get_response,
+        crate::api::handlers::responses::delete_response,
+        crate::api::handlers::files::upload_file,
+        crate::api::handlers::files::list_files,
+        crate::api::handlers::files::get_file,
+        crate::api::handlers::files::get_file_content,
+        crate::api::handlers::files::delete_file,
+        crate::api::handlers::batches::create_batch,
+        crate::api::handlers::batches::list_batches,
+        crate::api::handlers::batches::get_batch,
+        crate::api::handlers::batches::cancel_batch,
+        crate::api::handlers::moderation::create_moderation,
+        crate::api::handlers::realtime::realtime_session,
+        crate::api::handlers::tokenize::tokenize,
     ),
     components(
         schemas(
             HealthResponse,
             DetailedHealthResponse,
             TranscribeResponseDto,
+            TranscriptionJobDto,
+            TranscriptionJobStatusDto,
             ResponseFormatDto,
             TimestampGranularityDto,
             TranscriptionSegmentDto,
             TranscriptionUsageDto,
             TranscriptionWordDto,
+            SpeechRequestDto,
+            SpeechFormatDto,
+            ImageGenerationRequestDto,
+            ImageResponseFormatDto,
+            ImagesResponse,
+            ImageData,
+            ImagesMetadata,
             ChatCompletionRequest,
             ChatCompletionResponse,
             ChatChoice,
             ChatMessage,
+            ChatContent,
+            ContentPart,
+            ImageUrl,
+            ImageDetail,
+            InputAudio,
             ChatRole,
             ChatUsage,
             ChatMetadata,
+            PromptInjectionMetadata,
             FinishReason,
             ChatErrorResponse,
             ChatError,
             ChatCompletionChunk,
             ChatChoiceChunk,
             ChatDelta,
+            ChatTool,
+            FunctionDefinition,
+            ToolChoice,
+            ToolChoiceMode,
+            NamedToolChoice,
+            ToolChoiceFunction,
+            ToolType,
+            ToolCall,
+            FunctionCall,
+            ToolCallDelta,
+            FunctionCallDelta,
+            ChatResponseFormat,
+            JsonSchemaFormat,
+            StopSequences,
+            StreamOptions,
+            ReasoningEffort,
+            TruncationStrategy,
+            ChoiceLogprobs,
+            TokenLogprob,
+            TopLogprob,
+            PromptTokensDetails,
+            CompletionTokensDetails,
+            MessagesRequest,
+            MessagesResponse,
+            InputMessage,
+            MessageRole,
+            MessageContent,
+            SystemPrompt,
+            ContentBlock,
+            ImageSource,
+            ToolResultContent,
+            MessagesTool,
+            MessagesToolChoice,
+            MessagesMetadata,
+            MessagesUsage,
+            MessagesErrorResponse,
+            MessagesError,
+            GenerateContentRequest,
+            GenerateContentResponse,
+            GeminiContent,
+            GeminiPart,
+            InlineData,
+            FileData,
+            GeminiFunctionCall,
+            GeminiFunctionResponse,
+            GeminiTool,
+            FunctionDeclaration,
+            GeminiToolConfig,
+            FunctionCallingConfig,
+            GenerationConfig,
+            GeminiCandidate,
+            UsageMetadata,
+            GeminiErrorResponse,
+            GeminiError,
+            IncompleteDetails,
+            InputContentPart,
+            InputItem,
+            InputItemContent,
+            InputItemMessage,
+            InputTokensDetails,
+            OutputContent,
+            OutputItem,
+            OutputTokensDetails,
+            ReasoningConfig,
+            ResponseDeleted,
+            ResponseInput,
+            ResponseObject,
+            ResponseTextConfig,
+            ResponseTextFormat,
+            ResponsesRequest,
+            ResponsesTool,
+            ResponsesToolChoice,
+            ResponsesTruncation,
+            ResponsesUsage,
+            FileObjectDto,
+            FileListDto,
+            FileDeletedDto,
+            CreateBatchRequestDto,
+            BatchDto,
+            BatchStatusDto,
+            BatchErrorsDto,
+            BatchErrorDto,
+            BatchRequestCountsDto,
+            BatchCostDto,
+            BatchListDto,
+            ModerationRequest,
+            ModerationInput,
+            ModerationInputPart,
+            ModerationResponse,
+            ModerationResult,
+            TokenizeRequest,
+            TokenizeInput,
+            TokenizeResponse,
         )
     ),
     tags(
         (name = "Health", description = "Health check endpoints"),
-        (name = "Audio", description = "Audio transcription endpoints"),
-        (name = "Chat Completions", description = "OpenAI-compatible chat completions API")
+        (name = "Audio", description = "Audio transcription and speech synthesis endpoints"),
+        (name = "Images", description = "Image generation, edit and variation endpoints"),
+        (name = "Chat Completions", description = "OpenAI-compatible chat completions API"),
+        (name = "Messages", description = "Anthropic Messages-compatible API"),
+        (name = "Gemini", description = "Gemini generateContent-compatible API"),
+        (name = "Responses", description = "OpenAI Responses-compatible API"),
+        (name = "Files", description = "OpenAI Files-compatible API"),
+        (name = "Batches", description = "OpenAI Batch-compatible API executed by the gateway"),
+        (name = "Moderations", description = "OpenAI Moderations-compatible API"),
+        (name = "Realtime", description = "Realtime speech WebSocket proxy"),
+        (name = "Tokenize", description = "Token counting with the gateway's tokenizers")
     ),
     info(
         title = "AI Gateway - LLM Hub Data Plane",
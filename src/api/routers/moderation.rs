@@ -0,0 +1,8 @@
+use axum::{routing::post, Router};
+
+use crate::api::handlers::moderation::create_moderation;
+
+/// Moderations API router
+pub fn moderations_router() -> Router<std::sync::Arc<crate::AppState>> {
+    Router::new().route("/", post(create_moderation))
+}
@@ -0,0 +1,12 @@
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+use crate::api::handlers::realtime::realtime_session;
+use crate::AppState;
+
+/// Realtime speech router
+///
+/// WebSocket proxy to the upstream realtime API
+pub fn realtime_router() -> Router<Arc<AppState>> {
+    Router::new().route("/", get(realtime_session))
+}
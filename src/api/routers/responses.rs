@@ -0,0 +1,17 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use std::sync::Arc;
+
+use crate::api::handlers::responses::{create_response, delete_response, get_response};
+use crate::AppState;
+
+/// Create the responses router
+///
+/// Provides the OpenAI Responses-compatible API
+pub fn responses_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", post(create_response))
+        .route("/:response_id", get(get_response).delete(delete_response))
+}
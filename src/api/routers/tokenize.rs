@@ -0,0 +1,8 @@
+use axum::{routing::post, Router};
+
+use crate::api::handlers::tokenize::tokenize;
+
+/// Token counting router
+pub fn tokenize_router() -> Router<std::sync::Arc<crate::AppState>> {
+    Router::new().route("/", post(tokenize))
+}
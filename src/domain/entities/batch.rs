@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Batch of chat or embedding requests executed by the gateway's worker pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchJob {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<bson::oid::ObjectId>,
+    pub batch_id: String,
+    #[serde(with = "crate::shared::utils::string_or_objectid")]
+    pub project_id: String,  // Deserializes ObjectId from MongoDB to String
+    pub endpoint: String,
+    pub input_file_id: String,
+    pub completion_window: String,
+    pub status: BatchStatus,
+    pub output_file_id: Option<String>,
+    pub error_file_id: Option<String>,
+    #[serde(default)]
+    pub errors: Vec<BatchError>,
+    pub request_counts: BatchRequestCounts,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    pub cost_usd: f64,       // Discounted cost of the requests run so far
+    pub attempts: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub in_progress_at: Option<DateTime<Utc>>,
+    pub finalizing_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub failed_at: Option<DateTime<Utc>>,
+    pub expired_at: Option<DateTime<Utc>>,
+    pub cancelling_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+/// Batch lifecycle status, named as in the OpenAI Batch API
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    /// Queued; the input was validated on submission
+    Validating,
+    InProgress,
+    Finalizing,
+    Completed,
+    Failed,
+    Expired,
+    Cancelling,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BatchRequestCounts {
+    pub total: u32,
+    pub completed: u32,
+    pub failed: u32,
+}
+
+/// Problem with the batch as a whole, e.g. an unreadable input file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchError {
+    pub code: String,
+    pub message: String,
+    pub line: Option<u32>,
+}
+
+impl BatchJob {
+    pub fn new(
+        project_id: String,
+        endpoint: String,
+        input_file_id: String,
+        total_requests: u32,
+        metadata: HashMap<String, String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            batch_id: format!("batch_{}", uuid::Uuid::new_v4().simple()),
+            project_id,
+            endpoint,
+            input_file_id,
+            completion_window: "24h".to_string(),
+            status: BatchStatus::Validating,
+            output_file_id: None,
+            error_file_id: None,
+            errors: Vec::new(),
+            request_counts: BatchRequestCounts {
+                total: total_requests,
+                ..Default::default()
+            },
+            metadata,
+            cost_usd: 0.0,
+            attempts: 0,
+            created_at: now,
+            updated_at: now,
+            expires_at: now + chrono::Duration::hours(24),
+            in_progress_at: None,
+            finalizing_at: None,
+            completed_at: None,
+            failed_at: None,
+            expired_at: None,
+            cancelling_at: None,
+            cancelled_at: None,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// File uploaded through `/v1/files` or written by the gateway, such as
+/// batch output; the bytes live in GridFS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredFile {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<bson::oid::ObjectId>,
+    pub file_id: String,
+    #[serde(with = "crate::shared::utils::string_or_objectid")]
+    pub project_id: String,  // Deserializes ObjectId from MongoDB to String
+    pub filename: String,
+    pub purpose: String,
+    pub bytes: usize,
+    pub upload_id: String,   // GridFS file holding the content
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredFile {
+    pub fn new(project_id: String, filename: String, purpose: String, bytes: usize) -> Self {
+        Self {
+            id: None,
+            file_id: format!("file-{}", uuid::Uuid::new_v4().simple()),
+            project_id,
+            filename,
+            purpose,
+            bytes,
+            upload_id: String::new(),
+            created_at: Utc::now(),
+        }
+    }
+}
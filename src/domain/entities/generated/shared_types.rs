@@ -47,10 +47,11 @@ pub enum ProjectRole {
 }
 
 /// Type of LLM provider API key.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum LlmApiKeyType {
     Admin,
+    #[default]
     Standard,
     ServiceAccount,
     ShortTerm,
@@ -59,12 +60,6 @@ pub enum LlmApiKeyType {
     WorkspaceScoped,
 }
 
-impl Default for LlmApiKeyType {
-    fn default() -> Self {
-        Self::Standard
-    }
-}
-
 /// Rate limiting configuration for a project
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimits {
@@ -0,0 +1,143 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Per-project guardrail policy, managed by the control plane in the
+/// `guardrail_policies` collection. Projects without one are not checked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuardrailPolicy {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<bson::oid::ObjectId>,
+    #[serde(with = "crate::shared::utils::string_or_objectid")]
+    pub project_id: String,  // Deserializes ObjectId from MongoDB to String
+    pub moderation: Option<ModerationPolicy>,
+    pub pii: Option<PiiPolicy>,
+    pub injection: Option<InjectionPolicy>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Moderation of chat traffic through the provider's moderation model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationPolicy {
+    /// Moderate request messages before dispatch
+    #[serde(default)]
+    pub input: bool,
+    /// Moderate the reply before it is returned; buffers streamed replies
+    #[serde(default)]
+    pub output: bool,
+    #[serde(default)]
+    pub action: ModerationAction,
+    /// Moderation model (default: omni-moderation-latest)
+    pub model: Option<String>,
+    /// Categories that block; empty blocks on any flagged category
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Score at or above which a category blocks, overriding the provider's flag
+    #[serde(default)]
+    pub thresholds: HashMap<String, f64>,
+}
+
+/// What happens to flagged traffic
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// Fail with a `content_policy_violation` error
+    #[default]
+    Reject,
+    /// Return an empty reply with `finish_reason: content_filter`
+    ContentFilter,
+}
+
+/// Detection of personal data before it reaches a provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PiiPolicy {
+    #[serde(default)]
+    pub mode: PiiMode,
+    /// Built-in detectors to run; empty runs all of them
+    #[serde(default)]
+    pub entities: Vec<PiiEntity>,
+    /// Project-specific patterns, e.g. customer or contract numbers
+    #[serde(default)]
+    pub custom_patterns: Vec<CustomPiiPattern>,
+}
+
+/// What happens to detected personal data
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiMode {
+    /// Fail the request with a `content_policy_violation` error
+    Block,
+    /// Replace each match with its entity name, e.g. `[EMAIL]`
+    #[default]
+    Redact,
+    /// Replace each match with a numbered placeholder, e.g. `[EMAIL_1]`,
+    /// and restore the original values in the reply
+    Tokenize,
+}
+
+/// Built-in PII detectors
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiEntity {
+    Email,
+    Phone,
+    /// Card numbers passing the Luhn check
+    CreditCard,
+    /// IBANs passing the ISO 13616 checksum
+    Iban,
+    /// US social security and UK national insurance numbers
+    NationalId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomPiiPattern {
+    /// Placeholder name, upper-cased in output (e.g. `customer_id` → `[CUSTOMER_ID]`)
+    pub name: String,
+    /// Regular expression in `regex` crate syntax
+    pub pattern: String,
+}
+
+/// Scoring of user input, tool results and retrieved documents for
+/// prompt-injection and jailbreak attempts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionPolicy {
+    /// Score (0-1) at or above which the action applies
+    #[serde(default = "default_injection_threshold")]
+    pub threshold: f64,
+    #[serde(default)]
+    pub action: InjectionAction,
+    /// Chat model asked to score the input as well as the built-in rules
+    pub classifier_model: Option<String>,
+}
+
+fn default_injection_threshold() -> f64 {
+    0.5
+}
+
+/// What happens to input scoring at or above the threshold
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectionAction {
+    /// Record the result on the usage log only
+    Log,
+    /// Also report the result in the reply's `x_llmhub` metadata
+    #[default]
+    Flag,
+    /// Fail the request with a `content_policy_violation` error
+    Block,
+}
+
+/// Prompt-injection result recorded on the usage log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionResult {
+    /// Highest score of any detector
+    pub score: f64,
+    /// Whether the score reached the policy threshold
+    pub flagged: bool,
+    pub action: InjectionAction,
+    /// Rules that matched, as `rule@role`
+    pub rules: Vec<String>,
+    /// Score of each detector that ran
+    pub detectors: BTreeMap<String, f64>,
+}
@@ -0,0 +1,45 @@
+use bytes::Bytes;
+
+/// Image endpoint a request targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageOperation {
+    Generation,
+    Edit,
+    Variation,
+}
+
+impl ImageOperation {
+    /// Path below the provider base URL
+    pub fn path(&self) -> &'static str {
+        match self {
+            ImageOperation::Generation => "images/generations",
+            ImageOperation::Edit => "images/edits",
+            ImageOperation::Variation => "images/variations",
+        }
+    }
+}
+
+/// Image generation, edit or variation request entity
+#[derive(Debug, Clone)]
+pub struct ImageRequest {
+    pub operation: ImageOperation,
+    pub model: String,
+    pub prompt: Option<String>,
+    pub n: u32,
+    pub size: Option<String>,
+    pub quality: Option<String>,
+    /// Remaining OpenAI parameters (`response_format`, `style`, `background`, ...),
+    /// forwarded as given
+    pub options: serde_json::Map<String, serde_json::Value>,
+    /// Source images for edits and variations
+    pub images: Vec<ImageUpload>,
+    pub mask: Option<ImageUpload>,
+    pub llm_api_key_id: Option<String>,
+}
+
+/// Uploaded image forwarded to the provider
+#[derive(Debug, Clone)]
+pub struct ImageUpload {
+    pub file_name: String,
+    pub data: Bytes,
+}
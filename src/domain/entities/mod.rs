@@ -1,5 +1,12 @@
+pub mod batch;
+pub mod file;
 pub mod generated;  // Generated types from OpenAPI schemas
+pub mod guardrail;
+pub mod image;
+pub mod speech;
+pub mod stored_response;
 pub mod transcription;
+pub mod transcription_job;
 pub mod usage;
 
 // Re-export shared entities from generated module
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// Text-to-speech request entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechRequest {
+    pub model: String,
+    pub input: String,
+    pub voice: String,
+    pub response_format: SpeechFormat,
+    pub speed: Option<f32>,
+    pub llm_api_key_id: Option<String>,
+}
+
+/// Audio container/codec returned by speech synthesis
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeechFormat {
+    #[default]
+    Mp3,
+    Opus,
+    Aac,
+    Flac,
+    Wav,
+    Pcm,
+}
+
+impl SpeechFormat {
+    /// Wire value expected by OpenAI-compatible APIs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpeechFormat::Mp3 => "mp3",
+            SpeechFormat::Opus => "opus",
+            SpeechFormat::Aac => "aac",
+            SpeechFormat::Flac => "flac",
+            SpeechFormat::Wav => "wav",
+            SpeechFormat::Pcm => "pcm",
+        }
+    }
+
+    /// Content-Type header for the synthesized audio
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            SpeechFormat::Mp3 => "audio/mpeg",
+            SpeechFormat::Opus => "audio/ogg",
+            SpeechFormat::Aac => "audio/aac",
+            SpeechFormat::Flac => "audio/flac",
+            SpeechFormat::Wav => "audio/wav",
+            // Raw 24kHz 16-bit signed little-endian samples
+            SpeechFormat::Pcm => "audio/pcm",
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::dto::{ChatMessage, ResponseObject};
+
+/// Responses API response kept so later requests can continue its
+/// conversation with `previous_response_id`, whichever provider served it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredResponse {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<bson::oid::ObjectId>,
+    pub response_id: String,
+    #[serde(with = "crate::shared::utils::string_or_objectid")]
+    pub project_id: String,  // Deserializes ObjectId from MongoDB to String
+    pub response: ResponseObject,
+    /// Conversation up to and including this response's output, without
+    /// instructions
+    pub conversation: Vec<ChatMessage>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredResponse {
+    pub fn new(project_id: String, response: ResponseObject, conversation: Vec<ChatMessage>) -> Self {
+        Self {
+            id: None,
+            response_id: response.id.clone(),
+            project_id,
+            response,
+            conversation,
+            created_at: Utc::now(),
+        }
+    }
+}
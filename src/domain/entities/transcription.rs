@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -6,7 +7,8 @@ use super::LlmProvider;
 /// Transcription request entity
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionRequest {
-    pub file_data: Vec<u8>,
+    pub file_data: Bytes,  // Usually backed by the spooled upload, so clones are cheap
+    pub file_hash: Option<String>,  // SHA-256 of file_data when computed on receipt
     pub file_name: String,
     pub model: Option<String>,
     pub language: Option<String>,
@@ -15,6 +17,72 @@ pub struct TranscriptionRequest {
     pub temperature: Option<f32>,
     pub timestamp_granularities: Option<Vec<TimestampGranularity>>,
     pub llm_api_key_id: Option<String>,
+    pub subtitle_options: SubtitleOptions,
+    pub provider: Option<TranscriptionProviderKind>,  // Defaults to OpenAI
+    pub diarize: bool,  // Label segments and words with speakers
+}
+
+/// Speech-to-text backend handling a transcription
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionProviderKind {
+    #[default]
+    Openai,
+    Deepgram,
+    Assemblyai,
+    Google,
+    Azure,
+    WhisperServer,  // Self-hosted OpenAI-compatible server (whisper.cpp, faster-whisper)
+}
+
+impl TranscriptionProviderKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TranscriptionProviderKind::Openai => "openai",
+            TranscriptionProviderKind::Deepgram => "deepgram",
+            TranscriptionProviderKind::Assemblyai => "assemblyai",
+            TranscriptionProviderKind::Google => "google",
+            TranscriptionProviderKind::Azure => "azure",
+            TranscriptionProviderKind::WhisperServer => "whisper_server",
+        }
+    }
+
+    /// Provider whose project LLM keys authenticate this backend, if any
+    pub fn llm_provider(&self) -> Option<LlmProvider> {
+        match self {
+            TranscriptionProviderKind::Openai => Some(LlmProvider::Openai),
+            TranscriptionProviderKind::Google => Some(LlmProvider::Google),
+            TranscriptionProviderKind::Azure => Some(LlmProvider::Azure),
+            TranscriptionProviderKind::Deepgram
+            | TranscriptionProviderKind::Assemblyai
+            | TranscriptionProviderKind::WhisperServer => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TranscriptionProviderKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for TranscriptionProviderKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "openai" => Ok(TranscriptionProviderKind::Openai),
+            "deepgram" => Ok(TranscriptionProviderKind::Deepgram),
+            "assemblyai" | "assembly_ai" => Ok(TranscriptionProviderKind::Assemblyai),
+            "google" => Ok(TranscriptionProviderKind::Google),
+            "azure" => Ok(TranscriptionProviderKind::Azure),
+            "whisper_server" | "whisper-server" | "local" => Ok(TranscriptionProviderKind::WhisperServer),
+            other => Err(format!(
+                "Unknown transcription provider '{}': expected one of openai, deepgram, assemblyai, google, azure, whisper_server",
+                other
+            )),
+        }
+    }
 }
 
 /// Response format enum
@@ -26,6 +94,22 @@ pub enum ResponseFormat {
     Srt,
     VerboseJson,
     Vtt,
+    Tsv,
+}
+
+/// Options for locally rendered SRT, VTT and TSV output
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubtitleOptions {
+    pub max_line_length: Option<usize>,
+    pub max_cue_chars: Option<usize>,
+    pub highlight_words: bool,
+}
+
+impl SubtitleOptions {
+    /// Whether cues need word timestamps rather than interpolated ones
+    pub fn needs_word_timestamps(&self) -> bool {
+        self.highlight_words || self.max_cue_chars.is_some()
+    }
 }
 
 /// Timestamp granularity enum
@@ -59,6 +143,7 @@ pub struct TranscriptionSegment {
     pub avg_logprob: Option<f32>,
     pub compression_ratio: Option<f32>,
     pub no_speech_prob: Option<f32>,
+    pub speaker: Option<String>,  // Set when diarization was requested
 }
 
 /// Transcription word with timing
@@ -67,6 +152,7 @@ pub struct TranscriptionWord {
     pub word: String,
     pub start: f32,
     pub end: f32,
+    pub speaker: Option<String>,
 }
 
 /// Usage information
@@ -85,7 +171,7 @@ pub struct TranscriptionHistory {
     pub transcription_id: String,
     #[serde(with = "crate::shared::utils::string_or_objectid")]
     pub project_id: String,  // Deserializes ObjectId from MongoDB to String
-    pub provider: LlmProvider,
+    pub provider: TranscriptionProviderKind,
     pub file_hash: String,
     pub file_name: String,
     pub file_size_bytes: usize,
@@ -93,6 +179,8 @@ pub struct TranscriptionHistory {
     pub model: String,
     pub language: Option<String>,
     pub text: String,
+    #[serde(default)]
+    pub speakers: Option<Vec<String>>,  // Distinct speaker labels when diarized
     pub cost_usd: f64,
     pub response_time_ms: u64,
     pub from_cache: bool,
@@ -100,9 +188,10 @@ pub struct TranscriptionHistory {
 }
 
 impl TranscriptionHistory {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         project_id: String,
-        provider: LlmProvider,
+        provider: TranscriptionProviderKind,
         file_hash: String,
         file_name: String,
         file_size_bytes: usize,
@@ -110,6 +199,7 @@ impl TranscriptionHistory {
         model: String,
         language: Option<String>,
         text: String,
+        speakers: Option<Vec<String>>,
         cost_usd: f64,
         response_time_ms: u64,
         from_cache: bool,
@@ -126,6 +216,7 @@ impl TranscriptionHistory {
             model,
             language,
             text,
+            speakers,
             cost_usd,
             response_time_ms,
             from_cache,
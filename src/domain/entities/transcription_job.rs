@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::transcription::{
+    ResponseFormat, SubtitleOptions, TimestampGranularity, TranscriptionProviderKind,
+    TranscriptionRequest, TranscriptionResponse,
+};
+
+/// Asynchronous transcription job, persisted while the upload is processed
+/// by the background worker pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionJob {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<bson::oid::ObjectId>,
+    pub job_id: String,
+    #[serde(with = "crate::shared::utils::string_or_objectid")]
+    pub project_id: String,  // Deserializes ObjectId from MongoDB to String
+    pub status: TranscriptionJobStatus,
+    pub upload_id: String,   // GridFS file holding the uploaded audio
+    pub file_name: String,
+    pub file_size_bytes: usize,
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub prompt: Option<String>,
+    pub response_format: Option<ResponseFormat>,
+    pub temperature: Option<f32>,
+    pub timestamp_granularities: Option<Vec<TimestampGranularity>>,
+    pub llm_api_key_id: Option<String>,
+    #[serde(default)]
+    pub subtitle_options: SubtitleOptions,
+    #[serde(default)]
+    pub provider: Option<TranscriptionProviderKind>,
+    #[serde(default)]
+    pub diarize: bool,
+    pub webhook_url: Option<String>,
+    pub webhook_secret: Option<String>,  // AES-256-GCM encrypted
+    pub result: Option<TranscriptionResponse>,
+    pub error: Option<String>,
+    pub attempts: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Transcription job lifecycle status
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionJobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl TranscriptionJob {
+    pub fn new(
+        project_id: String,
+        upload_id: String,
+        request: &TranscriptionRequest,
+        webhook_url: Option<String>,
+        webhook_secret: Option<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            job_id: format!("trjob_{}", uuid::Uuid::new_v4()),
+            project_id,
+            status: TranscriptionJobStatus::Queued,
+            upload_id,
+            file_name: request.file_name.clone(),
+            file_size_bytes: request.file_data.len(),
+            model: request.model.clone(),
+            language: request.language.clone(),
+            prompt: request.prompt.clone(),
+            response_format: request.response_format.clone(),
+            temperature: request.temperature,
+            timestamp_granularities: request.timestamp_granularities.clone(),
+            llm_api_key_id: request.llm_api_key_id.clone(),
+            subtitle_options: request.subtitle_options.clone(),
+            provider: request.provider,
+            diarize: request.diarize,
+            webhook_url,
+            webhook_secret,
+            result: None,
+            error: None,
+            attempts: 0,
+            created_at: now,
+            updated_at: now,
+            started_at: None,
+            completed_at: None,
+        }
+    }
+
+    /// Rebuild the original transcription request around the stored upload
+    pub fn to_request(&self, file_data: Vec<u8>) -> TranscriptionRequest {
+        TranscriptionRequest {
+            file_data: file_data.into(),
+            file_hash: None,
+            file_name: self.file_name.clone(),
+            model: self.model.clone(),
+            language: self.language.clone(),
+            prompt: self.prompt.clone(),
+            response_format: self.response_format.clone(),
+            temperature: self.temperature,
+            timestamp_granularities: self.timestamp_granularities.clone(),
+            llm_api_key_id: self.llm_api_key_id.clone(),
+            subtitle_options: self.subtitle_options.clone(),
+            provider: self.provider,
+            diarize: self.diarize,
+        }
+    }
+}
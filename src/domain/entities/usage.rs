@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::guardrail::InjectionResult;
 use super::LlmProvider;
 
 /// Usage log entity for tracking API usage and costs
@@ -19,6 +20,9 @@ pub struct UsageLog {
     pub cost_data: CostData,
     pub cache_info: Option<CacheInfo>,
     pub error: Option<String>,
+    /// Prompt-injection guardrail result, when the project has the guardrail enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_injection: Option<InjectionResult>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -28,9 +32,14 @@ pub struct UsageLog {
 pub enum ApiEndpoint {
     ChatCompletions,
     AudioTranscribe,
+    AudioSpeech,
     AudioTranslate,
     Realtime,
     Embeddings,
+    ImageGeneration,
+    ImageEdit,
+    ImageVariation,
+    Moderations,
 }
 
 /// Request metadata
@@ -87,6 +96,7 @@ pub struct CostData {
 }
 
 impl UsageLog {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         project_id: String,
         api_endpoint: ApiEndpoint,
@@ -110,6 +120,7 @@ impl UsageLog {
             cost_data,
             cache_info,
             error,
+            prompt_injection: None,
             created_at: Utc::now(),
         }
     }
@@ -119,7 +130,7 @@ impl UsageLog {
     }
 
     pub fn is_cached(&self) -> bool {
-        self.cache_info.as_ref().map_or(false, |info| info.cache_hit)
+        self.cache_info.as_ref().is_some_and(|info| info.cache_hit)
     }
 
     pub fn get_actual_cost(&self) -> f64 {
@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::entities::batch::{BatchJob, BatchRequestCounts, BatchStatus};
+use crate::shared::error::AppError;
+
+/// Repository trait for batches
+#[async_trait]
+pub trait BatchRepository: Send + Sync {
+    /// Create new batch
+    async fn create(&self, batch: &BatchJob) -> Result<(), AppError>;
+
+    /// Find batch by ID within a project
+    async fn find_by_id(&self, project_id: &str, batch_id: &str) -> Result<BatchJob, AppError>;
+
+    /// List a project's batches, newest first, optionally those created before a time
+    async fn list(
+        &self,
+        project_id: &str,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<BatchJob>, AppError>;
+
+    /// Current status of a batch
+    async fn status(&self, batch_id: &str) -> Result<BatchStatus, AppError>;
+
+    /// Atomically claim the oldest queued batch and mark it in progress
+    async fn claim_next(&self) -> Result<Option<BatchJob>, AppError>;
+
+    /// Record progress of a running batch
+    async fn update_progress(
+        &self,
+        batch_id: &str,
+        counts: &BatchRequestCounts,
+        cost_usd: f64,
+    ) -> Result<(), AppError>;
+
+    /// Replace the stored batch, e.g. once it has finished
+    async fn save(&self, batch: &BatchJob) -> Result<(), AppError>;
+
+    /// Cancel a queued batch, or ask the worker to stop a running one.
+    /// Returns the updated batch.
+    async fn cancel(&self, project_id: &str, batch_id: &str) -> Result<BatchJob, AppError>;
+
+    /// Requeue batches left running by a crashed worker; batches out of
+    /// attempts are failed instead. Returns the number of batches requeued.
+    async fn requeue_stale(&self, started_before: DateTime<Utc>, max_attempts: u32) -> Result<u64, AppError>;
+}
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+
+use crate::domain::entities::file::StoredFile;
+use crate::shared::error::AppError;
+
+/// Repository trait for `/v1/files` files and their content
+#[async_trait]
+pub trait FileRepository: Send + Sync {
+    /// Store content and metadata, returning the stored file
+    async fn create(&self, file: StoredFile, data: &[u8]) -> Result<StoredFile, AppError>;
+
+    /// Find file by ID within a project
+    async fn find_by_id(&self, project_id: &str, file_id: &str) -> Result<StoredFile, AppError>;
+
+    /// List a project's files, newest first
+    async fn list(&self, project_id: &str, purpose: Option<&str>, limit: i64) -> Result<Vec<StoredFile>, AppError>;
+
+    /// Load a file's content
+    async fn load_content(&self, file: &StoredFile) -> Result<Vec<u8>, AppError>;
+
+    /// Delete file and content by ID within a project
+    async fn delete(&self, project_id: &str, file_id: &str) -> Result<(), AppError>;
+}
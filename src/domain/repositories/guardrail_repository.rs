@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::domain::entities::guardrail::GuardrailPolicy;
+use crate::shared::error::AppError;
+
+/// Repository trait for per-project guardrail policies
+#[async_trait]
+pub trait GuardrailRepository: Send + Sync {
+    /// Find the project's policy, if it has one
+    async fn find_by_project(&self, project_id: &str) -> Result<Option<GuardrailPolicy>, AppError>;
+}
@@ -10,6 +10,9 @@ pub trait LlmApiKeyRepository: Send + Sync {
     /// Find LLM API key by ID
     async fn find_by_id(&self, key_id: &str) -> Result<LlmApiKey, AppError>;
 
+    /// Find LLM API key by ID, only if it belongs to the project
+    async fn find_by_project(&self, project_id: &str, key_id: &str) -> Result<LlmApiKey, AppError>;
+
     /// Find default key for project and provider
     async fn find_default_for_provider(
         &self,
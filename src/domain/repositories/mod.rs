@@ -1,9 +1,19 @@
+pub mod batch_repository;
+pub mod file_repository;
+pub mod guardrail_repository;
 pub mod llm_api_key_repository;
 pub mod project_repository;
+pub mod response_repository;
+pub mod transcription_job_repository;
 pub mod transcription_repository;
 pub mod usage_repository;
 
+pub use batch_repository::BatchRepository;
+pub use file_repository::FileRepository;
+pub use guardrail_repository::GuardrailRepository;
 pub use llm_api_key_repository::LlmApiKeyRepository;
 pub use project_repository::ProjectRepository;
+pub use response_repository::ResponseRepository;
+pub use transcription_job_repository::TranscriptionJobRepository;
 pub use transcription_repository::TranscriptionRepository;
 pub use usage_repository::UsageRepository;
\ No newline at end of file
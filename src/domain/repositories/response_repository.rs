@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use crate::domain::entities::stored_response::StoredResponse;
+use crate::shared::error::AppError;
+
+/// Repository trait for stored Responses API responses
+#[async_trait]
+pub trait ResponseRepository: Send + Sync {
+    /// Store a response
+    async fn create(&self, response: &StoredResponse) -> Result<(), AppError>;
+
+    /// Find response by ID within a project
+    async fn find_by_id(&self, project_id: &str, response_id: &str) -> Result<StoredResponse, AppError>;
+
+    /// Delete response by ID within a project
+    async fn delete(&self, project_id: &str, response_id: &str) -> Result<(), AppError>;
+}
@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::entities::transcription::TranscriptionResponse;
+use crate::domain::entities::transcription_job::TranscriptionJob;
+use crate::shared::error::AppError;
+
+/// Repository trait for asynchronous transcription jobs and their uploads
+#[async_trait]
+pub trait TranscriptionJobRepository: Send + Sync {
+    /// Store uploaded audio, returning its storage ID
+    async fn store_upload(&self, file_name: &str, data: &[u8]) -> Result<String, AppError>;
+
+    /// Load uploaded audio by storage ID
+    async fn load_upload(&self, upload_id: &str) -> Result<Vec<u8>, AppError>;
+
+    /// Delete uploaded audio by storage ID
+    async fn delete_upload(&self, upload_id: &str) -> Result<(), AppError>;
+
+    /// Create new job
+    async fn create(&self, job: &TranscriptionJob) -> Result<(), AppError>;
+
+    /// Find job by ID within a project
+    async fn find_by_id(&self, project_id: &str, job_id: &str) -> Result<TranscriptionJob, AppError>;
+
+    /// Atomically claim the oldest queued job and mark it running
+    async fn claim_next(&self) -> Result<Option<TranscriptionJob>, AppError>;
+
+    /// Mark job as succeeded with its result
+    async fn mark_succeeded(&self, job_id: &str, result: &TranscriptionResponse) -> Result<(), AppError>;
+
+    /// Mark job as failed with an error message
+    async fn mark_failed(&self, job_id: &str, error: &str) -> Result<(), AppError>;
+
+    /// Requeue jobs left running by a crashed worker; jobs out of attempts
+    /// are failed instead. Returns the number of jobs requeued.
+    async fn requeue_stale(&self, started_before: DateTime<Utc>, max_attempts: u32) -> Result<u64, AppError>;
+}
@@ -0,0 +1,656 @@
+//! Splitting of long recordings into overlapping chunks that fit provider
+//! upload limits. Chunks are cut on frame boundaries and re-wrapped in a
+//! minimal container header, so no re-encoding is needed.
+
+use std::ops::Range;
+
+use bytes::Bytes;
+
+use crate::shared::error::AppError;
+
+/// A piece of a larger recording, described by its byte range so the audio
+/// is only copied when the chunk is sent
+#[derive(Debug, Clone)]
+pub struct AudioChunk {
+    /// Container header the body needs to stand alone; empty for MP3
+    pub header: Vec<u8>,
+    /// Frames of the chunk within the original recording
+    pub body: Range<usize>,
+    /// Position of the chunk start within the original recording
+    pub offset_seconds: f32,
+    pub duration_seconds: f32,
+}
+
+impl AudioChunk {
+    /// The chunk as a standalone file. Headerless chunks share the
+    /// recording's memory; the others are assembled here.
+    pub fn data(&self, recording: &Bytes) -> Bytes {
+        let body = recording.slice(self.body.clone());
+        if self.header.is_empty() {
+            return body;
+        }
+        let mut data = Vec::with_capacity(self.header.len() + body.len());
+        data.extend_from_slice(&self.header);
+        data.extend_from_slice(&body);
+        data.into()
+    }
+}
+
+/// Container formats that can be split without re-encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplittableFormat {
+    Wav,
+    Flac,
+    Mp3,
+}
+
+impl SplittableFormat {
+    /// Detect the container from its leading bytes
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WAVE" {
+            Some(SplittableFormat::Wav)
+        } else if data.starts_with(b"fLaC") {
+            Some(SplittableFormat::Flac)
+        } else if data.starts_with(b"ID3")
+            || (data.len() >= 4 && mp3_frame_header(&data[0..4]).is_some())
+        {
+            Some(SplittableFormat::Mp3)
+        } else {
+            None
+        }
+    }
+
+    /// File extension used when uploading a chunk
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SplittableFormat::Wav => "wav",
+            SplittableFormat::Flac => "flac",
+            SplittableFormat::Mp3 => "mp3",
+        }
+    }
+}
+
+/// Contiguous run of encoded audio that can be cut on either side
+#[derive(Debug, Clone, Copy)]
+struct Unit {
+    offset: usize,
+    len: usize,
+    start_seconds: f64,
+    duration_seconds: f64,
+}
+
+/// Split a recording into chunks no larger than `max_chunk_bytes`, each
+/// overlapping the previous one by roughly `overlap_seconds`
+pub fn split_audio(
+    data: &[u8],
+    max_chunk_bytes: usize,
+    overlap_seconds: f32,
+) -> Result<Vec<AudioChunk>, AppError> {
+    let format = SplittableFormat::detect(data).ok_or_else(|| {
+        AppError::BadRequest(
+            "Audio exceeds the provider upload limit and can only be split when it is WAV, FLAC or MP3"
+                .to_string(),
+        )
+    })?;
+
+    let (header, units) = match format {
+        SplittableFormat::Wav => parse_wav(data)?,
+        SplittableFormat::Flac => parse_flac(data)?,
+        SplittableFormat::Mp3 => parse_mp3(data)?,
+    };
+
+    if units.is_empty() {
+        return Err(AppError::BadRequest("Audio file contains no audio frames".to_string()));
+    }
+
+    let ranges = group_units(&units, header.len(), max_chunk_bytes, overlap_seconds as f64)?;
+
+    Ok(ranges
+        .into_iter()
+        .map(|range| {
+            let first = units[range.start];
+            let last = units[range.end - 1];
+            let body = first.offset..last.offset + last.len;
+            let duration = last.start_seconds + last.duration_seconds - first.start_seconds;
+
+            let header = match format {
+                SplittableFormat::Wav => wav_header(&header, body.len()),
+                SplittableFormat::Flac | SplittableFormat::Mp3 => header.clone(),
+            };
+
+            AudioChunk {
+                header,
+                body,
+                offset_seconds: first.start_seconds as f32,
+                duration_seconds: duration as f32,
+            }
+        })
+        .collect())
+}
+
+/// Greedily pack units into chunks, starting each chunk `overlap` seconds
+/// before the end of the previous one
+fn group_units(
+    units: &[Unit],
+    header_len: usize,
+    max_chunk_bytes: usize,
+    overlap: f64,
+) -> Result<Vec<Range<usize>>, AppError> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let mut end = start;
+        let mut bytes = header_len;
+        while end < units.len() && bytes + units[end].len <= max_chunk_bytes {
+            bytes += units[end].len;
+            end += 1;
+        }
+
+        if end == start {
+            return Err(AppError::BadRequest(
+                "Audio frame is larger than the provider upload limit".to_string(),
+            ));
+        }
+
+        ranges.push(start..end);
+        if end == units.len() {
+            return Ok(ranges);
+        }
+
+        // Always advance by at least one unit
+        let overlap_start = units[end].start_seconds - overlap;
+        let mut next = end;
+        while next > start + 1 && units[next - 1].start_seconds >= overlap_start {
+            next -= 1;
+        }
+        start = next;
+    }
+}
+
+// ============= WAV =============
+
+/// Returns the `fmt ` chunk body as header and fixed 100ms units of PCM data
+fn parse_wav(data: &[u8]) -> Result<(Vec<u8>, Vec<Unit>), AppError> {
+    let mut fmt: Option<&[u8]> = None;
+    let mut pos = 12;
+
+    while pos + 8 <= data.len() {
+        let id = &data[pos..pos + 4];
+        let size = u32::from_le_bytes([data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]])
+            as usize;
+        let body_start = pos + 8;
+
+        match id {
+            b"fmt " => {
+                let end = body_start + size;
+                if size < 16 || end > data.len() {
+                    return Err(invalid_audio("WAV fmt chunk is truncated"));
+                }
+                fmt = Some(&data[body_start..end]);
+            }
+            b"data" => {
+                let fmt = fmt.ok_or_else(|| invalid_audio("WAV data chunk precedes fmt chunk"))?;
+                let byte_rate = u32::from_le_bytes([fmt[8], fmt[9], fmt[10], fmt[11]]) as usize;
+                let block_align = u16::from_le_bytes([fmt[12], fmt[13]]) as usize;
+                if byte_rate == 0 || block_align == 0 {
+                    return Err(invalid_audio("WAV fmt chunk has zero byte rate"));
+                }
+
+                // Streamed WAVs may leave the size as 0 or u32::MAX
+                let available = data.len() - body_start;
+                let size = if size == 0 || size > available { available } else { size };
+                let size = size - size % block_align;
+
+                let unit_len = (byte_rate / 10).max(block_align);
+                let unit_len = unit_len - unit_len % block_align;
+                let units = (0..size)
+                    .step_by(unit_len)
+                    .map(|offset| Unit {
+                        offset: body_start + offset,
+                        len: unit_len.min(size - offset),
+                        start_seconds: offset as f64 / byte_rate as f64,
+                        duration_seconds: unit_len.min(size - offset) as f64 / byte_rate as f64,
+                    })
+                    .collect();
+
+                return Ok((fmt.to_vec(), units));
+            }
+            _ => {}
+        }
+
+        pos = body_start + size + (size & 1);
+    }
+
+    Err(invalid_audio("WAV file has no data chunk"))
+}
+
+/// Build a canonical RIFF/WAVE header around an existing fmt chunk body
+fn wav_header(fmt: &[u8], data_len: usize) -> Vec<u8> {
+    let padded_fmt_len = fmt.len() + (fmt.len() & 1);
+    let riff_len = 4 + 8 + padded_fmt_len + 8 + data_len;
+
+    let mut header = Vec::with_capacity(20 + padded_fmt_len + 8);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&(riff_len as u32).to_le_bytes());
+    header.extend_from_slice(b"WAVE");
+    header.extend_from_slice(b"fmt ");
+    header.extend_from_slice(&(fmt.len() as u32).to_le_bytes());
+    header.extend_from_slice(fmt);
+    if fmt.len() & 1 == 1 {
+        header.push(0);
+    }
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&(data_len as u32).to_le_bytes());
+    header
+}
+
+// ============= MP3 =============
+
+/// Parsed MPEG audio frame header
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Mp3Frame {
+    pub(super) len: usize,
+    pub(super) samples: u32,
+    pub(super) sample_rate: u32,
+}
+
+const MP3_BITRATES_V1_L1: [u32; 15] =
+    [0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448];
+const MP3_BITRATES_V1_L2: [u32; 15] =
+    [0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384];
+const MP3_BITRATES_V1_L3: [u32; 15] =
+    [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320];
+const MP3_BITRATES_V2_L1: [u32; 15] =
+    [0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256];
+const MP3_BITRATES_V2_L23: [u32; 15] =
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+pub(super) fn mp3_frame_header(h: &[u8]) -> Option<Mp3Frame> {
+    if h.len() < 4 || h[0] != 0xFF || h[1] & 0xE0 != 0xE0 {
+        return None;
+    }
+
+    // 0 = MPEG 2.5, 1 = reserved, 2 = MPEG 2, 3 = MPEG 1
+    let version = (h[1] >> 3) & 0x03;
+    // 1 = Layer III, 2 = Layer II, 3 = Layer I
+    let layer = (h[1] >> 1) & 0x03;
+    let bitrate_index = (h[2] >> 4) as usize;
+    let sample_rate_index = ((h[2] >> 2) & 0x03) as usize;
+    let padding = ((h[2] >> 1) & 0x01) as u32;
+
+    // Free-format bitrates are not supported
+    if version == 1 || layer == 0 || bitrate_index == 0 || bitrate_index == 15 || sample_rate_index == 3 {
+        return None;
+    }
+
+    let bitrate = match (version, layer) {
+        (3, 3) => MP3_BITRATES_V1_L1[bitrate_index],
+        (3, 2) => MP3_BITRATES_V1_L2[bitrate_index],
+        (3, 1) => MP3_BITRATES_V1_L3[bitrate_index],
+        (_, 3) => MP3_BITRATES_V2_L1[bitrate_index],
+        _ => MP3_BITRATES_V2_L23[bitrate_index],
+    } * 1000;
+
+    let sample_rate = match version {
+        3 => [44100, 48000, 32000][sample_rate_index],
+        2 => [22050, 24000, 16000][sample_rate_index],
+        _ => [11025, 12000, 8000][sample_rate_index],
+    };
+
+    let samples = match (version, layer) {
+        (_, 3) => 384,
+        (_, 2) => 1152,
+        (3, _) => 1152,
+        _ => 576,
+    };
+
+    let len = if layer == 3 {
+        (12 * bitrate / sample_rate + padding) * 4
+    } else {
+        samples / 8 * bitrate / sample_rate + padding
+    };
+
+    Some(Mp3Frame {
+        len: len as usize,
+        samples,
+        sample_rate,
+    })
+}
+
+/// Length of a leading ID3v2 tag, if any
+pub(super) fn id3v2_len(data: &[u8]) -> usize {
+    if data.len() < 10 || !data.starts_with(b"ID3") {
+        return 0;
+    }
+    let size = data[6..10]
+        .iter()
+        .fold(0usize, |acc, b| (acc << 7) | (*b & 0x7F) as usize);
+    let footer = if data[5] & 0x10 != 0 { 10 } else { 0 };
+    10 + size + footer
+}
+
+/// MP3 has no container header; every frame is independently decodable
+fn parse_mp3(data: &[u8]) -> Result<(Vec<u8>, Vec<Unit>), AppError> {
+    let mut units: Vec<Unit> = Vec::new();
+    let mut pos = id3v2_len(data);
+    let mut elapsed = 0.0;
+    let mut synced = false;
+
+    while pos + 4 <= data.len() {
+        // Trailing ID3v1 tag
+        if data.len() - pos == 128 && &data[pos..pos + 3] == b"TAG" {
+            break;
+        }
+
+        let frame = mp3_frame_header(&data[pos..pos + 4])
+            .filter(|f| f.len >= 4 && pos + f.len <= data.len())
+            // After losing sync, require the following frame to line up too
+            .filter(|f| {
+                synced
+                    || pos + f.len + 4 > data.len()
+                    || mp3_frame_header(&data[pos + f.len..pos + f.len + 4]).is_some()
+            });
+
+        let Some(frame) = frame else {
+            synced = false;
+            pos += 1;
+            continue;
+        };
+        synced = true;
+
+        let duration = frame.samples as f64 / frame.sample_rate as f64;
+
+        // Xing/Info frames carry whole-file metadata that would be wrong for a chunk
+        let is_info_frame = units.is_empty() && {
+            let window = &data[pos..pos + frame.len.min(64)];
+            window.windows(4).any(|w| w == b"Xing" || w == b"Info")
+        };
+
+        if !is_info_frame {
+            units.push(Unit {
+                offset: pos,
+                len: frame.len,
+                start_seconds: elapsed,
+                duration_seconds: duration,
+            });
+            elapsed += duration;
+        }
+
+        pos += frame.len;
+    }
+
+    Ok((Vec::new(), units))
+}
+
+// ============= FLAC =============
+
+/// FLAC frame header fields needed to walk the stream
+#[derive(Debug, Clone, Copy)]
+struct FlacFrame {
+    variable_blocksize: bool,
+    /// Frame number (fixed blocksize) or first sample number (variable)
+    number: u64,
+    block_size: u64,
+}
+
+/// Returns `fLaC` + a sanitised STREAMINFO block as header, and one unit per frame
+fn parse_flac(data: &[u8]) -> Result<(Vec<u8>, Vec<Unit>), AppError> {
+    let mut pos = 4;
+    let mut stream_info: Option<&[u8]> = None;
+
+    loop {
+        if pos + 4 > data.len() {
+            return Err(invalid_audio("FLAC metadata is truncated"));
+        }
+        let last = data[pos] & 0x80 != 0;
+        let block_type = data[pos] & 0x7F;
+        let len = u32::from_be_bytes([0, data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        let body_start = pos + 4;
+        if body_start + len > data.len() {
+            return Err(invalid_audio("FLAC metadata is truncated"));
+        }
+        if block_type == 0 && len >= 34 {
+            stream_info = Some(&data[body_start..body_start + 34]);
+        }
+        pos = body_start + len;
+        if last {
+            break;
+        }
+    }
+
+    let stream_info = stream_info.ok_or_else(|| invalid_audio("FLAC STREAMINFO block missing"))?;
+    let sample_rate = ((stream_info[10] as u32) << 12)
+        | ((stream_info[11] as u32) << 4)
+        | ((stream_info[12] as u32) >> 4);
+    if sample_rate == 0 {
+        return Err(invalid_audio("FLAC STREAMINFO has zero sample rate"));
+    }
+
+    // Only STREAMINFO is kept; total samples and MD5 no longer apply to a chunk
+    let mut info = stream_info.to_vec();
+    info[13] &= 0xF0;
+    info[14..34].fill(0);
+    let mut header = b"fLaC".to_vec();
+    header.push(0x80);
+    header.extend_from_slice(&[0, 0, 34]);
+    header.extend_from_slice(&info);
+
+    let mut frames: Vec<(usize, FlacFrame)> = Vec::new();
+    let mut search = pos;
+    while let Some((offset, frame)) = find_flac_frame(data, search, frames.last().map(|(_, f)| f)) {
+        frames.push((offset, frame));
+        search = offset + 2;
+    }
+
+    let mut elapsed = 0.0;
+    let units = frames
+        .iter()
+        .enumerate()
+        .map(|(i, (offset, frame))| {
+            let end = frames.get(i + 1).map(|(next, _)| *next).unwrap_or(data.len());
+            let duration = frame.block_size as f64 / sample_rate as f64;
+            let unit = Unit {
+                offset: *offset,
+                len: end - offset,
+                start_seconds: elapsed,
+                duration_seconds: duration,
+            };
+            elapsed += duration;
+            unit
+        })
+        .collect();
+
+    Ok((header, units))
+}
+
+/// Find the next frame header at or after `from` that continues the stream
+/// after `previous`. Sync codes can occur inside audio data, so candidates
+/// are confirmed with the header CRC-8 and frame/sample numbering.
+fn find_flac_frame(data: &[u8], from: usize, previous: Option<&FlacFrame>) -> Option<(usize, FlacFrame)> {
+    let mut pos = from;
+    while pos + 2 <= data.len() {
+        if data[pos] == 0xFF && data[pos + 1] & 0xFE == 0xF8 {
+            if let Some(frame) = parse_flac_frame_header(&data[pos..]) {
+                let continues = match previous {
+                    None => true,
+                    Some(prev) if prev.variable_blocksize => {
+                        frame.variable_blocksize && frame.number == prev.number + prev.block_size
+                    }
+                    Some(prev) => !frame.variable_blocksize && frame.number == prev.number + 1,
+                };
+                if continues {
+                    return Some((pos, frame));
+                }
+            }
+        }
+        pos += 1;
+    }
+    None
+}
+
+fn parse_flac_frame_header(h: &[u8]) -> Option<FlacFrame> {
+    if h.len() < 6 {
+        return None;
+    }
+
+    let variable_blocksize = h[1] & 0x01 == 1;
+    let block_size_bits = h[2] >> 4;
+    let sample_rate_bits = h[2] & 0x0F;
+    let channel_bits = h[3] >> 4;
+    let sample_size_bits = (h[3] >> 1) & 0x07;
+    if block_size_bits == 0
+        || sample_rate_bits == 0x0F
+        || channel_bits > 10
+        || sample_size_bits == 3
+        || h[3] & 0x01 != 0
+    {
+        return None;
+    }
+
+    // UTF-8 style coded frame or sample number
+    let first = h[4];
+    let extra = match first {
+        0x00..=0x7F => 0,
+        0xC0..=0xDF => 1,
+        0xE0..=0xEF => 2,
+        0xF0..=0xF7 => 3,
+        0xF8..=0xFB => 4,
+        0xFC..=0xFD => 5,
+        0xFE => 6,
+        _ => return None,
+    };
+    let mut pos = 5;
+    let mut number = if extra == 0 {
+        first as u64
+    } else {
+        (first & (0x7F >> (extra + 1))) as u64
+    };
+    for _ in 0..extra {
+        let byte = *h.get(pos)?;
+        if byte & 0xC0 != 0x80 {
+            return None;
+        }
+        number = (number << 6) | (byte & 0x3F) as u64;
+        pos += 1;
+    }
+
+    let block_size = match block_size_bits {
+        1 => 192,
+        2..=5 => 576 << (block_size_bits - 2),
+        6 => {
+            pos += 1;
+            *h.get(pos - 1)? as u64 + 1
+        }
+        7 => {
+            pos += 2;
+            u16::from_be_bytes([*h.get(pos - 2)?, *h.get(pos - 1)?]) as u64 + 1
+        }
+        _ => 256 << (block_size_bits - 8),
+    };
+
+    pos += match sample_rate_bits {
+        12 => 1,
+        13 | 14 => 2,
+        _ => 0,
+    };
+
+    if *h.get(pos)? != crc8(&h[..pos]) {
+        return None;
+    }
+
+    Some(FlacFrame {
+        variable_blocksize,
+        number,
+        block_size,
+    })
+}
+
+/// CRC-8 with polynomial x^8 + x^2 + x + 1, as used by FLAC frame headers
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |mut crc, byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+pub(super) fn invalid_audio(message: &str) -> AppError {
+    AppError::BadRequest(format!("Invalid audio file: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 16-bit mono PCM WAV of the given length
+    fn wav(sample_rate: u32, seconds: u32) -> Vec<u8> {
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        fmt.extend_from_slice(&1u16.to_le_bytes()); // mono
+        fmt.extend_from_slice(&sample_rate.to_le_bytes());
+        fmt.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+        fmt.extend_from_slice(&2u16.to_le_bytes());
+        fmt.extend_from_slice(&16u16.to_le_bytes());
+
+        let data_len = (sample_rate * 2 * seconds) as usize;
+        let mut file = wav_header(&fmt, data_len);
+        file.resize(file.len() + data_len, 0x11);
+        file
+    }
+
+    #[test]
+    fn splits_wav_into_overlapping_chunks() {
+        let file = Bytes::from(wav(16000, 60));
+        // ~20s of audio per chunk
+        let chunks = split_audio(&file, 16000 * 2 * 20 + 44, 2.0).unwrap();
+
+        assert!(chunks.len() >= 3);
+        assert!(chunks.iter().all(|c| c.header.len() + c.body.len() <= 16000 * 2 * 20 + 44));
+        assert_eq!(chunks[0].offset_seconds, 0.0);
+        for pair in chunks.windows(2) {
+            let previous_end = pair[0].offset_seconds + pair[0].duration_seconds;
+            let overlap = previous_end - pair[1].offset_seconds;
+            assert!((overlap - 2.0).abs() < 0.11, "overlap was {}", overlap);
+        }
+        let last = chunks.last().unwrap();
+        assert!((last.offset_seconds + last.duration_seconds - 60.0).abs() < 0.01);
+
+        // Every chunk is a well-formed WAV on its own
+        for chunk in &chunks {
+            let data = chunk.data(&file);
+            assert_eq!(data.len(), chunk.header.len() + chunk.body.len());
+            assert_eq!(SplittableFormat::detect(&data), Some(SplittableFormat::Wav));
+            let data_len = u32::from_le_bytes(data[40..44].try_into().unwrap()) as usize;
+            assert_eq!(data_len, data.len() - 44);
+        }
+    }
+
+    #[test]
+    fn splits_mp3_on_frame_boundaries() {
+        // MPEG-1 Layer III, 128 kbps, 44.1 kHz, no padding: 417 byte frames
+        let mut frame = vec![0xFF, 0xFB, 0x90, 0x00];
+        frame.resize(417, 0x55);
+        let file: Bytes = frame.iter().copied().cycle().take(417 * 1000).collect();
+
+        let chunks = split_audio(&file, 417 * 300, 1.0).unwrap();
+
+        assert!(chunks.len() >= 4);
+        for chunk in &chunks {
+            let data = chunk.data(&file);
+            assert_eq!(data.len() % 417, 0);
+            assert_eq!(&data[0..2], &[0xFF, 0xFB]);
+        }
+        let frame_seconds = 1152.0 / 44100.0;
+        let total: f32 = chunks.last().map(|c| c.offset_seconds + c.duration_seconds).unwrap();
+        assert!((total - 1000.0 * frame_seconds).abs() < 0.01);
+    }
+
+    #[test]
+    fn rejects_unsplittable_formats() {
+        let result = split_audio(b"OggS\0\0\0\0\0\0\0\0", 1024, 1.0);
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+}
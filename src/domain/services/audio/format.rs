@@ -0,0 +1,536 @@
+//! Container sniffing and duration probing for uploaded audio. Files are
+//! identified by their magic bytes rather than the client-supplied name, and
+//! durations are read from container headers without decoding any audio.
+
+use crate::shared::error::AppError;
+
+use super::chunking::{id3v2_len, invalid_audio, mp3_frame_header};
+
+/// Audio containers accepted for transcription
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Mp3,
+    Mp4,
+    M4a,
+    Wav,
+    Webm,
+    Ogg,
+    Flac,
+}
+
+impl AudioFormat {
+    /// Detect the container from its leading bytes
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WAVE" {
+            Some(AudioFormat::Wav)
+        } else if data.starts_with(b"fLaC") {
+            Some(AudioFormat::Flac)
+        } else if data.starts_with(b"OggS") {
+            Some(AudioFormat::Ogg)
+        } else if data.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+            Some(AudioFormat::Webm)
+        } else if data.len() >= 12 && &data[4..8] == b"ftyp" {
+            // Audio-only brands are reported as m4a
+            match &data[8..12] {
+                b"M4A " | b"M4B " | b"M4P " => Some(AudioFormat::M4a),
+                _ => Some(AudioFormat::Mp4),
+            }
+        } else if data.starts_with(b"ID3")
+            || (data.len() >= 4 && mp3_frame_header(&data[0..4]).is_some())
+        {
+            Some(AudioFormat::Mp3)
+        } else {
+            None
+        }
+    }
+
+    /// Canonical file extension
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Mp4 => "mp4",
+            AudioFormat::M4a => "m4a",
+            AudioFormat::Wav => "wav",
+            AudioFormat::Webm => "webm",
+            AudioFormat::Ogg => "ogg",
+            AudioFormat::Flac => "flac",
+        }
+    }
+
+    /// Whether `extension` is a common name for this container
+    fn accepts_extension(&self, extension: &str) -> bool {
+        match self {
+            AudioFormat::Mp3 => matches!(extension, "mp3" | "mpga" | "mpeg"),
+            AudioFormat::Mp4 | AudioFormat::M4a => matches!(extension, "mp4" | "m4a"),
+            AudioFormat::Wav => extension == "wav",
+            AudioFormat::Webm => extension == "webm",
+            AudioFormat::Ogg => matches!(extension, "ogg" | "oga" | "opus"),
+            AudioFormat::Flac => extension == "flac",
+        }
+    }
+
+    /// File name whose extension matches the detected container, since
+    /// providers pick a decoder from it
+    pub fn file_name(&self, file_name: &str) -> String {
+        let (stem, extension) = match file_name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => (stem, extension.to_lowercase()),
+            _ => (file_name, String::new()),
+        };
+
+        if self.accepts_extension(&extension) {
+            file_name.to_string()
+        } else {
+            let stem = if stem.is_empty() { "audio" } else { stem };
+            format!("{}.{}", stem, self.extension())
+        }
+    }
+}
+
+/// What could be learned about an upload from its headers
+#[derive(Debug, Clone, Copy)]
+pub struct AudioInfo {
+    pub format: AudioFormat,
+    /// `None` when the container does not record its length up front
+    pub duration_seconds: Option<f32>,
+}
+
+/// Identify the container and read its duration, rejecting unknown formats
+/// with 415 and truncated or corrupt headers with 400
+pub fn probe_audio(data: &[u8]) -> Result<AudioInfo, AppError> {
+    let format = AudioFormat::detect(data).ok_or_else(|| {
+        AppError::UnsupportedMediaType(
+            "Unrecognised audio format; expected mp3, mp4, m4a, wav, webm, ogg or flac".to_string(),
+        )
+    })?;
+
+    let duration = match format {
+        AudioFormat::Wav => wav_duration(data)?,
+        AudioFormat::Flac => flac_duration(data)?,
+        AudioFormat::Mp3 => mp3_duration(data)?,
+        AudioFormat::Mp4 | AudioFormat::M4a => mp4_duration(data)?,
+        AudioFormat::Ogg => ogg_duration(data)?,
+        AudioFormat::Webm => webm_duration(data)?,
+    };
+
+    Ok(AudioInfo {
+        format,
+        duration_seconds: duration.map(|d| d as f32),
+    })
+}
+
+fn u16_le(b: &[u8]) -> u16 {
+    u16::from_le_bytes([b[0], b[1]])
+}
+
+fn u32_le(b: &[u8]) -> u32 {
+    u32::from_le_bytes([b[0], b[1], b[2], b[3]])
+}
+
+fn u32_be(b: &[u8]) -> u32 {
+    u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+}
+
+fn u64_be(b: &[u8]) -> u64 {
+    u64::from_be_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]])
+}
+
+// ============= WAV =============
+
+fn wav_duration(data: &[u8]) -> Result<Option<f64>, AppError> {
+    let mut byte_rate = None;
+    let mut pos = 12;
+
+    while pos + 8 <= data.len() {
+        let id = &data[pos..pos + 4];
+        let size = u32_le(&data[pos + 4..pos + 8]) as usize;
+        let body = pos + 8;
+
+        if id == b"fmt " {
+            if body + 16 > data.len() {
+                return Err(invalid_audio("truncated WAV fmt chunk"));
+            }
+            byte_rate = Some(u32_le(&data[body + 8..body + 12]));
+        } else if id == b"data" {
+            let byte_rate = byte_rate
+                .filter(|r| *r > 0)
+                .ok_or_else(|| invalid_audio("WAV data chunk before a valid fmt chunk"))?;
+            // Streaming writers leave the size at 0 or u32::MAX
+            let available = data.len() - body;
+            let size = if size == 0 || size == u32::MAX as usize {
+                available
+            } else if size > available {
+                return Err(invalid_audio("WAV file is truncated"));
+            } else {
+                size
+            };
+            return Ok(Some(size as f64 / byte_rate as f64));
+        }
+
+        // Chunks are word aligned
+        pos = body + size + (size & 1);
+    }
+
+    Err(invalid_audio("WAV file has no data chunk"))
+}
+
+// ============= FLAC =============
+
+fn flac_duration(data: &[u8]) -> Result<Option<f64>, AppError> {
+    // "fLaC", a 4-byte block header, then the 34-byte STREAMINFO block
+    if data.len() < 42 || data[4] & 0x7F != 0 {
+        return Err(invalid_audio("FLAC file has no STREAMINFO block"));
+    }
+    let info = &data[8..42];
+    let sample_rate = (info[10] as u32) << 12 | (info[11] as u32) << 4 | (info[12] as u32) >> 4;
+    let total_samples = ((info[13] & 0x0F) as u64) << 32 | u32_be(&info[14..18]) as u64;
+
+    if sample_rate == 0 {
+        return Err(invalid_audio("FLAC STREAMINFO has no sample rate"));
+    }
+    // A zero sample count means the encoder did not know the length
+    Ok((total_samples > 0).then(|| total_samples as f64 / sample_rate as f64))
+}
+
+// ============= MP3 =============
+
+fn mp3_duration(data: &[u8]) -> Result<Option<f64>, AppError> {
+    let start = id3v2_len(data);
+    if start >= data.len() {
+        return Err(invalid_audio("MP3 file has no audio frames"));
+    }
+
+    // Some encoders pad the tag; resync on the first two consecutive frames
+    let search_end = data.len().min(start + 64 * 1024);
+    let (pos, frame) = (start..search_end)
+        .find_map(|pos| {
+            let frame = mp3_frame_header(data.get(pos..pos + 4)?)?;
+            let next = pos + frame.len;
+            let follows = next == data.len()
+                || data.get(next..next + 4).and_then(mp3_frame_header).is_some();
+            follows.then_some((pos, frame))
+        })
+        .ok_or_else(|| invalid_audio("MP3 file has no valid audio frames"))?;
+
+    let frame_duration = frame.samples as f64 / frame.sample_rate as f64;
+
+    // VBR files carry a frame count in a Xing/Info or VBRI header
+    let header = &data[pos..data.len().min(pos + frame.len)];
+    if let Some(frames) = vbr_frame_count(header).filter(|f| *f > 0) {
+        return Ok(Some(frames as f64 * frame_duration));
+    }
+
+    // Otherwise assume a constant bitrate
+    let frames = (data.len() - pos) as f64 / frame.len as f64;
+    Ok(Some(frames * frame_duration))
+}
+
+/// Frame count from the Xing/Info or VBRI header in the first frame
+fn vbr_frame_count(frame: &[u8]) -> Option<u32> {
+    if let Some(at) = frame.windows(4).position(|w| w == b"Xing" || w == b"Info") {
+        let flags = u32_be(frame.get(at + 4..at + 8)?);
+        return if flags & 0x01 != 0 {
+            frame.get(at + 8..at + 12).map(u32_be)
+        } else {
+            None
+        };
+    }
+    if frame.get(36..40)? == b"VBRI" {
+        return frame.get(50..54).map(u32_be);
+    }
+    None
+}
+
+// ============= MP4 / M4A =============
+
+fn mp4_duration(data: &[u8]) -> Result<Option<f64>, AppError> {
+    let mut moov = None;
+    let mut pos = 0;
+
+    while pos + 8 <= data.len() {
+        let (header, size) = box_size(data, pos)?;
+        if pos + size > data.len() {
+            return Err(invalid_audio("MP4 file is truncated"));
+        }
+        if &data[pos + 4..pos + 8] == b"moov" {
+            moov = Some(&data[pos + header..pos + size]);
+        }
+        pos += size;
+    }
+
+    let moov = moov.ok_or_else(|| invalid_audio("MP4 file has no moov box"))?;
+
+    let mut pos = 0;
+    while pos + 8 <= moov.len() {
+        let (header, size) = box_size(moov, pos)?;
+        if pos + size > moov.len() {
+            break;
+        }
+        if &moov[pos + 4..pos + 8] == b"mvhd" {
+            return mvhd_duration(&moov[pos + header..pos + size]).map(Some);
+        }
+        pos += size;
+    }
+
+    Err(invalid_audio("MP4 file has no movie header"))
+}
+
+/// Header and total length of the box at `pos`
+fn box_size(data: &[u8], pos: usize) -> Result<(usize, usize), AppError> {
+    let size = u32_be(&data[pos..pos + 4]) as usize;
+    let (header, size) = match size {
+        // Extends to the end of the file
+        0 => (8, data.len() - pos),
+        1 => {
+            let large = data
+                .get(pos + 8..pos + 16)
+                .map(u64_be)
+                .ok_or_else(|| invalid_audio("MP4 file is truncated"))?;
+            (16, large as usize)
+        }
+        size => (8, size),
+    };
+    if size < header {
+        return Err(invalid_audio("MP4 box has an invalid size"));
+    }
+    Ok((header, size))
+}
+
+fn mvhd_duration(mvhd: &[u8]) -> Result<f64, AppError> {
+    let (timescale, duration) = match mvhd.first() {
+        Some(0) if mvhd.len() >= 20 => (u32_be(&mvhd[12..16]), u32_be(&mvhd[16..20]) as u64),
+        Some(1) if mvhd.len() >= 32 => (u32_be(&mvhd[20..24]), u64_be(&mvhd[24..32])),
+        _ => return Err(invalid_audio("MP4 movie header is malformed")),
+    };
+    if timescale == 0 {
+        return Err(invalid_audio("MP4 movie header has no timescale"));
+    }
+    Ok(duration as f64 / timescale as f64)
+}
+
+// ============= OGG =============
+
+fn ogg_duration(data: &[u8]) -> Result<Option<f64>, AppError> {
+    let first = ogg_page(data, 0)?;
+    let payload = &data[first.body..first.end];
+
+    // The first packet identifies the codec
+    let (sample_rate, pre_skip) = if payload.starts_with(b"OpusHead") && payload.len() >= 12 {
+        (48000, u16_le(&payload[10..12]) as u64)
+    } else if payload.starts_with(b"\x01vorbis") && payload.len() >= 16 {
+        (u32_le(&payload[12..16]), 0)
+    } else {
+        return Err(AppError::UnsupportedMediaType(
+            "Ogg stream is neither Vorbis nor Opus".to_string(),
+        ));
+    };
+
+    // The granule position of the last page is the total sample count
+    let last = data
+        .windows(4)
+        .rposition(|w| w == b"OggS")
+        .unwrap_or_default();
+    let page = ogg_page(data, last)?;
+    if page.granule < 0 || sample_rate == 0 {
+        return Ok(None);
+    }
+
+    let samples = (page.granule as u64).saturating_sub(pre_skip);
+    Ok(Some(samples as f64 / sample_rate as f64))
+}
+
+struct OggPage {
+    granule: i64,
+    body: usize,
+    end: usize,
+}
+
+fn ogg_page(data: &[u8], pos: usize) -> Result<OggPage, AppError> {
+    let header = data
+        .get(pos..pos + 27)
+        .ok_or_else(|| invalid_audio("Ogg file is truncated"))?;
+    let segments = header[26] as usize;
+    let table = data
+        .get(pos + 27..pos + 27 + segments)
+        .ok_or_else(|| invalid_audio("Ogg file is truncated"))?;
+    let body = pos + 27 + segments;
+    let end = body + table.iter().map(|s| *s as usize).sum::<usize>();
+    if end > data.len() {
+        return Err(invalid_audio("Ogg file is truncated"));
+    }
+
+    Ok(OggPage {
+        granule: i64::from_le_bytes(header[6..14].try_into().unwrap_or_default()),
+        body,
+        end,
+    })
+}
+
+// ============= WebM =============
+
+const EBML_SEGMENT: u32 = 0x1853_8067;
+const EBML_INFO: u32 = 0x1549_A966;
+const EBML_CLUSTER: u32 = 0x1F43_B675;
+const EBML_TIMECODE_SCALE: u32 = 0x2A_D7B1;
+const EBML_DURATION: u32 = 0x4489;
+
+fn webm_duration(data: &[u8]) -> Result<Option<f64>, AppError> {
+    // Skip the EBML header element
+    let (_, header_len, size) = ebml_element(data, 0)?;
+    let mut pos = header_len + size.unwrap_or_default() as usize;
+
+    let (id, header_len, _) = ebml_element(data, pos)?;
+    if id != EBML_SEGMENT {
+        return Err(invalid_audio("WebM file has no segment"));
+    }
+    pos += header_len;
+
+    // Info normally precedes the first cluster
+    while pos < data.len() {
+        let (id, header_len, size) = ebml_element(data, pos)?;
+        let body = pos + header_len;
+        let Some(size) = size.map(|s| s as usize) else {
+            return Ok(None);
+        };
+        if id == EBML_CLUSTER {
+            return Ok(None);
+        }
+        if id == EBML_INFO {
+            let info = data
+                .get(body..body + size)
+                .ok_or_else(|| invalid_audio("WebM file is truncated"))?;
+            return Ok(webm_info_duration(info));
+        }
+        pos = body + size;
+    }
+
+    Ok(None)
+}
+
+fn webm_info_duration(info: &[u8]) -> Option<f64> {
+    let mut timecode_scale = 1_000_000u64;
+    let mut duration = None;
+    let mut pos = 0;
+
+    while pos < info.len() {
+        let (id, header_len, size) = ebml_element(info, pos).ok()?;
+        let body = pos + header_len;
+        let value = info.get(body..body + size? as usize)?;
+        match id {
+            EBML_TIMECODE_SCALE => {
+                timecode_scale = value.iter().fold(0u64, |acc, b| acc << 8 | *b as u64);
+            }
+            EBML_DURATION => {
+                duration = match value.len() {
+                    4 => Some(f32::from_be_bytes(value.try_into().ok()?) as f64),
+                    8 => Some(f64::from_be_bytes(value.try_into().ok()?)),
+                    _ => None,
+                };
+            }
+            _ => {}
+        }
+        pos = body + value.len();
+    }
+
+    duration.map(|d| d * timecode_scale as f64 / 1e9)
+}
+
+/// ID, header length and data size (`None` if unknown) of the element at `pos`
+fn ebml_element(data: &[u8], pos: usize) -> Result<(u32, usize, Option<u64>), AppError> {
+    let truncated = || invalid_audio("WebM file is truncated");
+
+    let first = *data.get(pos).ok_or_else(truncated)?;
+    let id_len = first.leading_zeros() as usize + 1;
+    if id_len > 4 {
+        return Err(invalid_audio("WebM element has an invalid ID"));
+    }
+    let id = data
+        .get(pos..pos + id_len)
+        .ok_or_else(truncated)?
+        .iter()
+        .fold(0u32, |acc, b| acc << 8 | *b as u32);
+
+    let first = *data.get(pos + id_len).ok_or_else(truncated)?;
+    let size_len = first.leading_zeros() as usize + 1;
+    if size_len > 8 {
+        return Err(invalid_audio("WebM element has an invalid size"));
+    }
+    let bytes = data
+        .get(pos + id_len..pos + id_len + size_len)
+        .ok_or_else(truncated)?;
+    let mask = if size_len == 8 { 0 } else { 0xFFu8 >> size_len };
+    let size = bytes[1..]
+        .iter()
+        .fold((bytes[0] & mask) as u64, |acc, b| acc << 8 | *b as u64);
+    // All value bits set means the size is unknown (live streams)
+    let unknown = size == (1u64 << (7 * size_len)) - 1;
+
+    Ok((id, id_len + size_len, (!unknown).then_some(size)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(sample_rate: u32, seconds: u32) -> Vec<u8> {
+        let data_len = sample_rate * 2 * seconds;
+        let mut file = Vec::new();
+        file.extend_from_slice(b"RIFF");
+        file.extend_from_slice(&(36 + data_len).to_le_bytes());
+        file.extend_from_slice(b"WAVEfmt ");
+        file.extend_from_slice(&16u32.to_le_bytes());
+        file.extend_from_slice(&1u16.to_le_bytes());
+        file.extend_from_slice(&1u16.to_le_bytes());
+        file.extend_from_slice(&sample_rate.to_le_bytes());
+        file.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+        file.extend_from_slice(&2u16.to_le_bytes());
+        file.extend_from_slice(&16u16.to_le_bytes());
+        file.extend_from_slice(b"data");
+        file.extend_from_slice(&data_len.to_le_bytes());
+        file.resize(file.len() + data_len as usize, 0);
+        file
+    }
+
+    #[test]
+    fn probes_wav_duration_and_rejects_truncation() {
+        let file = wav(16000, 3);
+        let info = probe_audio(&file).unwrap();
+        assert_eq!(info.format, AudioFormat::Wav);
+        assert_eq!(info.duration_seconds, Some(3.0));
+
+        let truncated = &file[..file.len() / 2];
+        assert!(matches!(probe_audio(truncated), Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn reads_mp4_movie_header() {
+        let mut mvhd = vec![0u8; 4 + 8];
+        mvhd.extend_from_slice(&1000u32.to_be_bytes());
+        mvhd.extend_from_slice(&90_500u32.to_be_bytes());
+        let mut moov = ((8 + 8 + mvhd.len()) as u32).to_be_bytes().to_vec();
+        moov.extend_from_slice(b"moov");
+        moov.extend_from_slice(&((8 + mvhd.len()) as u32).to_be_bytes());
+        moov.extend_from_slice(b"mvhd");
+        moov.extend_from_slice(&mvhd);
+
+        let mut file = 16u32.to_be_bytes().to_vec();
+        file.extend_from_slice(b"ftypM4A ");
+        file.extend_from_slice(&[0; 4]);
+        file.extend_from_slice(&moov);
+
+        let info = probe_audio(&file).unwrap();
+        assert_eq!(info.format, AudioFormat::M4a);
+        assert_eq!(info.duration_seconds, Some(90.5));
+    }
+
+    #[test]
+    fn rejects_unknown_formats_and_fixes_extensions() {
+        assert!(matches!(
+            probe_audio(b"%PDF-1.7 not audio at all"),
+            Err(AppError::UnsupportedMediaType(_))
+        ));
+
+        assert_eq!(AudioFormat::Flac.file_name("audio.wav"), "audio.flac");
+        assert_eq!(AudioFormat::Mp3.file_name("Interview.MPEG"), "Interview.MPEG");
+        assert_eq!(AudioFormat::Ogg.file_name("voice"), "voice.ogg");
+    }
+}
@@ -0,0 +1,5 @@
+pub mod chunking;
+pub mod format;
+
+pub use chunking::{split_audio, SplittableFormat};
+pub use format::{probe_audio, AudioInfo};
@@ -0,0 +1,703 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::api::dto::ChatCompletionRequest;
+use crate::domain::entities::batch::{BatchError, BatchJob, BatchRequestCounts, BatchStatus};
+use crate::domain::entities::file::StoredFile;
+use crate::domain::entities::usage::{
+    ApiEndpoint, CostData, RequestMetadata, ResponseMetadata, UsageLog,
+};
+use crate::domain::entities::{LlmProvider, Project, RateLimits};
+use crate::domain::repositories::{
+    BatchRepository, FileRepository, ProjectRepository, UsageRepository,
+};
+use crate::domain::services::chat_guardrails::ChatGuardrails;
+use crate::domain::services::moderation;
+use crate::domain::services::providers::openai::calculate_embedding_cost;
+use crate::domain::services::providers::OpenAIProvider;
+use crate::domain::services::structured_output;
+use crate::domain::services::token_rate_limiter::TokenRateLimiter;
+use crate::shared::config::{BatchConfig, ChatConfig};
+use crate::shared::error::AppError;
+
+/// Attempts before a batch interrupted by worker crashes is failed
+const MAX_BATCH_ATTEMPTS: u32 = 3;
+
+/// Progress is saved, and cancellation and expiry checked, after this many requests
+const PROGRESS_INTERVAL: u32 = 50;
+
+/// Endpoints a batch may target
+pub const BATCH_ENDPOINTS: [&str; 2] = ["/v1/chat/completions", "/v1/embeddings"];
+
+/// Purposes clients may upload files with; `batch_output` is reserved for
+/// files the gateway writes
+pub const FILE_PURPOSES: [&str; 6] = ["batch", "user_data", "assistants", "vision", "fine-tune", "evals"];
+
+/// One line of a batch input file
+#[derive(Debug, Deserialize)]
+pub struct BatchRequestLine {
+    pub custom_id: String,
+    pub method: String,
+    pub url: String,
+    pub body: serde_json::Value,
+}
+
+/// Parse a batch input file, checking every line targets a batch endpoint
+/// (`endpoint` when given) and custom IDs are unique
+pub fn parse_input(
+    data: &[u8],
+    endpoint: Option<&str>,
+    max_requests: usize,
+) -> Result<Vec<BatchRequestLine>, AppError> {
+    let text = std::str::from_utf8(data)
+        .map_err(|_| AppError::BadRequest("Batch input must be UTF-8 JSONL".to_string()))?;
+
+    let mut lines = Vec::new();
+    let mut custom_ids = HashSet::new();
+    for (number, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let number = number + 1;
+        let line: BatchRequestLine = serde_json::from_str(line)
+            .map_err(|e| AppError::BadRequest(format!("Line {}: invalid request: {}", number, e)))?;
+
+        if line.method != "POST" {
+            return Err(AppError::BadRequest(format!("Line {}: method must be POST", number)));
+        }
+        match endpoint {
+            Some(endpoint) if line.url != endpoint => {
+                return Err(AppError::BadRequest(format!(
+                    "Line {}: url {} does not match the batch endpoint {}",
+                    number, line.url, endpoint
+                )))
+            }
+            None if !BATCH_ENDPOINTS.contains(&line.url.as_str()) => {
+                return Err(AppError::BadRequest(format!(
+                    "Line {}: unsupported url {}",
+                    number, line.url
+                )))
+            }
+            _ => {}
+        }
+        if !custom_ids.insert(line.custom_id.clone()) {
+            return Err(AppError::BadRequest(format!(
+                "Line {}: duplicate custom_id {}",
+                number, line.custom_id
+            )));
+        }
+
+        lines.push(line);
+        if lines.len() > max_requests {
+            return Err(AppError::BadRequest(format!(
+                "Batch input exceeds {} requests",
+                max_requests
+            )));
+        }
+    }
+
+    if lines.is_empty() {
+        return Err(AppError::BadRequest("Batch input contains no requests".to_string()));
+    }
+    Ok(lines)
+}
+
+/// HTTP status reported for a failed batch request
+fn error_status(e: &AppError) -> u16 {
+    match e {
+        AppError::BadRequest(_)
+        | AppError::ValidationError(_)
+        | AppError::ContentPolicyViolation(_)
+        | AppError::ContextLengthExceeded(_) => 400,
+        AppError::RateLimitError(_) => 429,
+        AppError::StructuredOutputError(_) => 422,
+        // "OpenAI API error (429 Too Many Requests): ..."
+        AppError::ExternalApiError(msg) => msg
+            .split_once('(')
+            .and_then(|(_, rest)| rest.get(..3))
+            .and_then(|code| code.parse().ok())
+            .unwrap_or(502),
+        _ => 500,
+    }
+}
+
+/// Result of one executed request
+struct Executed {
+    body: serde_json::Value,
+    cost_usd: f64,
+}
+
+/// Rate-limit pacing shared by every running batch of a project, so that
+/// concurrent batches split the project's limits rather than each using them
+struct ProjectBudget {
+    /// `requests_per_minute` and `max_concurrent_requests` it was built from
+    limits: (u32, u32),
+    permits: Option<Semaphore>,
+    spacing: Duration,
+    next_start: Mutex<tokio::time::Instant>,
+}
+
+impl ProjectBudget {
+    fn new(rate_limits: &RateLimits) -> Self {
+        let permits = match rate_limits.max_concurrent_requests {
+            0 => None,
+            limit => Some(Semaphore::new(limit as usize)),
+        };
+        let spacing = match rate_limits.requests_per_minute {
+            0 => Duration::ZERO,
+            rpm => Duration::from_secs(60) / rpm,
+        };
+        Self {
+            limits: (rate_limits.requests_per_minute, rate_limits.max_concurrent_requests),
+            permits,
+            spacing,
+            next_start: Mutex::new(tokio::time::Instant::now()),
+        }
+    }
+
+    /// Wait for a concurrency slot and the project's next start slot; the
+    /// permit is held until the request finishes
+    async fn acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let permit = match &self.permits {
+            Some(permits) => Some(permits.acquire().await.expect("budget semaphore is never closed")),
+            None => None,
+        };
+        let start = {
+            let mut next_start = self.next_start.lock().unwrap_or_else(|e| e.into_inner());
+            let start = (*next_start).max(tokio::time::Instant::now());
+            *next_start = start + self.spacing;
+            start
+        };
+        tokio::time::sleep_until(start).await;
+        permit
+    }
+}
+
+/// Service for `/v1/files` and `/v1/batches`. Batches are executed by the
+/// gateway on a background worker pool, paced by the project's rate limits,
+/// and chat lines pass the same guardrails as interactive requests.
+pub struct BatchService {
+    files: Arc<dyn FileRepository>,
+    batches: Arc<dyn BatchRepository>,
+    projects: Arc<dyn ProjectRepository>,
+    usage_repository: Arc<dyn UsageRepository>,
+    guardrails: Arc<ChatGuardrails>,
+    token_rate_limiter: Arc<TokenRateLimiter>,
+    provider: OpenAIProvider,
+    config: BatchConfig,
+    chat_config: ChatConfig,
+    budgets: Mutex<HashMap<String, Arc<ProjectBudget>>>,
+}
+
+impl BatchService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        files: Arc<dyn FileRepository>,
+        batches: Arc<dyn BatchRepository>,
+        projects: Arc<dyn ProjectRepository>,
+        usage_repository: Arc<dyn UsageRepository>,
+        guardrails: Arc<ChatGuardrails>,
+        token_rate_limiter: Arc<TokenRateLimiter>,
+        config: BatchConfig,
+        chat_config: ChatConfig,
+    ) -> Self {
+        Self {
+            files,
+            batches,
+            projects,
+            usage_repository,
+            guardrails,
+            token_rate_limiter,
+            provider: OpenAIProvider::new(),
+            config,
+            chat_config,
+            budgets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Store an uploaded file; batch input is validated up front
+    pub async fn upload_file(
+        &self,
+        project_id: String,
+        filename: String,
+        purpose: String,
+        data: &[u8],
+    ) -> Result<StoredFile, AppError> {
+        if !FILE_PURPOSES.contains(&purpose.as_str()) {
+            return Err(AppError::BadRequest(format!("Unsupported file purpose: {}", purpose)));
+        }
+        if purpose == "batch" {
+            parse_input(data, None, self.config.max_requests)?;
+        }
+
+        let file = StoredFile::new(project_id, filename, purpose, data.len());
+        let file = self.files.create(file, data).await?;
+        tracing::info!("Stored file {} ({} bytes)", file.file_id, file.bytes);
+        Ok(file)
+    }
+
+    pub async fn get_file(&self, project_id: &str, file_id: &str) -> Result<StoredFile, AppError> {
+        self.files.find_by_id(project_id, file_id).await
+    }
+
+    pub async fn list_files(
+        &self,
+        project_id: &str,
+        purpose: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<StoredFile>, AppError> {
+        self.files.list(project_id, purpose, limit).await
+    }
+
+    pub async fn file_content(&self, project_id: &str, file_id: &str) -> Result<(StoredFile, Vec<u8>), AppError> {
+        let file = self.files.find_by_id(project_id, file_id).await?;
+        let content = self.files.load_content(&file).await?;
+        Ok((file, content))
+    }
+
+    pub async fn delete_file(&self, project_id: &str, file_id: &str) -> Result<(), AppError> {
+        self.files.delete(project_id, file_id).await
+    }
+
+    /// Queue a batch over an uploaded `batch` file
+    pub async fn create_batch(
+        &self,
+        project_id: String,
+        input_file_id: String,
+        endpoint: String,
+        completion_window: &str,
+        metadata: HashMap<String, String>,
+    ) -> Result<BatchJob, AppError> {
+        if !BATCH_ENDPOINTS.contains(&endpoint.as_str()) {
+            return Err(AppError::BadRequest(format!("Unsupported batch endpoint: {}", endpoint)));
+        }
+        if completion_window != "24h" {
+            return Err(AppError::BadRequest("completion_window must be 24h".to_string()));
+        }
+
+        let (file, content) = self.file_content(&project_id, &input_file_id).await?;
+        if file.purpose != "batch" {
+            return Err(AppError::BadRequest(format!(
+                "File {} was not uploaded with purpose batch",
+                input_file_id
+            )));
+        }
+        let lines = parse_input(&content, Some(&endpoint), self.config.max_requests)?;
+
+        let batch = BatchJob::new(project_id, endpoint, input_file_id, lines.len() as u32, metadata);
+        self.batches.create(&batch).await?;
+        tracing::info!("Queued batch {} ({} requests)", batch.batch_id, lines.len());
+        Ok(batch)
+    }
+
+    pub async fn get_batch(&self, project_id: &str, batch_id: &str) -> Result<BatchJob, AppError> {
+        self.batches.find_by_id(project_id, batch_id).await
+    }
+
+    /// List batches newest first, continuing after the batch `after`.
+    /// Returns the page and whether more batches follow.
+    pub async fn list_batches(
+        &self,
+        project_id: &str,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<(Vec<BatchJob>, bool), AppError> {
+        let before = match after {
+            Some(after) => Some(self.batches.find_by_id(project_id, after).await?.created_at),
+            None => None,
+        };
+
+        let mut batches = self.batches.list(project_id, before, limit + 1).await?;
+        let has_more = batches.len() as i64 > limit;
+        batches.truncate(limit as usize);
+        Ok((batches, has_more))
+    }
+
+    pub async fn cancel_batch(&self, project_id: &str, batch_id: &str) -> Result<BatchJob, AppError> {
+        self.batches.cancel(project_id, batch_id).await
+    }
+
+    /// Recover batches interrupted by a previous shutdown and start the worker pool
+    pub fn start_workers(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let stale_after = chrono::Duration::seconds(service.config.stale_after_seconds as i64);
+            match service
+                .batches
+                .requeue_stale(Utc::now() - stale_after, MAX_BATCH_ATTEMPTS)
+                .await
+            {
+                Ok(0) => {}
+                Ok(count) => tracing::warn!("Requeued {} interrupted batches", count),
+                Err(e) => tracing::error!("Failed to requeue interrupted batches: {}", e),
+            }
+
+            for worker in 0..service.config.workers {
+                tokio::spawn(service.clone().run_worker(worker));
+            }
+        });
+    }
+
+    async fn run_worker(self: Arc<Self>, worker: usize) {
+        let poll_interval = Duration::from_millis(self.config.poll_interval_ms);
+        tracing::debug!("Batch worker {} started", worker);
+
+        loop {
+            match self.batches.claim_next().await {
+                Ok(Some(batch)) => self.process(batch).await,
+                Ok(None) => tokio::time::sleep(poll_interval).await,
+                Err(e) => {
+                    tracing::error!("Worker {} failed to claim batch: {}", worker, e);
+                    tokio::time::sleep(poll_interval).await;
+                }
+            }
+        }
+    }
+
+    /// Run a claimed batch and store its output and error files
+    async fn process(&self, mut batch: BatchJob) {
+        tracing::info!("Processing batch {} (attempt {})", batch.batch_id, batch.attempts);
+
+        let outcome = self.run(&mut batch).await;
+        let now = Utc::now();
+        match outcome {
+            Ok(status) => {
+                batch.status = status;
+                match status {
+                    BatchStatus::Cancelled => batch.cancelled_at = Some(now),
+                    BatchStatus::Expired => batch.expired_at = Some(now),
+                    _ => batch.completed_at = Some(now),
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Batch {} failed: {}", batch.batch_id, e);
+                batch.status = BatchStatus::Failed;
+                batch.failed_at = Some(now);
+                batch.errors.push(BatchError {
+                    code: "batch_failed".to_string(),
+                    message: e.to_string(),
+                    line: None,
+                });
+            }
+        }
+        batch.updated_at = now;
+
+        if let Err(e) = self.batches.save(&batch).await {
+            tracing::error!("Failed to record result of batch {}: {}", batch.batch_id, e);
+        }
+    }
+
+    /// Execute every request, returning the final status
+    async fn run(&self, batch: &mut BatchJob) -> Result<BatchStatus, AppError> {
+        let api_key = std::env::var("OPENAI_API_KEY")
+            .map_err(|_| AppError::ConfigError("OpenAI API key not configured".to_string()))?;
+        let project = self.projects.find_by_id(&batch.project_id).await?;
+        let rate_limits = project.rate_limits.clone().unwrap_or_default();
+
+        let input = self.files.find_by_id(&batch.project_id, &batch.input_file_id).await?;
+        let content = self.files.load_content(&input).await?;
+        let lines = parse_input(&content, Some(&batch.endpoint), self.config.max_requests)?;
+
+        // Pace request starts by the project's rate limits, across all its batches
+        let budget = self.budget(&batch.project_id, &rate_limits);
+
+        let endpoint = batch.endpoint.clone();
+        let batch_ref: &BatchJob = batch;
+        let mut results = futures::stream::iter(lines)
+            .map(|line| {
+                let api_key = api_key.as_str();
+                let endpoint = endpoint.as_str();
+                let project = &project;
+                let budget = &budget;
+                async move {
+                    let _permit = budget.acquire().await;
+                    let outcome = self.execute(batch_ref, project, api_key, endpoint, line.body).await;
+                    (line.custom_id, outcome)
+                }
+            })
+            .buffer_unordered(self.config.max_concurrency.max(1));
+
+        let mut counts = BatchRequestCounts {
+            total: batch_ref.request_counts.total,
+            ..Default::default()
+        };
+        let mut cost_usd = 0.0;
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+        let mut stopped = None;
+        while let Some((custom_id, outcome)) = results.next().await {
+            let request_id = format!("batch_req_{}", uuid::Uuid::new_v4().simple());
+            match outcome {
+                Ok(executed) => {
+                    counts.completed += 1;
+                    cost_usd += executed.cost_usd;
+                    output.push(json!({
+                        "id": request_id,
+                        "custom_id": custom_id,
+                        "response": { "status_code": 200, "request_id": request_id, "body": executed.body },
+                        "error": null,
+                    }));
+                }
+                Err(e) => {
+                    counts.failed += 1;
+                    let status_code = error_status(&e);
+                    let error_type = if status_code < 500 { "invalid_request_error" } else { "api_error" };
+                    errors.push(json!({
+                        "id": request_id,
+                        "custom_id": custom_id,
+                        "response": {
+                            "status_code": status_code,
+                            "request_id": request_id,
+                            "body": { "error": { "message": e.to_string(), "type": error_type } },
+                        },
+                        "error": null,
+                    }));
+                }
+            }
+
+            if (counts.completed + counts.failed).is_multiple_of(PROGRESS_INTERVAL) {
+                if let Err(e) = self.batches.update_progress(&batch_ref.batch_id, &counts, cost_usd).await {
+                    tracing::warn!("Failed to record progress of batch {}: {}", batch_ref.batch_id, e);
+                }
+                if matches!(self.batches.status(&batch_ref.batch_id).await, Ok(BatchStatus::Cancelling)) {
+                    stopped = Some(BatchStatus::Cancelled);
+                    break;
+                }
+                if Utc::now() > batch_ref.expires_at {
+                    stopped = Some(BatchStatus::Expired);
+                    break;
+                }
+            }
+        }
+        // Dropping the stream abandons requests still in flight
+        drop(results);
+        self.release_budget(&batch_ref.project_id, budget);
+
+        // A cancellation requested after the last progress check still applies;
+        // checked before saving, which overwrites the status
+        if stopped.is_none() && matches!(self.batches.status(&batch.batch_id).await, Ok(BatchStatus::Cancelling)) {
+            stopped = Some(BatchStatus::Cancelled);
+        }
+
+        batch.request_counts = counts;
+        batch.cost_usd = cost_usd;
+        batch.status = BatchStatus::Finalizing;
+        batch.finalizing_at = Some(Utc::now());
+        self.batches.save(batch).await?;
+
+        batch.output_file_id = self.write_results(batch, "output", &output).await?;
+        batch.error_file_id = self.write_results(batch, "error", &errors).await?;
+
+        tracing::info!(
+            "Batch {} finished: {} completed, {} failed, ${:.4}",
+            batch.batch_id,
+            counts.completed,
+            counts.failed,
+            cost_usd
+        );
+        Ok(stopped.unwrap_or(BatchStatus::Completed))
+    }
+
+    /// The project's shared pacing budget. A budget is rebuilt when the
+    /// project's limits change, once no running batch holds it.
+    fn budget(&self, project_id: &str, rate_limits: &RateLimits) -> Arc<ProjectBudget> {
+        let mut budgets = self.budgets.lock().unwrap_or_else(|e| e.into_inner());
+        let limits = (rate_limits.requests_per_minute, rate_limits.max_concurrent_requests);
+        match budgets.get(project_id) {
+            Some(budget) if budget.limits == limits || Arc::strong_count(budget) > 1 => budget.clone(),
+            _ => {
+                let budget = Arc::new(ProjectBudget::new(rate_limits));
+                budgets.insert(project_id.to_string(), budget.clone());
+                budget
+            }
+        }
+    }
+
+    /// Return a batch's budget, forgetting it once no other batch of the
+    /// project holds it
+    fn release_budget(&self, project_id: &str, budget: Arc<ProjectBudget>) {
+        drop(budget);
+        let mut budgets = self.budgets.lock().unwrap_or_else(|e| e.into_inner());
+        if budgets.get(project_id).is_some_and(|budget| Arc::strong_count(budget) == 1) {
+            budgets.remove(project_id);
+        }
+    }
+
+    /// Store result lines as a `batch_output` file
+    async fn write_results(
+        &self,
+        batch: &BatchJob,
+        kind: &str,
+        lines: &[serde_json::Value],
+    ) -> Result<Option<String>, AppError> {
+        if lines.is_empty() {
+            return Ok(None);
+        }
+
+        let mut data = Vec::new();
+        for line in lines {
+            serde_json::to_writer(&mut data, line)?;
+            data.push(b'\n');
+        }
+
+        let file = StoredFile::new(
+            batch.project_id.clone(),
+            format!("{}_{}.jsonl", batch.batch_id, kind),
+            "batch_output".to_string(),
+            data.len(),
+        );
+        Ok(Some(self.files.create(file, &data).await?.file_id))
+    }
+
+    /// Execute one request body against the batch endpoint
+    async fn execute(
+        &self,
+        batch: &BatchJob,
+        project: &Project,
+        api_key: &str,
+        endpoint: &str,
+        body: serde_json::Value,
+    ) -> Result<Executed, AppError> {
+        let started = Instant::now();
+
+        if endpoint == "/v1/embeddings" {
+            let model = body
+                .get("model")
+                .and_then(|m| m.as_str())
+                .ok_or_else(|| AppError::BadRequest("model is required".to_string()))?
+                .to_string();
+            let response = self.provider.embeddings(api_key, &body).await?;
+            let tokens = response["usage"]["prompt_tokens"].as_u64().unwrap_or(0) as u32;
+            let cost_usd = calculate_embedding_cost(&model, tokens) * self.config.cost_discount;
+            self.record_usage(batch, ApiEndpoint::Embeddings, model, (tokens, 0), cost_usd, started);
+            return Ok(Executed { body: response, cost_usd });
+        }
+
+        let mut request: ChatCompletionRequest = serde_json::from_value(body)
+            .map_err(|e| AppError::BadRequest(format!("Invalid chat completion request: {}", e)))?;
+        if request.stream {
+            return Err(AppError::BadRequest("stream is not supported in batches".to_string()));
+        }
+        if !self.chat_config.forward_unknown_fields {
+            request.extra.clear();
+        }
+        request.validate().map_err(AppError::BadRequest)?;
+
+        let path = format!("/v1/batches/{}", batch.batch_id);
+        let mut guarded = self
+            .guardrails
+            .check(&batch.project_id, &path, api_key, &mut request)
+            .await?;
+        if guarded.input_filtered {
+            return Ok(Executed {
+                body: serde_json::to_value(moderation::filtered_response(&request))?,
+                cost_usd: 0.0,
+            });
+        }
+
+        // Batches wait for token budget rather than failing the line
+        let tokens_per_minute = project.rate_limits.as_ref().and_then(|limits| limits.tokens_per_minute);
+        let reservation = self
+            .token_rate_limiter
+            .reserve_when_available(&batch.project_id, tokens_per_minute, guarded.reserved_tokens(&request))
+            .await?;
+        guarded = guarded.with_token_reservation(reservation);
+        guarded.recorder = guarded.recorder.with_cost_discount(self.config.cost_discount);
+
+        let mut response =
+            structured_output::chat_completion(&self.provider, api_key, &request, &guarded.recorder).await?;
+        self.guardrails.finish(&guarded, api_key, &mut response).await?;
+        let cost_usd = guarded.recorder.cost(&response.usage);
+        if let Some(metadata) = response.x_llmhub.as_mut() {
+            metadata.cost = cost_usd;
+        }
+
+        Ok(Executed {
+            body: serde_json::to_value(response)?,
+            cost_usd,
+        })
+    }
+
+    /// Log usage of one batch request in the background
+    fn record_usage(
+        &self,
+        batch: &BatchJob,
+        endpoint: ApiEndpoint,
+        model: String,
+        (prompt_tokens, completion_tokens): (u32, u32),
+        cost_usd: f64,
+        started: Instant,
+    ) {
+        let log = UsageLog::new(
+            batch.project_id.clone(),
+            endpoint,
+            LlmProvider::Openai,
+            model,
+            RequestMetadata {
+                request_id: uuid::Uuid::new_v4().to_string(),
+                method: "POST".to_string(),
+                path: format!("/v1/batches/{}", batch.batch_id),
+                ip_address: None,
+                user_agent: None,
+                prompt_tokens: Some(prompt_tokens as i32),
+                audio_duration_seconds: None,
+                file_size_bytes: None,
+                temperature: None,
+                max_tokens: None,
+                stream: false,
+            },
+            ResponseMetadata {
+                status_code: 200,
+                latency_ms: started.elapsed().as_millis() as u64,
+                provider_latency_ms: None,
+                completion_tokens: Some(completion_tokens as i32),
+                total_tokens: Some((prompt_tokens + completion_tokens) as i32),
+                finish_reason: None,
+            },
+            CostData {
+                prompt_cost_usd: None,
+                completion_cost_usd: None,
+                audio_cost_usd: None,
+                total_cost_usd: cost_usd,
+                cached_savings_usd: None,
+            },
+            None,
+            None,
+        );
+
+        // Log in background
+        let repo = self.usage_repository.clone();
+        tokio::spawn(async move {
+            if let Err(e) = repo.create(&log).await {
+                tracing::error!("Failed to log batch usage: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_batch_input() {
+        let line = |id: &str, url: &str| {
+            format!(r#"{{"custom_id":"{}","method":"POST","url":"{}","body":{{"model":"gpt-4o-mini"}}}}"#, id, url)
+        };
+        let input = format!("{}\n\n{}\n", line("a", "/v1/chat/completions"), line("b", "/v1/chat/completions"));
+        assert_eq!(parse_input(input.as_bytes(), Some("/v1/chat/completions"), 10).unwrap().len(), 2);
+
+        // Endpoint mismatch, duplicate IDs and oversized files are rejected
+        assert!(parse_input(input.as_bytes(), Some("/v1/embeddings"), 10).is_err());
+        let duplicate = format!("{}\n{}", line("a", "/v1/embeddings"), line("a", "/v1/embeddings"));
+        assert!(parse_input(duplicate.as_bytes(), None, 10).is_err());
+        assert!(parse_input(input.as_bytes(), None, 1).is_err());
+        assert!(parse_input(line("a", "/v1/images").as_bytes(), None, 10).is_err());
+
+        assert_eq!(error_status(&AppError::ExternalApiError("OpenAI API error (429 Too Many Requests): slow down".to_string())), 429);
+    }
+}
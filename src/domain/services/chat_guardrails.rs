@@ -0,0 +1,211 @@
+//! Per-project guardrails around a chat request: context-window fit, PII
+//! scrubbing, prompt-injection scoring and moderation. Shared by every path
+//! that sends chat requests to a provider, interactive and batch alike.
+
+use std::sync::Arc;
+
+use tracing::{info, warn};
+
+use crate::api::dto::{ChatCompletionRequest, ChatCompletionResponse};
+use crate::domain::entities::guardrail::{
+    InjectionAction, InjectionPolicy, InjectionResult, ModerationAction, ModerationPolicy,
+};
+use crate::domain::repositories::{GuardrailRepository, UsageRepository};
+use crate::domain::services::chat_usage::ChatUsageRecorder;
+use crate::domain::services::context_window;
+use crate::domain::services::injection::{
+    self, ClassifierDetector, InjectionDetector, RuleDetector,
+};
+use crate::domain::services::moderation::{self, ModerationService};
+use crate::domain::services::pii::{PiiScanner, PiiVault};
+use crate::domain::services::token_rate_limiter::TokenReservation;
+use crate::shared::error::AppError;
+
+pub struct ChatGuardrails {
+    guardrail_repository: Arc<dyn GuardrailRepository>,
+    usage_repository: Arc<dyn UsageRepository>,
+    moderation_service: Arc<ModerationService>,
+}
+
+/// A request that passed the project's input guardrails
+pub struct GuardedRequest {
+    /// Prompt tokens once the request fits its context window
+    pub prompt_tokens: usize,
+    pub recorder: ChatUsageRecorder,
+    /// Tokenised PII to restore in the reply
+    pub vault: Option<Arc<PiiVault>>,
+    pub moderation: Option<ModerationPolicy>,
+    pub injection: Option<InjectionResult>,
+    /// Input moderation filtered the request; reply with the filtered
+    /// response instead of calling the provider
+    pub input_filtered: bool,
+}
+
+impl GuardedRequest {
+    /// Settle the request's tokens-per-minute reservation when usage is recorded
+    pub fn with_token_reservation(self, reservation: Option<TokenReservation>) -> Self {
+        Self {
+            recorder: self.recorder.with_token_reservation(reservation),
+            ..self
+        }
+    }
+
+    /// Tokens the request reserves against `tokens_per_minute`: the prompt
+    /// plus the completion budget, for every choice
+    pub fn reserved_tokens(&self, request: &ChatCompletionRequest) -> u64 {
+        let completion_tokens = request.max_completion_tokens.or(request.max_tokens).unwrap_or(0);
+        let choices = request.n.unwrap_or(1).max(1) as u64;
+        (self.prompt_tokens as u64 + completion_tokens as u64) * choices
+    }
+}
+
+impl ChatGuardrails {
+    pub fn new(
+        guardrail_repository: Arc<dyn GuardrailRepository>,
+        usage_repository: Arc<dyn UsageRepository>,
+        moderation_service: Arc<ModerationService>,
+    ) -> Self {
+        Self {
+            guardrail_repository,
+            usage_repository,
+            moderation_service,
+        }
+    }
+
+    /// Apply the project's input guardrails, scrubbing the request in place.
+    /// Fails when the request is too long or a policy blocks it.
+    pub async fn check(
+        &self,
+        project_id: &str,
+        path: &str,
+        api_key: &str,
+        request: &mut ChatCompletionRequest,
+    ) -> Result<GuardedRequest, AppError> {
+        // Oversize requests fail here with a precise error rather than upstream
+        let fit = context_window::fit_context_window(request)?;
+        if fit.dropped > 0 {
+            info!("Truncated {} messages to fit the context window of {}", fit.dropped, request.model);
+        }
+
+        let (moderation, pii, injection) = match self.guardrail_repository.find_by_project(project_id).await? {
+            Some(policy) => (policy.moderation, policy.pii, policy.injection),
+            None => (None, None, None),
+        };
+
+        // Personal data is scrubbed before anything leaves the gateway, moderation included
+        let vault = match &pii {
+            Some(policy) => Some(PiiScanner::new(policy)?.scrub_request(request)?),
+            None => None,
+        }
+        .filter(|vault| !vault.is_empty())
+        .map(Arc::new);
+
+        let injection = match &injection {
+            Some(policy) => Some(self.score_injection(policy, api_key, project_id, path, request).await),
+            None => None,
+        };
+        let recorder = ChatUsageRecorder::new(self.usage_repository.clone(), project_id.to_string(), path, request)
+            .with_prompt_injection(injection.clone());
+        if let Some(result) = injection.as_ref().filter(|r| r.flagged) {
+            warn!("Possible prompt injection (score {:.2}): {}", result.score, result.rules.join(", "));
+            if result.action == InjectionAction::Block {
+                let error = AppError::ContentPolicyViolation(format!(
+                    "Possible prompt injection (score {:.2})",
+                    result.score
+                ));
+                recorder.record_rejected(400, error.to_string());
+                return Err(error);
+            }
+        }
+
+        let mut input_filtered = false;
+        if let Some(policy) = moderation.as_ref().filter(|p| p.input) {
+            let texts = moderation::request_texts(request);
+            let categories = self.moderation_service.check(api_key, policy, texts).await?;
+            if !categories.is_empty() {
+                warn!("Chat request blocked by moderation: {}", categories.join(", "));
+                match policy.action {
+                    ModerationAction::Reject => {
+                        let error = policy_violation("Input", &categories);
+                        recorder.record_rejected(400, error.to_string());
+                        return Err(error);
+                    }
+                    ModerationAction::ContentFilter => input_filtered = true,
+                }
+            }
+        }
+
+        Ok(GuardedRequest {
+            prompt_tokens: fit.prompt_tokens,
+            recorder,
+            vault,
+            moderation,
+            injection,
+            input_filtered,
+        })
+    }
+
+    /// Record a complete reply and apply the output guardrails: moderation,
+    /// PII restore and the prompt-injection flag
+    pub async fn finish(
+        &self,
+        guarded: &GuardedRequest,
+        api_key: &str,
+        response: &mut ChatCompletionResponse,
+    ) -> Result<(), AppError> {
+        let finish_reason = response.choices.first().and_then(|c| c.finish_reason.as_ref());
+        guarded.recorder.record(&response.usage, finish_reason);
+
+        if let Some(policy) = guarded.moderation.as_ref().filter(|p| p.output) {
+            let texts = moderation::response_texts(response);
+            let categories = self.moderation_service.check(api_key, policy, texts).await?;
+            if !categories.is_empty() {
+                warn!("Chat reply blocked by moderation: {}", categories.join(", "));
+                match policy.action {
+                    ModerationAction::Reject => return Err(policy_violation("Output", &categories)),
+                    ModerationAction::ContentFilter => moderation::filter_response(response),
+                }
+            }
+        }
+
+        if let Some(vault) = &guarded.vault {
+            vault.restore_response(response);
+        }
+        let flag = guarded.injection.as_ref().and_then(injection::metadata);
+        if let (Some(meta), Some(flag)) = (&mut response.x_llmhub, flag) {
+            meta.prompt_injection = Some(flag);
+        }
+        Ok(())
+    }
+
+    /// Score the request's untrusted input with the rules and, when configured,
+    /// the classifier model
+    async fn score_injection(
+        &self,
+        policy: &InjectionPolicy,
+        api_key: &str,
+        project_id: &str,
+        path: &str,
+        request: &ChatCompletionRequest,
+    ) -> InjectionResult {
+        let mut detectors: Vec<Box<dyn InjectionDetector>> = vec![Box::new(RuleDetector)];
+        if let Some(model) = &policy.classifier_model {
+            detectors.push(Box::new(ClassifierDetector::new(
+                model.clone(),
+                api_key.to_string(),
+                self.usage_repository.clone(),
+                project_id.to_string(),
+                path,
+            )));
+        }
+        injection::evaluate(policy, &detectors, &injection::request_inputs(request)).await
+    }
+}
+
+pub fn policy_violation(side: &str, categories: &[String]) -> AppError {
+    AppError::ContentPolicyViolation(format!(
+        "{} flagged by moderation: {}",
+        side,
+        categories.join(", ")
+    ))
+}
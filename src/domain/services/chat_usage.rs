@@ -0,0 +1,156 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::api::dto::{ChatCompletionRequest, ChatUsage, FinishReason};
+use crate::domain::entities::guardrail::InjectionResult;
+use crate::domain::entities::usage::{
+    ApiEndpoint, CostData, RequestMetadata, ResponseMetadata, UsageLog,
+};
+use crate::domain::entities::LlmProvider;
+use crate::domain::repositories::usage_repository::UsageRepository;
+use crate::domain::services::providers::openai::calculate_openai_cost;
+use crate::domain::services::token_rate_limiter::TokenReservation;
+
+/// Records usage of one chat request, whichever ingress API it arrived on
+#[derive(Clone)]
+pub struct ChatUsageRecorder {
+    usage_repository: Arc<dyn UsageRepository>,
+    project_id: String,
+    path: String,
+    model: String,
+    temperature: f32,
+    max_tokens: Option<u32>,
+    stream: bool,
+    started_at: Instant,
+    prompt_injection: Option<InjectionResult>,
+    token_reservation: Option<Arc<TokenReservation>>,
+    cost_discount: f64,
+}
+
+impl ChatUsageRecorder {
+    pub fn new(
+        usage_repository: Arc<dyn UsageRepository>,
+        project_id: String,
+        path: &str,
+        request: &ChatCompletionRequest,
+    ) -> Self {
+        Self {
+            usage_repository,
+            project_id,
+            path: path.to_string(),
+            model: request.model.clone(),
+            temperature: request.temperature,
+            max_tokens: request.max_completion_tokens.or(request.max_tokens),
+            stream: request.stream,
+            started_at: Instant::now(),
+            prompt_injection: None,
+            token_reservation: None,
+            cost_discount: 1.0,
+        }
+    }
+
+    /// Attach the prompt-injection result to every log written
+    pub fn with_prompt_injection(mut self, result: Option<InjectionResult>) -> Self {
+        self.prompt_injection = result;
+        self
+    }
+
+    /// Settle the request's tokens-per-minute reservation when usage is recorded
+    pub fn with_token_reservation(mut self, reservation: Option<TokenReservation>) -> Self {
+        self.token_reservation = reservation.map(Arc::new);
+        self
+    }
+
+    /// Log cost at this fraction of the list price, as batches are billed
+    pub fn with_cost_discount(mut self, discount: f64) -> Self {
+        self.cost_discount = discount;
+        self
+    }
+
+    /// Cost of `usage` at the recorder's discount
+    pub fn cost(&self, usage: &ChatUsage) -> f64 {
+        calculate_openai_cost(&self.model, usage.prompt_tokens, usage.completion_tokens) * self.cost_discount
+    }
+
+    /// Log usage in the background
+    pub fn record(&self, usage: &ChatUsage, finish_reason: Option<&FinishReason>) {
+        if let Some(reservation) = &self.token_reservation {
+            reservation.settle(usage.total_tokens as u64);
+        }
+        let finish_reason = finish_reason
+            .and_then(|reason| serde_json::to_value(reason).ok())
+            .and_then(|value| value.as_str().map(str::to_string));
+        self.write(usage, 200, finish_reason, None);
+    }
+
+    /// Log usage billed by a request that still ended in an error
+    pub fn record_failed(&self, usage: &ChatUsage, status_code: u16, error: String) {
+        if let Some(reservation) = &self.token_reservation {
+            reservation.settle(usage.total_tokens as u64);
+        }
+        self.write(usage, status_code, None, Some(error));
+    }
+
+    /// Log a request the gateway refused before dispatch
+    pub fn record_rejected(&self, status_code: u16, error: String) {
+        let usage = ChatUsage {
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            total_tokens: 0,
+            prompt_tokens_details: None,
+            completion_tokens_details: None,
+        };
+        self.write(&usage, status_code, None, Some(error));
+    }
+
+    fn write(&self, usage: &ChatUsage, status_code: u16, finish_reason: Option<String>, error: Option<String>) {
+        let cost_usd = self.cost(usage);
+        let latency_ms = self.started_at.elapsed().as_millis() as u64;
+
+        let mut log = UsageLog::new(
+            self.project_id.clone(),
+            ApiEndpoint::ChatCompletions,
+            LlmProvider::Openai,
+            self.model.clone(),
+            RequestMetadata {
+                request_id: uuid::Uuid::new_v4().to_string(),
+                method: "POST".to_string(),
+                path: self.path.clone(),
+                ip_address: None,
+                user_agent: None,
+                prompt_tokens: Some(usage.prompt_tokens as i32),
+                audio_duration_seconds: None,
+                file_size_bytes: None,
+                temperature: Some(self.temperature),
+                max_tokens: self.max_tokens.map(|tokens| tokens as i32),
+                stream: self.stream,
+            },
+            ResponseMetadata {
+                status_code,
+                latency_ms,
+                provider_latency_ms: None,
+                completion_tokens: Some(usage.completion_tokens as i32),
+                total_tokens: Some(usage.total_tokens as i32),
+                finish_reason,
+            },
+            CostData {
+                prompt_cost_usd: None,
+                completion_cost_usd: None,
+                audio_cost_usd: None,
+                total_cost_usd: cost_usd,
+                cached_savings_usd: None,
+            },
+            None,
+            error,
+        );
+        log.prompt_injection = self.prompt_injection.clone();
+
+        // Log in background
+        let repo = self.usage_repository.clone();
+        tokio::spawn(async move {
+            if let Err(e) = repo.create(&log).await {
+                tracing::error!("Failed to log chat usage: {}", e);
+            }
+        });
+    }
+}
@@ -0,0 +1,165 @@
+//! Context-window validation: oversize chat requests are rejected with a
+//! `context_length_exceeded` error before they reach the provider, or
+//! shortened when the client opts into truncation.
+
+use std::collections::HashSet;
+
+use crate::api::dto::{ChatCompletionRequest, ChatMessage, ChatRole, TruncationStrategy};
+use crate::domain::services::tokenizer::{context_window, count_prompt};
+use crate::shared::error::AppError;
+
+/// Prompt size of a request once it fits its context window
+#[derive(Debug, PartialEq, Eq)]
+pub struct ContextFit {
+    /// Prompt tokens left after truncation
+    pub prompt_tokens: usize,
+    /// Messages dropped by truncation
+    pub dropped: usize,
+}
+
+/// Check the request fits its model's context window, truncating it when
+/// requested
+pub fn fit_context_window(request: &mut ChatCompletionRequest) -> Result<ContextFit, AppError> {
+    let completion = request.max_completion_tokens.or(request.max_tokens).unwrap_or(0) as usize;
+    let prompt = count_prompt(request);
+    let mut total = prompt.total();
+    let fits = |prompt_tokens| ContextFit { prompt_tokens, dropped: 0 };
+    let Some(window) = context_window(&request.model) else {
+        return Ok(fits(total));
+    };
+    if total + completion <= window {
+        return Ok(fits(total));
+    }
+    let Some(strategy) = request.truncation else {
+        return Err(exceeded(window, total, completion));
+    };
+
+    let turns = turns(&request.messages);
+    let Some((_latest, earlier)) = turns.split_last() else {
+        return Err(exceeded(window, total, completion));
+    };
+    let mut candidates: Vec<&Vec<usize>> = match strategy {
+        TruncationStrategy::DropOldest => earlier.iter().collect(),
+        TruncationStrategy::MiddleOut => earlier.iter().skip(1).collect(),
+    };
+
+    let mut dropped = HashSet::new();
+    while total + completion > window && !candidates.is_empty() {
+        let next = match strategy {
+            TruncationStrategy::DropOldest => 0,
+            TruncationStrategy::MiddleOut => (candidates.len() - 1) / 2,
+        };
+        for &index in candidates.remove(next) {
+            total -= prompt.messages[index];
+            dropped.insert(index);
+        }
+    }
+    if total + completion > window {
+        return Err(exceeded(window, total, completion));
+    }
+
+    let mut index = 0;
+    request.messages.retain(|_| {
+        index += 1;
+        !dropped.contains(&(index - 1))
+    });
+    Ok(ContextFit {
+        prompt_tokens: total,
+        dropped: dropped.len(),
+    })
+}
+
+/// Indices of the droppable conversation turns in order. System and developer
+/// messages are pinned; tool results stay with the assistant message that
+/// called them.
+fn turns(messages: &[ChatMessage]) -> Vec<Vec<usize>> {
+    let mut turns: Vec<Vec<usize>> = Vec::new();
+    let mut calls = HashSet::new();
+    for (index, message) in messages.iter().enumerate() {
+        match &message.role {
+            ChatRole::System => {}
+            ChatRole::Other(role) if role == "developer" => {}
+            ChatRole::Tool
+                if message.tool_call_id.as_ref().is_some_and(|id| calls.contains(id.as_str())) =>
+            {
+                if let Some(turn) = turns.last_mut() {
+                    turn.push(index);
+                }
+            }
+            _ => {
+                calls = message
+                    .tool_calls
+                    .iter()
+                    .flatten()
+                    .map(|call| call.id.as_str())
+                    .collect();
+                turns.push(vec![index]);
+            }
+        }
+    }
+    turns
+}
+
+fn exceeded(window: usize, prompt: usize, completion: usize) -> AppError {
+    AppError::ContextLengthExceeded(format!(
+        "This model's maximum context length is {} tokens. However, you requested {} tokens \
+         ({} in the messages, {} in the completion). Please reduce the length of the messages \
+         or completion, or set \"truncation\" to \"drop_oldest\" or \"middle_out\".",
+        window,
+        prompt + completion,
+        prompt,
+        completion
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::dto::ChatContent;
+
+    fn request(truncation: Option<TruncationStrategy>) -> ChatCompletionRequest {
+        let long = "word ".repeat(4000);
+        serde_json::from_value(serde_json::json!({
+            "model": "gpt-4-0613",
+            "max_tokens": 500,
+            "messages": [
+                { "role": "system", "content": "Be brief." },
+                { "role": "user", "content": format!("first {}", long) },
+                { "role": "assistant", "content": null, "tool_calls": [
+                    { "id": "call_1", "type": "function", "function": { "name": "search", "arguments": "{}" } }
+                ] },
+                { "role": "tool", "tool_call_id": "call_1", "content": format!("result {}", long) },
+                { "role": "user", "content": format!("second {}", long) },
+                { "role": "user", "content": "Latest question" }
+            ],
+            "truncation": truncation,
+        }))
+        .unwrap()
+    }
+
+    fn texts(request: &ChatCompletionRequest) -> Vec<String> {
+        request
+            .messages
+            .iter()
+            .map(|m| match &m.content {
+                Some(ChatContent::Text(text)) => text.split(' ').next().unwrap().to_string(),
+                _ => "call".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rejects_or_truncates_oversize_requests() {
+        let err = fit_context_window(&mut request(None)).unwrap_err();
+        assert!(err.to_string().contains("maximum context length is 8192 tokens"));
+
+        let mut oldest = request(Some(TruncationStrategy::DropOldest));
+        assert_eq!(fit_context_window(&mut oldest).unwrap().dropped, 3);
+        assert_eq!(texts(&oldest), vec!["Be", "second", "Latest"]);
+
+        // The tool result leaves with its call
+        let mut middle = request(Some(TruncationStrategy::MiddleOut));
+        assert_eq!(fit_context_window(&mut middle).unwrap().dropped, 3);
+        assert_eq!(texts(&middle), vec!["Be", "first", "Latest"]);
+    }
+}
@@ -0,0 +1,596 @@
+//! Translation between the Gemini generateContent API and the gateway's chat
+//! completions model, so `/v1beta/models/*` reaches every chat backend.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use tracing::{debug, warn};
+
+use crate::api::dto::{
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatContent, ChatMessage,
+    ChatResponseFormat, ChatRole, ChatTool, ChatUsage, ContentPart, FinishReason, FunctionCall,
+    FunctionDefinition, GeminiCandidate, GeminiContent, GeminiFunctionCall, GeminiPart,
+    GenerateContentRequest, GenerateContentResponse, GenerationConfig, ImageUrl, InputAudio,
+    JsonSchemaFormat, NamedToolChoice, StopSequences, StreamOptions, ToolCall, ToolChoice,
+    ToolChoiceFunction, ToolChoiceMode, ToolType, UsageMetadata,
+};
+use crate::shared::error::AppError;
+
+/// Build the equivalent chat completion request
+pub fn to_chat_request(
+    model: String,
+    request: GenerateContentRequest,
+    stream: bool,
+) -> Result<ChatCompletionRequest, AppError> {
+    let config = request.generation_config;
+    if config.as_ref().and_then(|c| c.top_k).is_some() {
+        return Err(AppError::BadRequest(format!("topK is not supported by model {}", model)));
+    }
+
+    if request.safety_settings.is_some() {
+        debug!("Ignoring safetySettings; {} applies its own filtering", model);
+    }
+
+    let mut messages = Vec::new();
+    if let Some(system) = request.system_instruction {
+        messages.push(text_message(ChatRole::System, part_text(&system.parts)));
+    }
+    let mut calls = PendingCalls::default();
+    for content in request.contents {
+        push_content(&mut messages, &mut calls, content)?;
+    }
+
+    let mut tools: Option<Vec<ChatTool>> = request.tools.map(|tools| {
+        tools
+            .into_iter()
+            .flat_map(|tool| tool.function_declarations.unwrap_or_default())
+            .map(|declaration| ChatTool {
+                r#type: ToolType::Function,
+                function: FunctionDefinition {
+                    name: declaration.name,
+                    description: declaration.description,
+                    parameters: declaration.parameters.map(json_schema),
+                    strict: None,
+                },
+            })
+            .collect()
+    });
+
+    let mut tool_choice = None;
+    if let Some(calling) = request.tool_config.and_then(|c| c.function_calling_config) {
+        let allowed = calling.allowed_function_names.unwrap_or_default();
+        tool_choice = match calling.mode.as_deref().map(str::to_ascii_uppercase).as_deref() {
+            None | Some("AUTO") | Some("MODE_UNSPECIFIED") => Some(ToolChoice::Mode(ToolChoiceMode::Auto)),
+            Some("NONE") => Some(ToolChoice::Mode(ToolChoiceMode::None)),
+            Some("ANY") if allowed.len() == 1 => Some(ToolChoice::Function(NamedToolChoice {
+                r#type: ToolType::Function,
+                function: ToolChoiceFunction { name: allowed[0].clone() },
+            })),
+            Some("ANY") => {
+                // Chat completions cannot name several tools, so narrow the list instead
+                if let (Some(tools), false) = (tools.as_mut(), allowed.is_empty()) {
+                    tools.retain(|tool| allowed.contains(&tool.function.name));
+                }
+                Some(ToolChoice::Mode(ToolChoiceMode::Required))
+            }
+            Some(mode) => {
+                return Err(AppError::BadRequest(format!(
+                    "Unsupported functionCallingConfig mode: {}",
+                    mode
+                )))
+            }
+        };
+    }
+
+    let GenerationConfig {
+        temperature,
+        top_p,
+        candidate_count,
+        max_output_tokens,
+        stop_sequences,
+        presence_penalty,
+        frequency_penalty,
+        seed,
+        response_mime_type,
+        response_schema,
+        response_json_schema,
+        ..
+    } = config.unwrap_or_default();
+
+    let response_format = match (response_json_schema, response_schema.map(json_schema)) {
+        (Some(schema), _) | (None, Some(schema)) => Some(ChatResponseFormat::JsonSchema {
+            json_schema: JsonSchemaFormat {
+                name: "response".to_string(),
+                description: None,
+                schema: Some(schema),
+                strict: None,
+            },
+        }),
+        (None, None) => match response_mime_type.as_deref() {
+            Some("application/json") => Some(ChatResponseFormat::JsonObject),
+            Some("text/plain") | None => None,
+            Some(mime_type) => {
+                return Err(AppError::BadRequest(format!(
+                    "Unsupported responseMimeType: {}",
+                    mime_type
+                )))
+            }
+        },
+    };
+
+    Ok(ChatCompletionRequest {
+        model,
+        messages,
+        temperature: temperature.unwrap_or(1.0),
+        max_tokens: max_output_tokens,
+        stream,
+        top_p: top_p.unwrap_or(1.0),
+        frequency_penalty: frequency_penalty.unwrap_or(0.0),
+        presence_penalty: presence_penalty.unwrap_or(0.0),
+        tools,
+        tool_choice,
+        parallel_tool_calls: None,
+        response_format,
+        n: candidate_count,
+        stop: stop_sequences.map(StopSequences::Multiple),
+        seed,
+        logprobs: None,
+        top_logprobs: None,
+        logit_bias: None,
+        user: None,
+        max_completion_tokens: None,
+        // Usage is reported on the final chunk
+        stream_options: stream.then_some(StreamOptions {
+            include_usage: Some(true),
+        }),
+        reasoning_effort: None,
+        truncation: None,
+        extra: Default::default(),
+    })
+}
+
+fn text_message(role: ChatRole, text: String) -> ChatMessage {
+    ChatMessage {
+        role,
+        content: Some(ChatContent::Text(text)),
+        name: None,
+        tool_calls: None,
+        tool_call_id: None,
+        refusal: None,
+    }
+}
+
+/// Text of the text parts, concatenated as Gemini does
+fn part_text(parts: &[GeminiPart]) -> String {
+    parts.iter().filter_map(|part| part.text.as_deref()).collect()
+}
+
+/// Tool call ids issued for model turns, so function responses that only
+/// carry a name can be matched to their call
+#[derive(Default)]
+struct PendingCalls {
+    next_id: u32,
+    by_name: HashMap<String, VecDeque<String>>,
+}
+
+impl PendingCalls {
+    fn issue(&mut self, call: &GeminiFunctionCall) -> String {
+        let id = call.id.clone().unwrap_or_else(|| {
+            self.next_id += 1;
+            format!("call_{}", self.next_id)
+        });
+        self.by_name.entry(call.name.clone()).or_default().push_back(id.clone());
+        id
+    }
+
+    fn resolve(&mut self, id: Option<&str>, name: &str) -> Option<String> {
+        let pending = self.by_name.get_mut(name)?;
+        match id {
+            Some(id) => {
+                let position = pending.iter().position(|pending| pending == id)?;
+                pending.remove(position)
+            }
+            None => pending.pop_front(),
+        }
+    }
+}
+
+fn push_content(
+    messages: &mut Vec<ChatMessage>,
+    calls: &mut PendingCalls,
+    content: GeminiContent,
+) -> Result<(), AppError> {
+    if content.role.as_deref() == Some("model") {
+        let mut tool_calls = Vec::new();
+        for call in content.parts.iter().filter_map(|part| part.function_call.as_ref()) {
+            tool_calls.push(ToolCall {
+                id: calls.issue(call),
+                r#type: ToolType::Function,
+                function: FunctionCall {
+                    name: call.name.clone(),
+                    arguments: call.args.to_string(),
+                },
+            });
+        }
+        let text = part_text(&content.parts);
+        messages.push(ChatMessage {
+            role: ChatRole::Assistant,
+            content: (!text.is_empty()).then_some(ChatContent::Text(text)),
+            name: None,
+            tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+            tool_call_id: None,
+            refusal: None,
+        });
+        return Ok(());
+    }
+
+    // Function responses must directly follow the model's function calls
+    let mut parts = Vec::new();
+    for part in content.parts {
+        if let Some(response) = part.function_response {
+            let id = calls.resolve(response.id.as_deref(), &response.name).ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "functionResponse {} does not match any preceding functionCall",
+                    response.name
+                ))
+            })?;
+            messages.push(ChatMessage {
+                role: ChatRole::Tool,
+                content: Some(ChatContent::Text(response.response.to_string())),
+                name: None,
+                tool_calls: None,
+                tool_call_id: Some(id),
+                refusal: None,
+            });
+        } else if let Some(text) = part.text {
+            parts.push(ContentPart::Text { text });
+        } else if let Some(data) = part.inline_data {
+            parts.push(media_part(&data.mime_type, data.data, true)?);
+        } else if let Some(file) = part.file_data {
+            let mime_type = file.mime_type.as_deref().unwrap_or("image/*");
+            parts.push(media_part(mime_type, file.file_uri, false)?);
+        } else if part.function_call.is_some() {
+            return Err(AppError::BadRequest(
+                "functionCall parts are only allowed in model turns".to_string(),
+            ));
+        }
+    }
+    if !parts.is_empty() {
+        messages.push(ChatMessage {
+            role: ChatRole::User,
+            content: Some(ChatContent::Parts(parts)),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+            refusal: None,
+        });
+    }
+
+    Ok(())
+}
+
+/// Chat content part for inline bytes or a file URI
+fn media_part(mime_type: &str, data: String, inline: bool) -> Result<ContentPart, AppError> {
+    if mime_type.starts_with("image/") {
+        let url = if inline {
+            format!("data:{};base64,{}", mime_type, data)
+        } else {
+            data
+        };
+        return Ok(ContentPart::ImageUrl {
+            image_url: ImageUrl { url, detail: None },
+        });
+    }
+
+    let format = match mime_type {
+        "audio/wav" | "audio/x-wav" => "wav",
+        "audio/mp3" | "audio/mpeg" => "mp3",
+        _ => {
+            return Err(AppError::BadRequest(format!(
+                "Unsupported media type: {}",
+                mime_type
+            )))
+        }
+    };
+    if !inline {
+        return Err(AppError::BadRequest(
+            "Audio must be sent as inlineData".to_string(),
+        ));
+    }
+    Ok(ContentPart::InputAudio {
+        input_audio: InputAudio {
+            data,
+            format: format.to_string(),
+        },
+    })
+}
+
+/// Convert a Gemini (OpenAPI-style) schema to JSON Schema: type names are
+/// upper case and nullability is a separate flag
+fn json_schema(schema: serde_json::Value) -> serde_json::Value {
+    match schema {
+        serde_json::Value::Object(mut object) => {
+            let nullable = object.remove("nullable").and_then(|n| n.as_bool()) == Some(true);
+            for (key, value) in object.iter_mut() {
+                *value = match (key.as_str(), value.take()) {
+                    ("type", serde_json::Value::String(name)) => {
+                        serde_json::Value::String(name.to_ascii_lowercase())
+                    }
+                    // Property names are not schemas but their values are
+                    ("properties", serde_json::Value::Object(properties)) => serde_json::Value::Object(
+                        properties.into_iter().map(|(name, schema)| (name, json_schema(schema))).collect(),
+                    ),
+                    (_, value) => json_schema(value),
+                };
+            }
+            if nullable {
+                if let Some(name) = object.remove("type") {
+                    object.insert("type".to_string(), serde_json::json!([name, "null"]));
+                }
+            }
+            serde_json::Value::Object(object)
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(json_schema).collect())
+        }
+        value => value,
+    }
+}
+
+fn finish_reason(reason: &FinishReason) -> String {
+    match reason {
+        FinishReason::Stop | FinishReason::ToolCalls => "STOP",
+        FinishReason::Length => "MAX_TOKENS",
+        FinishReason::ContentFilter => "SAFETY",
+        FinishReason::Other(_) => "OTHER",
+    }
+    .to_string()
+}
+
+fn usage_metadata(usage: &ChatUsage) -> UsageMetadata {
+    UsageMetadata {
+        prompt_token_count: usage.prompt_tokens,
+        candidates_token_count: usage.completion_tokens,
+        total_token_count: usage.total_tokens,
+        cached_content_token_count: usage
+            .prompt_tokens_details
+            .as_ref()
+            .and_then(|details| details.cached_tokens)
+            .filter(|tokens| *tokens > 0),
+        thoughts_token_count: usage
+            .completion_tokens_details
+            .as_ref()
+            .and_then(|details| details.reasoning_tokens)
+            .filter(|tokens| *tokens > 0),
+    }
+}
+
+/// Function call part with the arguments Gemini clients expect as an object
+fn function_call_part(id: String, name: String, arguments: &str) -> GeminiPart {
+    let args = serde_json::from_str(arguments).unwrap_or_else(|e| {
+        warn!("Tool call arguments are not valid JSON: {}", e);
+        serde_json::Value::Object(Default::default())
+    });
+    GeminiPart {
+        function_call: Some(GeminiFunctionCall { id: Some(id), name, args }),
+        ..Default::default()
+    }
+}
+
+fn model_content(parts: Vec<GeminiPart>) -> GeminiContent {
+    GeminiContent {
+        role: Some("model".to_string()),
+        parts,
+    }
+}
+
+/// Build the generateContent response from a chat completion
+pub fn from_chat_response(response: ChatCompletionResponse) -> GenerateContentResponse {
+    let candidates = response
+        .choices
+        .into_iter()
+        .map(|choice| {
+            let message = choice.message;
+            let mut parts = Vec::new();
+            let text = match message.content {
+                Some(ChatContent::Text(text)) => text,
+                Some(ChatContent::Parts(parts)) => parts
+                    .into_iter()
+                    .filter_map(|part| match part {
+                        ContentPart::Text { text } => Some(text),
+                        _ => None,
+                    })
+                    .collect(),
+                None => String::new(),
+            };
+            if let Some(text) = Some(text).filter(|t| !t.is_empty()).or(message.refusal) {
+                parts.push(GeminiPart {
+                    text: Some(text),
+                    ..Default::default()
+                });
+            }
+            for call in message.tool_calls.into_iter().flatten() {
+                parts.push(function_call_part(call.id, call.function.name, &call.function.arguments));
+            }
+
+            GeminiCandidate {
+                index: choice.index,
+                content: model_content(parts),
+                finish_reason: choice.finish_reason.as_ref().map(finish_reason),
+            }
+        })
+        .collect();
+
+    GenerateContentResponse {
+        candidates,
+        usage_metadata: Some(usage_metadata(&response.usage)),
+        model_version: response.model,
+        response_id: response.id,
+    }
+}
+
+/// Converts a chat completion chunk stream into streamed generateContent
+/// responses. Text is relayed as it arrives; function calls are sent whole
+/// with the finish reasons and usage once the upstream stream ends.
+#[derive(Default)]
+pub struct GeminiStream {
+    response_id: String,
+    model: String,
+    /// Id, name and accumulated arguments per (choice, tool call) index
+    tool_calls: BTreeMap<(u32, u32), (String, String, String)>,
+    finish_reasons: BTreeMap<u32, String>,
+    usage: Option<UsageMetadata>,
+}
+
+impl GeminiStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Response for one upstream chunk, if it carries text
+    pub fn push(&mut self, chunk: ChatCompletionChunk) -> Option<GenerateContentResponse> {
+        if self.response_id.is_empty() {
+            self.response_id = chunk.id;
+            self.model = chunk.model;
+        }
+        if let Some(usage) = &chunk.usage {
+            self.usage = Some(usage_metadata(usage));
+        }
+
+        let mut candidates = Vec::new();
+        for choice in chunk.choices {
+            for call in choice.delta.tool_calls.into_iter().flatten() {
+                let entry = self.tool_calls.entry((choice.index, call.index)).or_default();
+                if let Some(id) = call.id {
+                    entry.0 = id;
+                }
+                if let Some(function) = call.function {
+                    entry.1.push_str(function.name.as_deref().unwrap_or_default());
+                    entry.2.push_str(function.arguments.as_deref().unwrap_or_default());
+                }
+            }
+            if let Some(reason) = &choice.finish_reason {
+                self.finish_reasons.insert(choice.index, finish_reason(reason));
+            }
+
+            let text: String = [choice.delta.content, choice.delta.refusal]
+                .into_iter()
+                .flatten()
+                .collect();
+            if !text.is_empty() {
+                candidates.push(GeminiCandidate {
+                    index: choice.index,
+                    content: model_content(vec![GeminiPart {
+                        text: Some(text),
+                        ..Default::default()
+                    }]),
+                    finish_reason: None,
+                });
+            }
+        }
+
+        (!candidates.is_empty()).then(|| self.response(candidates, None))
+    }
+
+    /// Closing response once the upstream stream has ended
+    pub fn finish(&mut self) -> GenerateContentResponse {
+        let mut parts: BTreeMap<u32, Vec<GeminiPart>> = BTreeMap::new();
+        for ((choice, _), (id, name, arguments)) in std::mem::take(&mut self.tool_calls) {
+            parts.entry(choice).or_default().push(function_call_part(id, name, &arguments));
+        }
+
+        let mut indexes: Vec<u32> = parts.keys().chain(self.finish_reasons.keys()).copied().collect();
+        indexes.sort_unstable();
+        indexes.dedup();
+        if indexes.is_empty() {
+            indexes.push(0);
+        }
+
+        let candidates = indexes
+            .into_iter()
+            .map(|index| GeminiCandidate {
+                index,
+                content: model_content(parts.remove(&index).unwrap_or_default()),
+                finish_reason: Some(
+                    self.finish_reasons.remove(&index).unwrap_or_else(|| "STOP".to_string()),
+                ),
+            })
+            .collect();
+        let usage = self.usage.take();
+        self.response(candidates, usage)
+    }
+
+    fn response(
+        &self,
+        candidates: Vec<GeminiCandidate>,
+        usage_metadata: Option<UsageMetadata>,
+    ) -> GenerateContentResponse {
+        GenerateContentResponse {
+            candidates,
+            usage_metadata,
+            model_version: self.model.clone(),
+            response_id: self.response_id.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn translates_function_calling_round_trip() {
+        let request: GenerateContentRequest = serde_json::from_value(json!({
+            "systemInstruction": { "parts": [{ "text": "Be brief." }] },
+            "tools": [{ "functionDeclarations": [{
+                "name": "get_weather",
+                "parameters": { "type": "OBJECT", "properties": { "city": { "type": "STRING", "nullable": true } } }
+            }]}],
+            "contents": [
+                { "role": "user", "parts": [{ "text": "Weather in Paris?" }] },
+                { "role": "model", "parts": [{ "functionCall": { "name": "get_weather", "args": { "city": "Paris" } } }] },
+                { "role": "user", "parts": [{ "functionResponse": { "name": "get_weather", "response": { "temp": 18 } } }] }
+            ],
+            "generationConfig": { "maxOutputTokens": 64, "responseMimeType": "application/json" }
+        }))
+        .unwrap();
+
+        let chat = to_chat_request("gpt-4o".to_string(), request, false).unwrap();
+        assert!(chat.validate().is_ok());
+        let roles: Vec<_> = chat.messages.iter().map(|m| serde_json::to_value(&m.role).unwrap()).collect();
+        assert_eq!(roles, ["system", "user", "assistant", "tool"]);
+        let call_id = &chat.messages[2].tool_calls.as_ref().unwrap()[0].id;
+        assert_eq!(chat.messages[3].tool_call_id.as_ref(), Some(call_id));
+        assert_eq!(
+            chat.tools.as_ref().unwrap()[0].function.parameters,
+            Some(json!({ "type": "object", "properties": { "city": { "type": ["string", "null"] } } }))
+        );
+        assert!(matches!(chat.response_format, Some(ChatResponseFormat::JsonObject)));
+    }
+
+    #[test]
+    fn streams_function_calls_at_the_end() {
+        let chunk = |value: serde_json::Value| -> ChatCompletionChunk {
+            let mut chunk = json!({ "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4o" });
+            chunk.as_object_mut().unwrap().extend(value.as_object().unwrap().clone());
+            serde_json::from_value(chunk).unwrap()
+        };
+
+        let mut stream = GeminiStream::new();
+        let text = stream.push(chunk(json!({ "choices": [{ "index": 0, "delta": { "content": "Checking" } }] })));
+        assert_eq!(text.unwrap().candidates[0].content.parts[0].text.as_deref(), Some("Checking"));
+        assert!(stream
+            .push(chunk(json!({ "choices": [{ "index": 0, "delta": { "tool_calls": [{
+                "index": 0, "id": "call_1", "type": "function",
+                "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" }
+            }]}, "finish_reason": "tool_calls" }] })))
+            .is_none());
+        stream.push(chunk(json!({ "choices": [], "usage": { "prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15 } })));
+
+        let last = stream.finish();
+        assert_eq!(last.candidates[0].finish_reason.as_deref(), Some("STOP"));
+        let call = last.candidates[0].content.parts[0].function_call.as_ref().unwrap();
+        assert_eq!(call.args, json!({ "city": "Paris" }));
+        assert_eq!(last.usage_metadata.unwrap().total_token_count, 15);
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::api::dto::{ImagesMetadata, ImagesResponse};
+use crate::domain::entities::image::{ImageOperation, ImageRequest};
+use crate::domain::entities::usage::{
+    ApiEndpoint, CostData, RequestMetadata, ResponseMetadata, UsageLog,
+};
+use crate::domain::entities::{LlmProvider, RateLimits};
+use crate::domain::repositories::usage_repository::UsageRepository;
+use crate::domain::services::llm_api_key::LlmApiKeyService;
+use crate::domain::services::providers::openai::{calculate_image_cost, image_content_type};
+use crate::domain::services::providers::OpenAIProvider;
+use crate::shared::error::AppError;
+
+/// Window over which `requests_per_minute` is counted
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Image service proxying generations, edits and variations to OpenAI or an
+/// OpenAI-compatible server, within the project's rate limits
+pub struct ImageService {
+    usage_repository: Arc<dyn UsageRepository>,
+    llm_key_service: Arc<LlmApiKeyService>,
+    openai_provider: OpenAIProvider,
+    usage: Arc<Mutex<HashMap<String, ProjectUsage>>>,
+}
+
+/// Recent and in-flight image requests of one project
+#[derive(Default)]
+struct ProjectUsage {
+    in_flight: u32,
+    started: VecDeque<Instant>,
+}
+
+/// Holds one of the project's concurrent request slots until dropped
+struct RequestSlot {
+    project_id: String,
+    usage: Arc<Mutex<HashMap<String, ProjectUsage>>>,
+}
+
+impl Drop for RequestSlot {
+    fn drop(&mut self) {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(project) = usage.get_mut(&self.project_id) {
+            project.in_flight -= 1;
+            if project.in_flight == 0 && project.started.is_empty() {
+                usage.remove(&self.project_id);
+            }
+        }
+    }
+}
+
+impl ImageService {
+    pub fn new(
+        usage_repository: Arc<dyn UsageRepository>,
+        llm_key_service: Arc<LlmApiKeyService>,
+        base_url: Option<String>,
+    ) -> Self {
+        Self {
+            usage_repository,
+            llm_key_service,
+            openai_provider: base_url
+                .map(OpenAIProvider::with_base_url)
+                .unwrap_or_else(OpenAIProvider::new),
+            usage: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Run an image request and log its per-image cost
+    pub async fn create(
+        &self,
+        project_id: String,
+        rate_limits: &RateLimits,
+        request: ImageRequest,
+    ) -> Result<ImagesResponse, AppError> {
+        validate(&request)?;
+        let _slot = self.reserve_slot(&project_id, rate_limits)?;
+        let start_time = Instant::now();
+
+        let provider = LlmProvider::Openai;
+        let api_key = if let Some(key_id) = &request.llm_api_key_id {
+            self.llm_key_service
+                .get_project_key(&project_id, key_id, Some(&provider))
+                .await?
+        } else {
+            self.llm_key_service
+                .get_default_key_for_provider(&project_id, &provider)
+                .await?
+                .ok_or_else(|| {
+                    AppError::ConfigError(format!(
+                        "No LLM API key configured for provider: {:?}",
+                        provider
+                    ))
+                })?
+        };
+
+        let mut response = self.openai_provider.images(&api_key, &request).await?;
+        let response_time_ms = start_time.elapsed().as_millis() as u64;
+
+        // Fall back to the size and quality the provider reports it used
+        let reported = |key: &str| response.extra.get(key).and_then(|v| v.as_str());
+        let size = request.size.as_deref().or_else(|| reported("size"));
+        let quality = request.quality.as_deref().or_else(|| reported("quality"));
+        let cost_usd = calculate_image_cost(&request.model, size, quality, response.data.len());
+
+        tracing::info!(
+            "Images {:?} completed: model={}, images={}, cost=${:.4}",
+            request.operation,
+            request.model,
+            response.data.len(),
+            cost_usd
+        );
+        self.log_usage(project_id, &request, cost_usd, response_time_ms);
+
+        response.x_llmhub = Some(ImagesMetadata {
+            cost: cost_usd,
+            latency_ms: response_time_ms,
+        });
+        Ok(response)
+    }
+
+    /// Apply the project's `max_concurrent_requests` and `requests_per_minute`
+    fn reserve_slot(&self, project_id: &str, rate_limits: &RateLimits) -> Result<RequestSlot, AppError> {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let project = usage.entry(project_id.to_string()).or_default();
+
+        let now = Instant::now();
+        while project
+            .started
+            .front()
+            .is_some_and(|started| now.duration_since(*started) >= RATE_WINDOW)
+        {
+            project.started.pop_front();
+        }
+
+        if rate_limits.max_concurrent_requests > 0
+            && project.in_flight >= rate_limits.max_concurrent_requests
+        {
+            return Err(AppError::RateLimitError(format!(
+                "Project already has {} image requests in progress",
+                project.in_flight
+            )));
+        }
+        if rate_limits.requests_per_minute > 0
+            && project.started.len() >= rate_limits.requests_per_minute as usize
+        {
+            return Err(AppError::RateLimitError(format!(
+                "Project exceeded {} image requests per minute",
+                rate_limits.requests_per_minute
+            )));
+        }
+
+        project.in_flight += 1;
+        project.started.push_back(now);
+
+        Ok(RequestSlot {
+            project_id: project_id.to_string(),
+            usage: self.usage.clone(),
+        })
+    }
+
+    /// Log image usage, charged per generated image
+    fn log_usage(
+        &self,
+        project_id: String,
+        request: &ImageRequest,
+        cost_usd: f64,
+        response_time_ms: u64,
+    ) {
+        let endpoint = match request.operation {
+            ImageOperation::Generation => ApiEndpoint::ImageGeneration,
+            ImageOperation::Edit => ApiEndpoint::ImageEdit,
+            ImageOperation::Variation => ApiEndpoint::ImageVariation,
+        };
+        let upload_bytes: usize = request
+            .images
+            .iter()
+            .chain(request.mask.iter())
+            .map(|image| image.data.len())
+            .sum();
+
+        let log = UsageLog::new(
+            project_id,
+            endpoint,
+            LlmProvider::Openai,
+            request.model.clone(),
+            RequestMetadata {
+                request_id: uuid::Uuid::new_v4().to_string(),
+                method: "POST".to_string(),
+                path: format!("/v1/{}", request.operation.path()),
+                ip_address: None,
+                user_agent: None,
+                prompt_tokens: None,
+                audio_duration_seconds: None,
+                file_size_bytes: (upload_bytes > 0).then_some(upload_bytes as i64),
+                temperature: None,
+                max_tokens: None,
+                stream: false,
+            },
+            ResponseMetadata {
+                status_code: 200,
+                latency_ms: response_time_ms,
+                provider_latency_ms: Some(response_time_ms),
+                completion_tokens: None,
+                total_tokens: None,
+                finish_reason: None,
+            },
+            CostData {
+                prompt_cost_usd: None,
+                completion_cost_usd: None,
+                audio_cost_usd: None,
+                total_cost_usd: cost_usd,
+                cached_savings_usd: None,
+            },
+            None,
+            None,
+        );
+
+        // Log in background
+        let repo = self.usage_repository.clone();
+        tokio::spawn(async move {
+            if let Err(e) = repo.create(&log).await {
+                tracing::error!("Failed to log image usage: {}", e);
+            }
+        });
+    }
+}
+
+/// Check the request has what its operation needs before reserving a slot
+fn validate(request: &ImageRequest) -> Result<(), AppError> {
+    match request.operation {
+        ImageOperation::Generation => {}
+        ImageOperation::Edit => {
+            if request.prompt.as_deref().is_none_or(|p| p.trim().is_empty()) {
+                return Err(AppError::ValidationError("prompt is required".to_string()));
+            }
+            if request.images.is_empty() {
+                return Err(AppError::ValidationError("image is required".to_string()));
+            }
+        }
+        ImageOperation::Variation => {
+            if request.images.len() != 1 {
+                return Err(AppError::ValidationError("exactly one image is required".to_string()));
+            }
+        }
+    }
+
+    for image in request.images.iter().chain(request.mask.iter()) {
+        if image_content_type(&image.data).is_none() {
+            return Err(AppError::UnsupportedMediaType(format!(
+                "{} is not a PNG, JPEG or WebP image",
+                image.file_name
+            )));
+        }
+    }
+
+    Ok(())
+}
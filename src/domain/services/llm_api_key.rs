@@ -27,7 +27,31 @@ impl LlmApiKeyService {
     pub async fn get_decrypted_key(&self, key_id: &str) -> Result<String, AppError> {
         // Get from database
         let llm_key = self.repository.find_by_id(key_id).await?;
+        self.decrypt(&llm_key)
+    }
+
+    /// Get a decrypted LLM API key the client chose by ID. The key must belong
+    /// to the project and, when given, be for `provider`; it is checked before
+    /// anything is decrypted.
+    pub async fn get_project_key(
+        &self,
+        project_id: &str,
+        key_id: &str,
+        provider: Option<&LlmProvider>,
+    ) -> Result<String, AppError> {
+        // Keys of other projects are reported as missing, like unknown IDs
+        let llm_key = self.repository.find_by_project(project_id, key_id).await?;
+        if let Some(provider) = provider.filter(|p| **p != llm_key.provider) {
+            return Err(AppError::BadRequest(format!(
+                "LLM API key {} is for {:?}, not {:?}",
+                key_id, llm_key.provider, provider
+            )));
+        }
+
+        self.decrypt(&llm_key)
+    }
 
+    fn decrypt(&self, llm_key: &LlmApiKey) -> Result<String, AppError> {
         if !llm_key.is_active {
             return Err(AppError::AuthorizationError(
                 "LLM API key is inactive".to_string(),
@@ -39,7 +63,7 @@ impl LlmApiKeyService {
 
         // Mark as used (fire and forget)
         let repo = self.repository.clone();
-        let key_id = key_id.to_string();
+        let key_id = llm_key.key_id.clone();
         tokio::spawn(async move {
             if let Err(e) = repo.mark_used(&key_id).await {
                 tracing::warn!("Failed to mark LLM API key as used: {}", e);
@@ -109,8 +133,6 @@ impl LlmApiKeyService {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     // Tests would require mock repository and Redis
     // Omitted for brevity
 }
\ No newline at end of file
@@ -0,0 +1,536 @@
+//! Translation between the Anthropic Messages API and the gateway's chat
+//! completions model, so `/v1/messages` reaches every chat backend.
+
+use std::collections::HashMap;
+
+use tracing::warn;
+
+use crate::api::dto::{
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatContent, ChatMessage,
+    ChatRole, ChatTool, ContentBlock, ContentBlockDelta, ContentPart, FinishReason, FunctionCall,
+    FunctionDefinition, ImageSource, ImageUrl, InputMessage, MessageContent, MessageDelta,
+    MessageDeltaUsage, MessageRole, MessageStreamEvent, MessagesRequest, MessagesResponse,
+    MessagesToolChoice, MessagesUsage, NamedToolChoice, StopSequences, StreamOptions, SystemPrompt,
+    ToolCall, ToolChoice, ToolChoiceFunction, ToolChoiceMode, ToolResultContent, ToolType,
+};
+use crate::shared::error::AppError;
+
+/// Build the equivalent chat completion request
+pub fn to_chat_request(request: MessagesRequest) -> Result<ChatCompletionRequest, AppError> {
+    if request.max_tokens == 0 {
+        return Err(AppError::BadRequest("max_tokens must be greater than 0".to_string()));
+    }
+    if request.top_k.is_some() {
+        return Err(AppError::BadRequest(format!(
+            "top_k is not supported by model {}",
+            request.model
+        )));
+    }
+
+    let mut messages = Vec::new();
+    match request.system {
+        Some(SystemPrompt::Text(text)) => messages.push(text_message(ChatRole::System, text)),
+        Some(SystemPrompt::Blocks(blocks)) => {
+            messages.push(text_message(ChatRole::System, block_text(&blocks)))
+        }
+        None => {}
+    }
+    for message in request.messages {
+        push_message(&mut messages, message)?;
+    }
+
+    let mut parallel_tool_calls = None;
+    let tool_choice = request.tool_choice.map(|choice| match choice {
+        MessagesToolChoice::Auto { disable_parallel_tool_use } => {
+            parallel_tool_calls = disable_parallel_tool_use.then_some(false);
+            ToolChoice::Mode(ToolChoiceMode::Auto)
+        }
+        MessagesToolChoice::Any { disable_parallel_tool_use } => {
+            parallel_tool_calls = disable_parallel_tool_use.then_some(false);
+            ToolChoice::Mode(ToolChoiceMode::Required)
+        }
+        MessagesToolChoice::Tool { name, disable_parallel_tool_use } => {
+            parallel_tool_calls = disable_parallel_tool_use.then_some(false);
+            ToolChoice::Function(NamedToolChoice {
+                r#type: ToolType::Function,
+                function: ToolChoiceFunction { name },
+            })
+        }
+        MessagesToolChoice::None => ToolChoice::Mode(ToolChoiceMode::None),
+    });
+
+    let tools = request.tools.map(|tools| {
+        tools
+            .into_iter()
+            .map(|tool| ChatTool {
+                r#type: ToolType::Function,
+                function: FunctionDefinition {
+                    name: tool.name,
+                    description: tool.description,
+                    parameters: Some(tool.input_schema),
+                    strict: None,
+                },
+            })
+            .collect()
+    });
+
+    Ok(ChatCompletionRequest {
+        model: request.model,
+        messages,
+        temperature: request.temperature.unwrap_or(1.0),
+        max_tokens: Some(request.max_tokens),
+        stream: request.stream,
+        top_p: request.top_p.unwrap_or(1.0),
+        frequency_penalty: 0.0,
+        presence_penalty: 0.0,
+        tools,
+        tool_choice,
+        parallel_tool_calls,
+        response_format: None,
+        n: None,
+        stop: request.stop_sequences.map(StopSequences::Multiple),
+        seed: None,
+        logprobs: None,
+        top_logprobs: None,
+        logit_bias: None,
+        user: request.metadata.and_then(|m| m.user_id),
+        max_completion_tokens: None,
+        // Usage is reported on message_delta
+        stream_options: request.stream.then_some(StreamOptions {
+            include_usage: Some(true),
+        }),
+        reasoning_effort: None,
+        truncation: None,
+        extra: Default::default(),
+    })
+}
+
+fn text_message(role: ChatRole, text: String) -> ChatMessage {
+    ChatMessage {
+        role,
+        content: Some(ChatContent::Text(text)),
+        name: None,
+        tool_calls: None,
+        tool_call_id: None,
+        refusal: None,
+    }
+}
+
+/// Text of the text blocks, one paragraph each
+fn block_text(blocks: &[ContentBlock]) -> String {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn push_message(messages: &mut Vec<ChatMessage>, message: InputMessage) -> Result<(), AppError> {
+    let blocks = match message.content {
+        MessageContent::Text(text) => {
+            let role = match message.role {
+                MessageRole::User => ChatRole::User,
+                MessageRole::Assistant => ChatRole::Assistant,
+            };
+            messages.push(text_message(role, text));
+            return Ok(());
+        }
+        MessageContent::Blocks(blocks) => blocks,
+    };
+
+    match message.role {
+        MessageRole::Assistant => {
+            let mut tool_calls = Vec::new();
+            for block in &blocks {
+                if let ContentBlock::ToolUse { id, name, input } = block {
+                    tool_calls.push(ToolCall {
+                        id: id.clone(),
+                        r#type: ToolType::Function,
+                        function: FunctionCall {
+                            name: name.clone(),
+                            arguments: input.to_string(),
+                        },
+                    });
+                }
+            }
+            let text = block_text(&blocks);
+            messages.push(ChatMessage {
+                role: ChatRole::Assistant,
+                content: (!text.is_empty()).then_some(ChatContent::Text(text)),
+                name: None,
+                tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+                tool_call_id: None,
+                refusal: None,
+            });
+        }
+        MessageRole::User => {
+            // Tool results must directly follow the assistant's tool calls
+            let mut parts = Vec::new();
+            for block in blocks {
+                match block {
+                    ContentBlock::ToolResult { tool_use_id, content, is_error } => {
+                        let mut text = match content {
+                            Some(ToolResultContent::Text(text)) => text,
+                            Some(ToolResultContent::Blocks(blocks)) => block_text(&blocks),
+                            None => String::new(),
+                        };
+                        if is_error == Some(true) {
+                            text = format!("Error: {}", text);
+                        }
+                        messages.push(ChatMessage {
+                            role: ChatRole::Tool,
+                            content: Some(ChatContent::Text(text)),
+                            name: None,
+                            tool_calls: None,
+                            tool_call_id: Some(tool_use_id),
+                            refusal: None,
+                        });
+                    }
+                    ContentBlock::Text { text } => parts.push(ContentPart::Text { text }),
+                    ContentBlock::Image { source } => {
+                        let url = match source {
+                            ImageSource::Base64 { media_type, data } => {
+                                format!("data:{};base64,{}", media_type, data)
+                            }
+                            ImageSource::Url { url } => url,
+                        };
+                        parts.push(ContentPart::ImageUrl {
+                            image_url: ImageUrl { url, detail: None },
+                        });
+                    }
+                    ContentBlock::ToolUse { .. } => {
+                        return Err(AppError::BadRequest(
+                            "tool_use blocks are only allowed in assistant messages".to_string(),
+                        ));
+                    }
+                }
+            }
+            if !parts.is_empty() {
+                messages.push(ChatMessage {
+                    role: ChatRole::User,
+                    content: Some(ChatContent::Parts(parts)),
+                    name: None,
+                    tool_calls: None,
+                    tool_call_id: None,
+                    refusal: None,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn message_id(chat_id: &str) -> String {
+    format!("msg_{}", chat_id.trim_start_matches("chatcmpl-"))
+}
+
+fn stop_reason(reason: &FinishReason) -> String {
+    match reason {
+        FinishReason::Stop => "end_turn",
+        FinishReason::Length => "max_tokens",
+        FinishReason::ToolCalls => "tool_use",
+        FinishReason::ContentFilter => "refusal",
+        FinishReason::Other(reason) => reason,
+    }
+    .to_string()
+}
+
+/// Split prompt tokens into uncached input and cache reads, as Anthropic
+/// reports them
+fn input_usage(prompt_tokens: u32, cached_tokens: Option<u32>) -> (u32, Option<u32>) {
+    let cached = cached_tokens.filter(|tokens| *tokens > 0);
+    (prompt_tokens - cached.unwrap_or(0).min(prompt_tokens), cached)
+}
+
+/// Build the Messages response from a chat completion
+pub fn from_chat_response(response: ChatCompletionResponse) -> MessagesResponse {
+    let cached_tokens = response
+        .usage
+        .prompt_tokens_details
+        .as_ref()
+        .and_then(|details| details.cached_tokens);
+    let (input_tokens, cache_read_input_tokens) =
+        input_usage(response.usage.prompt_tokens, cached_tokens);
+
+    let mut content = Vec::new();
+    let mut reason = None;
+    if let Some(choice) = response.choices.into_iter().next() {
+        let message = choice.message;
+        match message.content {
+            Some(ChatContent::Text(text)) if !text.is_empty() => {
+                content.push(ContentBlock::Text { text })
+            }
+            Some(ChatContent::Parts(parts)) => {
+                content.extend(parts.into_iter().filter_map(|part| match part {
+                    ContentPart::Text { text } => Some(ContentBlock::Text { text }),
+                    _ => None,
+                }))
+            }
+            _ => {}
+        }
+        if let Some(refusal) = message.refusal {
+            content.push(ContentBlock::Text { text: refusal });
+            reason = Some("refusal".to_string());
+        }
+        for call in message.tool_calls.into_iter().flatten() {
+            content.push(ContentBlock::ToolUse {
+                input: tool_input(&call.function.arguments),
+                id: call.id,
+                name: call.function.name,
+            });
+        }
+        reason = reason.or(choice.finish_reason.as_ref().map(stop_reason));
+    }
+
+    MessagesResponse {
+        id: message_id(&response.id),
+        r#type: "message".to_string(),
+        role: MessageRole::Assistant,
+        content,
+        model: response.model,
+        stop_reason: reason,
+        stop_sequence: None,
+        usage: MessagesUsage {
+            input_tokens,
+            output_tokens: response.usage.completion_tokens,
+            cache_read_input_tokens,
+        },
+    }
+}
+
+/// Tool arguments as the JSON object Anthropic clients expect
+fn tool_input(arguments: &str) -> serde_json::Value {
+    serde_json::from_str(arguments).unwrap_or_else(|e| {
+        warn!("Tool call arguments are not valid JSON: {}", e);
+        serde_json::Value::Object(Default::default())
+    })
+}
+
+/// Converts a chat completion chunk stream into Messages stream events
+#[derive(Default)]
+pub struct MessageStream {
+    started: bool,
+    /// Content block currently open, and whether it holds text
+    open_block: Option<(u32, bool)>,
+    next_index: u32,
+    /// Content block index per upstream tool call index
+    tool_blocks: HashMap<u32, u32>,
+    stop_reason: Option<String>,
+    usage: Option<(u32, u32, Option<u32>)>,
+}
+
+impl MessageStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Events for one upstream chunk
+    pub fn push(&mut self, chunk: ChatCompletionChunk) -> Vec<MessageStreamEvent> {
+        let mut events = Vec::new();
+
+        if !self.started {
+            self.started = true;
+            events.push(MessageStreamEvent::MessageStart {
+                message: MessagesResponse {
+                    id: message_id(&chunk.id),
+                    r#type: "message".to_string(),
+                    role: MessageRole::Assistant,
+                    content: Vec::new(),
+                    model: chunk.model.clone(),
+                    stop_reason: None,
+                    stop_sequence: None,
+                    usage: MessagesUsage {
+                        input_tokens: 0,
+                        output_tokens: 0,
+                        cache_read_input_tokens: None,
+                    },
+                },
+            });
+        }
+
+        if let Some(usage) = &chunk.usage {
+            let cached_tokens = usage
+                .prompt_tokens_details
+                .as_ref()
+                .and_then(|details| details.cached_tokens);
+            let (input_tokens, cache_read) = input_usage(usage.prompt_tokens, cached_tokens);
+            self.usage = Some((input_tokens, usage.completion_tokens, cache_read));
+        }
+
+        // Only the first choice is relayed; Messages has no `n`
+        let Some(choice) = chunk.choices.into_iter().find(|c| c.index == 0) else {
+            return events;
+        };
+
+        let text = [choice.delta.content, choice.delta.refusal]
+            .into_iter()
+            .flatten()
+            .filter(|text| !text.is_empty());
+        for text in text {
+            if !matches!(self.open_block, Some((_, true))) {
+                let index = self.open(&mut events, true);
+                events.push(MessageStreamEvent::ContentBlockStart {
+                    index,
+                    content_block: ContentBlock::Text { text: String::new() },
+                });
+            }
+            let index = self.open_block.map(|(index, _)| index).unwrap_or_default();
+            events.push(MessageStreamEvent::ContentBlockDelta {
+                index,
+                delta: ContentBlockDelta::TextDelta { text },
+            });
+        }
+
+        for call in choice.delta.tool_calls.into_iter().flatten() {
+            let index = match self.tool_blocks.get(&call.index) {
+                Some(index) => *index,
+                None => {
+                    let index = self.open(&mut events, false);
+                    self.tool_blocks.insert(call.index, index);
+                    let function = call.function.as_ref();
+                    events.push(MessageStreamEvent::ContentBlockStart {
+                        index,
+                        content_block: ContentBlock::ToolUse {
+                            id: call.id.clone().unwrap_or_default(),
+                            name: function.and_then(|f| f.name.clone()).unwrap_or_default(),
+                            input: serde_json::Value::Object(Default::default()),
+                        },
+                    });
+                    index
+                }
+            };
+            if let Some(arguments) = call.function.and_then(|f| f.arguments) {
+                if !arguments.is_empty() {
+                    events.push(MessageStreamEvent::ContentBlockDelta {
+                        index,
+                        delta: ContentBlockDelta::InputJsonDelta { partial_json: arguments },
+                    });
+                }
+            }
+        }
+
+        if let Some(reason) = &choice.finish_reason {
+            self.stop_reason = Some(stop_reason(reason));
+        }
+
+        events
+    }
+
+    /// Closing events once the upstream stream has ended
+    pub fn finish(&mut self) -> Vec<MessageStreamEvent> {
+        let mut events = Vec::new();
+        if let Some((index, _)) = self.open_block.take() {
+            events.push(MessageStreamEvent::ContentBlockStop { index });
+        }
+
+        let (input_tokens, output_tokens, cache_read_input_tokens) = match self.usage {
+            Some((input, output, cached)) => (Some(input), output, cached),
+            None => (None, 0, None),
+        };
+        events.push(MessageStreamEvent::MessageDelta {
+            delta: MessageDelta {
+                stop_reason: self.stop_reason.take().or_else(|| Some("end_turn".to_string())),
+                stop_sequence: None,
+            },
+            usage: MessageDeltaUsage {
+                output_tokens,
+                input_tokens,
+                cache_read_input_tokens,
+            },
+        });
+        events.push(MessageStreamEvent::MessageStop);
+        events
+    }
+
+    /// Close the open block and open the next index
+    fn open(&mut self, events: &mut Vec<MessageStreamEvent>, text: bool) -> u32 {
+        if let Some((index, _)) = self.open_block.take() {
+            events.push(MessageStreamEvent::ContentBlockStop { index });
+        }
+        let index = self.next_index;
+        self.next_index += 1;
+        self.open_block = Some((index, text));
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn translates_tool_use_round_trip() {
+        let request: MessagesRequest = serde_json::from_value(json!({
+            "model": "gpt-4o",
+            "max_tokens": 256,
+            "system": [{ "type": "text", "text": "Be brief." }],
+            "tools": [{ "name": "get_weather", "input_schema": { "type": "object" } }],
+            "messages": [
+                { "role": "user", "content": "Weather in Paris?" },
+                { "role": "assistant", "content": [
+                    { "type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": { "city": "Paris" } }
+                ]},
+                { "role": "user", "content": [
+                    { "type": "tool_result", "tool_use_id": "toolu_1", "content": "18C" },
+                    { "type": "text", "text": "Thanks" }
+                ]}
+            ]
+        }))
+        .unwrap();
+
+        let chat = to_chat_request(request).unwrap();
+        assert!(chat.validate().is_ok());
+        let roles: Vec<_> = chat.messages.iter().map(|m| serde_json::to_value(&m.role).unwrap()).collect();
+        assert_eq!(roles, ["system", "user", "assistant", "tool", "user"]);
+        assert_eq!(chat.messages[2].tool_calls.as_ref().unwrap()[0].function.arguments, r#"{"city":"Paris"}"#);
+        assert_eq!(chat.messages[3].tool_call_id.as_deref(), Some("toolu_1"));
+    }
+
+    #[test]
+    fn streams_blocks_in_order() {
+        let chunk = |delta: serde_json::Value, finish: Option<&str>| -> ChatCompletionChunk {
+            serde_json::from_value(json!({
+                "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4o",
+                "choices": [{ "index": 0, "delta": delta, "finish_reason": finish }]
+            }))
+            .unwrap()
+        };
+
+        let mut stream = MessageStream::new();
+        let mut events = stream.push(chunk(json!({ "role": "assistant", "content": "Checking" }), None));
+        events.extend(stream.push(chunk(json!({ "tool_calls": [{
+            "index": 0, "id": "call_1", "type": "function",
+            "function": { "name": "get_weather", "arguments": "{\"city\"" }
+        }]}), None)));
+        events.extend(stream.push(chunk(json!({ "tool_calls": [{
+            "index": 0, "function": { "arguments": ":\"Paris\"}" }
+        }]}), Some("tool_calls"))));
+        events.extend(stream.finish());
+
+        let types: Vec<_> = events.iter().map(|e| e.event_type()).collect();
+        assert_eq!(
+            types,
+            [
+                "message_start",
+                "content_block_start",
+                "content_block_delta",
+                "content_block_stop",
+                "content_block_start",
+                "content_block_delta",
+                "content_block_delta",
+                "content_block_stop",
+                "message_delta",
+                "message_stop"
+            ]
+        );
+        assert!(matches!(
+            &events[8],
+            MessageStreamEvent::MessageDelta { delta, .. } if delta.stop_reason.as_deref() == Some("tool_use")
+        ));
+    }
+}
@@ -1,6 +1,34 @@
+pub mod audio;
+pub mod batch;
+pub mod chat_guardrails;
+pub mod chat_usage;
+pub mod context_window;
+pub mod gemini;
+pub mod images;
+pub mod injection;
 pub mod llm_api_key;
+pub mod messages;
+pub mod moderation;
+pub mod pii;
 pub mod providers;
+pub mod realtime;
+pub mod responses;
+pub mod speech;
+pub mod structured_output;
+pub mod subtitles;
+pub mod token_rate_limiter;
+pub mod tokenizer;
 pub mod transcription;
+pub mod transcription_job;
+pub mod vision;
 
+pub use batch::BatchService;
+pub use chat_guardrails::ChatGuardrails;
+pub use images::ImageService;
 pub use llm_api_key::LlmApiKeyService;
-pub use transcription::TranscriptionService;
\ No newline at end of file
+pub use moderation::ModerationService;
+pub use realtime::RealtimeService;
+pub use speech::SpeechService;
+pub use token_rate_limiter::TokenRateLimiter;
+pub use transcription::TranscriptionService;
+pub use transcription_job::TranscriptionJobService;
\ No newline at end of file
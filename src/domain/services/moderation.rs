@@ -0,0 +1,332 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::api::dto::{
+    ChatChoice, ChatChoiceChunk, ChatCompletionChunk, ChatCompletionRequest,
+    ChatCompletionResponse, ChatContent, ChatDelta, ChatMessage, ChatRole, ChatUsage, ContentPart,
+    FinishReason, ModerationInput, ModerationRequest, ModerationResponse, ModerationResult,
+};
+use crate::domain::entities::guardrail::ModerationPolicy;
+use crate::domain::entities::usage::{
+    ApiEndpoint, CostData, RequestMetadata, ResponseMetadata, UsageLog,
+};
+use crate::domain::entities::LlmProvider;
+use crate::domain::repositories::usage_repository::UsageRepository;
+use crate::domain::services::llm_api_key::LlmApiKeyService;
+use crate::domain::services::providers::OpenAIProvider;
+use crate::shared::error::AppError;
+
+/// Moderation model used when a policy or request names none
+pub const DEFAULT_MODERATION_MODEL: &str = "omni-moderation-latest";
+
+/// Moderation service backing `/v1/moderations` and the per-project
+/// moderation hooks around chat dispatch
+pub struct ModerationService {
+    usage_repository: Arc<dyn UsageRepository>,
+    llm_key_service: Arc<LlmApiKeyService>,
+    openai_provider: OpenAIProvider,
+}
+
+impl ModerationService {
+    pub fn new(usage_repository: Arc<dyn UsageRepository>, llm_key_service: Arc<LlmApiKeyService>) -> Self {
+        Self {
+            usage_repository,
+            llm_key_service,
+            openai_provider: OpenAIProvider::new(),
+        }
+    }
+
+    /// Classify content with the project's OpenAI key
+    pub async fn moderate(
+        &self,
+        project_id: String,
+        mut request: ModerationRequest,
+    ) -> Result<ModerationResponse, AppError> {
+        let start_time = Instant::now();
+
+        let provider = LlmProvider::Openai;
+        let api_key = if let Some(key_id) = &request.llm_api_key_id {
+            self.llm_key_service
+                .get_project_key(&project_id, key_id, Some(&provider))
+                .await?
+        } else {
+            self.llm_key_service
+                .get_default_key_for_provider(&project_id, &provider)
+                .await?
+                .ok_or_else(|| {
+                    AppError::ConfigError(format!(
+                        "No LLM API key configured for provider: {:?}",
+                        provider
+                    ))
+                })?
+        };
+
+        request.model.get_or_insert_with(|| DEFAULT_MODERATION_MODEL.to_string());
+        let response = self.openai_provider.moderations(&api_key, &request).await?;
+        self.log_usage(project_id, &response.model, start_time.elapsed().as_millis() as u64);
+
+        Ok(response)
+    }
+
+    /// Moderate texts under a policy, returning the categories that block
+    pub async fn check(
+        &self,
+        api_key: &str,
+        policy: &ModerationPolicy,
+        texts: Vec<String>,
+    ) -> Result<Vec<String>, AppError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let request = ModerationRequest {
+            input: ModerationInput::Texts(texts),
+            model: Some(policy.model.clone().unwrap_or_else(|| DEFAULT_MODERATION_MODEL.to_string())),
+            llm_api_key_id: None,
+        };
+        let response = self.openai_provider.moderations(api_key, &request).await?;
+
+        Ok(violations(policy, &response.results))
+    }
+
+    /// Log moderation usage; moderation is free upstream
+    fn log_usage(&self, project_id: String, model: &str, response_time_ms: u64) {
+        let log = UsageLog::new(
+            project_id,
+            ApiEndpoint::Moderations,
+            LlmProvider::Openai,
+            model.to_string(),
+            RequestMetadata {
+                request_id: uuid::Uuid::new_v4().to_string(),
+                method: "POST".to_string(),
+                path: "/v1/moderations".to_string(),
+                ip_address: None,
+                user_agent: None,
+                prompt_tokens: None,
+                audio_duration_seconds: None,
+                file_size_bytes: None,
+                temperature: None,
+                max_tokens: None,
+                stream: false,
+            },
+            ResponseMetadata {
+                status_code: 200,
+                latency_ms: response_time_ms,
+                provider_latency_ms: Some(response_time_ms),
+                completion_tokens: None,
+                total_tokens: None,
+                finish_reason: None,
+            },
+            CostData {
+                prompt_cost_usd: None,
+                completion_cost_usd: None,
+                audio_cost_usd: None,
+                total_cost_usd: 0.0,
+                cached_savings_usd: None,
+            },
+            None,
+            None,
+        );
+
+        // Log in background
+        let repo = self.usage_repository.clone();
+        tokio::spawn(async move {
+            if let Err(e) = repo.create(&log).await {
+                tracing::error!("Failed to log moderation usage: {}", e);
+            }
+        });
+    }
+}
+
+/// Categories that block under the policy: flagged ones, or those scoring at
+/// or above a configured threshold, limited to the policy's categories
+pub fn violations(policy: &ModerationPolicy, results: &[ModerationResult]) -> Vec<String> {
+    let mut blocked = BTreeSet::new();
+    for result in results {
+        for (category, flagged) in &result.categories {
+            let blocks = match policy.thresholds.get(category) {
+                Some(threshold) => result
+                    .category_scores
+                    .get(category)
+                    .is_some_and(|score| score >= threshold),
+                None => *flagged,
+            };
+            if blocks && (policy.categories.is_empty() || policy.categories.contains(category)) {
+                blocked.insert(category.clone());
+            }
+        }
+    }
+    blocked.into_iter().collect()
+}
+
+/// Text a user or tool supplied in the request
+pub fn request_texts(request: &ChatCompletionRequest) -> Vec<String> {
+    request
+        .messages
+        .iter()
+        .filter(|m| matches!(m.role, ChatRole::User | ChatRole::Tool))
+        .filter_map(|m| content_text(m.content.as_ref()))
+        .collect()
+}
+
+/// Text of each choice in a reply
+pub fn response_texts(response: &ChatCompletionResponse) -> Vec<String> {
+    response
+        .choices
+        .iter()
+        .filter_map(|c| content_text(c.message.content.as_ref()))
+        .collect()
+}
+
+/// Text of each choice in a streamed reply
+pub fn chunk_texts<'a>(chunks: impl IntoIterator<Item = &'a ChatCompletionChunk>) -> Vec<String> {
+    let mut texts: BTreeMap<u32, String> = BTreeMap::new();
+    for choice in chunks.into_iter().flat_map(|c| &c.choices) {
+        if let Some(content) = &choice.delta.content {
+            texts.entry(choice.index).or_default().push_str(content);
+        }
+    }
+    texts.into_values().filter(|t| !t.trim().is_empty()).collect()
+}
+
+fn content_text(content: Option<&ChatContent>) -> Option<String> {
+    let text = match content? {
+        ChatContent::Text(text) => text.clone(),
+        ChatContent::Parts(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+/// Empty reply returned in place of a blocked request
+pub fn filtered_response(request: &ChatCompletionRequest) -> ChatCompletionResponse {
+    ChatCompletionResponse {
+        id: format!("chatcmpl-{}", uuid::Uuid::new_v4().simple()),
+        object: "chat.completion".to_string(),
+        created: chrono::Utc::now().timestamp(),
+        model: request.model.clone(),
+        choices: vec![ChatChoice {
+            index: 0,
+            message: ChatMessage {
+                role: ChatRole::Assistant,
+                content: None,
+                name: None,
+                tool_calls: None,
+                tool_call_id: None,
+                refusal: None,
+            },
+            finish_reason: Some(FinishReason::ContentFilter),
+            logprobs: None,
+        }],
+        usage: ChatUsage {
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            total_tokens: 0,
+            prompt_tokens_details: None,
+            completion_tokens_details: None,
+        },
+        system_fingerprint: None,
+        service_tier: None,
+        x_llmhub: None,
+    }
+}
+
+/// Withhold a blocked reply, keeping its usage
+pub fn filter_response(response: &mut ChatCompletionResponse) {
+    for choice in &mut response.choices {
+        choice.message.content = None;
+        choice.message.tool_calls = None;
+        choice.message.refusal = None;
+        choice.logprobs = None;
+        choice.finish_reason = Some(FinishReason::ContentFilter);
+    }
+}
+
+/// Streamed form of [`filtered_response`]
+pub fn filtered_chunk(request: &ChatCompletionRequest) -> ChatCompletionChunk {
+    ChatCompletionChunk {
+        id: format!("chatcmpl-{}", uuid::Uuid::new_v4().simple()),
+        object: "chat.completion.chunk".to_string(),
+        created: chrono::Utc::now().timestamp(),
+        model: request.model.clone(),
+        choices: vec![ChatChoiceChunk {
+            index: 0,
+            delta: ChatDelta {
+                role: Some(ChatRole::Assistant),
+                content: None,
+                tool_calls: None,
+                refusal: None,
+            },
+            finish_reason: Some(FinishReason::ContentFilter),
+            logprobs: None,
+        }],
+        system_fingerprint: None,
+        service_tier: None,
+        usage: None,
+    }
+}
+
+/// Withhold a blocked streamed reply, keeping role, finish and usage chunks
+pub fn filter_chunks(chunks: Vec<ChatCompletionChunk>) -> Vec<ChatCompletionChunk> {
+    chunks
+        .into_iter()
+        .filter_map(|mut chunk| {
+            for choice in &mut chunk.choices {
+                choice.delta.content = None;
+                choice.delta.tool_calls = None;
+                choice.delta.refusal = None;
+                choice.logprobs = None;
+                if choice.finish_reason.is_some() {
+                    choice.finish_reason = Some(FinishReason::ContentFilter);
+                }
+            }
+            chunk
+                .choices
+                .retain(|c| c.delta.role.is_some() || c.finish_reason.is_some());
+            (!chunk.choices.is_empty() || chunk.usage.is_some()).then_some(chunk)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::guardrail::ModerationAction;
+
+    fn result(flags: &[(&str, bool, f64)]) -> ModerationResult {
+        ModerationResult {
+            flagged: flags.iter().any(|(_, flagged, _)| *flagged),
+            categories: flags.iter().map(|(c, f, _)| (c.to_string(), *f)).collect(),
+            category_scores: flags.iter().map(|(c, _, s)| (c.to_string(), *s)).collect(),
+            category_applied_input_types: None,
+        }
+    }
+
+    #[test]
+    fn applies_policy_categories_and_thresholds() {
+        let mut policy = ModerationPolicy {
+            input: true,
+            output: false,
+            action: ModerationAction::Reject,
+            model: None,
+            categories: Vec::new(),
+            thresholds: Default::default(),
+        };
+        let results = [result(&[("violence", true, 0.9), ("harassment", false, 0.3)])];
+        assert_eq!(violations(&policy, &results), vec!["violence"]);
+
+        // Thresholds override the provider's flag in both directions
+        policy.thresholds = [("violence".to_string(), 0.95), ("harassment".to_string(), 0.2)].into();
+        assert_eq!(violations(&policy, &results), vec!["harassment"]);
+
+        policy.categories = vec!["violence".to_string()];
+        assert!(violations(&policy, &results).is_empty());
+    }
+}
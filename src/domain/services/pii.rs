@@ -0,0 +1,572 @@
+//! PII guardrail: detects personal data in chat and transcription traffic and
+//! blocks, redacts or tokenises it before it reaches a provider.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, OnceLock};
+
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use regex::Regex;
+
+use crate::api::dto::{
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatContent, ContentPart,
+    FunctionCallDelta, ToolCallDelta,
+};
+use crate::domain::entities::guardrail::{PiiEntity, PiiMode, PiiPolicy};
+use crate::domain::entities::transcription::{TranscriptionResponse, TranscriptionWord};
+use crate::shared::error::AppError;
+
+/// Detection order; checksummed detectors go first so that card numbers and
+/// IBANs aren't taken for phone numbers
+const ALL_ENTITIES: [PiiEntity; 5] = [
+    PiiEntity::Iban,
+    PiiEntity::CreditCard,
+    PiiEntity::NationalId,
+    PiiEntity::Email,
+    PiiEntity::Phone,
+];
+
+impl PiiEntity {
+    fn label(&self) -> &'static str {
+        match self {
+            PiiEntity::Email => "EMAIL",
+            PiiEntity::Phone => "PHONE",
+            PiiEntity::CreditCard => "CREDIT_CARD",
+            PiiEntity::Iban => "IBAN",
+            PiiEntity::NationalId => "NATIONAL_ID",
+        }
+    }
+
+    fn regex(&self) -> &'static Regex {
+        static PATTERNS: OnceLock<HashMap<PiiEntity, Regex>> = OnceLock::new();
+        let patterns = PATTERNS.get_or_init(|| {
+            [
+                (PiiEntity::Email, r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}"),
+                (
+                    PiiEntity::Phone,
+                    r"(?:\+\d{1,3}[\s.-]?|\b)(?:\(\d{1,4}\)[\s.-]?)?\d{2,4}(?:[\s.-]?\d{2,4}){2,4}\b",
+                ),
+                (PiiEntity::CreditCard, r"\b\d(?:[ -]?\d){12,18}\b"),
+                (PiiEntity::Iban, r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,3})?\b"),
+                (
+                    PiiEntity::NationalId,
+                    r"\b(?:\d{3}-\d{2}-\d{4}|[A-CEGHJ-PR-TW-Z]{2} ?\d{2} ?\d{2} ?\d{2} ?[A-D])\b",
+                ),
+            ]
+            .into_iter()
+            .map(|(entity, pattern)| (entity, Regex::new(pattern).expect("valid PII pattern")))
+            .collect()
+        });
+        &patterns[self]
+    }
+
+    /// Checksum and range checks that weed out look-alike numbers
+    fn is_valid(&self, value: &str) -> bool {
+        let digits: Vec<u32> = value.chars().filter_map(|c| c.to_digit(10)).collect();
+        match self {
+            PiiEntity::Email => true,
+            PiiEntity::Phone => (10..=15).contains(&digits.len()),
+            PiiEntity::CreditCard => (13..=19).contains(&digits.len()) && luhn(&digits),
+            PiiEntity::Iban => iban_checksum(value),
+            PiiEntity::NationalId => !value.contains('-') || valid_ssn(&digits),
+        }
+    }
+}
+
+fn luhn(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match (i % 2 == 1, d * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => d,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// ISO 13616 mod-97 check
+fn iban_checksum(value: &str) -> bool {
+    let compact: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+    if !(15..=34).contains(&compact.len()) {
+        return false;
+    }
+    let rearranged = compact[4..].chars().chain(compact[..4].chars());
+    let mut remainder = 0u64;
+    for c in rearranged {
+        let Some(value) = c.to_digit(36) else {
+            return false;
+        };
+        remainder = if value < 10 {
+            (remainder * 10 + value as u64) % 97
+        } else {
+            (remainder * 100 + value as u64) % 97
+        };
+    }
+    remainder == 1
+}
+
+fn valid_ssn(digits: &[u32]) -> bool {
+    let area = digits[0] * 100 + digits[1] * 10 + digits[2];
+    area != 0 && area != 666 && area < 900 && digits[3..5] != [0, 0] && digits[5..] != [0, 0, 0, 0]
+}
+
+/// A detected value and its placeholder name
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiiMatch {
+    pub label: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Detectors compiled for a project's policy
+pub struct PiiScanner {
+    mode: PiiMode,
+    entities: Vec<PiiEntity>,
+    custom: Vec<(String, Regex)>,
+}
+
+impl PiiScanner {
+    pub fn new(policy: &PiiPolicy) -> Result<Self, AppError> {
+        let entities = if policy.entities.is_empty() {
+            ALL_ENTITIES.to_vec()
+        } else {
+            ALL_ENTITIES
+                .into_iter()
+                .filter(|e| policy.entities.contains(e))
+                .collect()
+        };
+        let custom = policy
+            .custom_patterns
+            .iter()
+            .map(|custom| {
+                let regex = Regex::new(&custom.pattern).map_err(|e| {
+                    AppError::ConfigError(format!("Invalid PII pattern {}: {}", custom.name, e))
+                })?;
+                Ok((custom.name.to_uppercase(), regex))
+            })
+            .collect::<Result<_, AppError>>()?;
+
+        Ok(Self {
+            mode: policy.mode,
+            entities,
+            custom,
+        })
+    }
+
+    /// Non-overlapping matches in text order; earlier detectors win overlaps
+    pub fn find(&self, text: &str) -> Vec<PiiMatch> {
+        let mut matches: Vec<PiiMatch> = Vec::new();
+        let builtin = self.entities.iter().flat_map(|entity| {
+            entity
+                .regex()
+                .find_iter(text)
+                .filter(|m| entity.is_valid(m.as_str()))
+                .map(|m| (entity.label().to_string(), m))
+        });
+        let custom = self
+            .custom
+            .iter()
+            .flat_map(|(label, regex)| regex.find_iter(text).map(|m| (label.clone(), m)));
+
+        for (label, m) in builtin.chain(custom) {
+            if m.is_empty() || matches.iter().any(|p| m.start() < p.end && p.start < m.end()) {
+                continue;
+            }
+            matches.push(PiiMatch {
+                label,
+                start: m.start(),
+                end: m.end(),
+            });
+        }
+        matches.sort_by_key(|m| m.start);
+        matches
+    }
+
+    /// Replace each match with `[LABEL]`
+    pub fn redact(&self, text: &str) -> String {
+        self.replace(text, |m, _| format!("[{}]", m.label))
+    }
+
+    /// Replace each match with a numbered placeholder kept in the vault
+    pub fn tokenize(&self, text: &str, vault: &mut PiiVault) -> String {
+        self.replace(text, |m, value| vault.placeholder(&m.label, value))
+    }
+
+    fn replace(&self, text: &str, mut placeholder: impl FnMut(&PiiMatch, &str) -> String) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for m in self.find(text) {
+            out.push_str(&text[last..m.start]);
+            out.push_str(&placeholder(&m, &text[m.start..m.end]));
+            last = m.end;
+        }
+        out.push_str(&text[last..]);
+        out
+    }
+
+    /// Apply the policy to one piece of text, recording labels found
+    fn apply(&self, text: &mut String, vault: &mut PiiVault, found: &mut BTreeSet<String>) {
+        let matches = self.find(text);
+        if matches.is_empty() {
+            return;
+        }
+        found.extend(matches.iter().map(|m| m.label.to_lowercase()));
+        *text = match self.mode {
+            PiiMode::Block => return,
+            PiiMode::Redact => self.redact(text),
+            PiiMode::Tokenize => self.tokenize(text, vault),
+        };
+    }
+
+    /// Scrub every message of a chat request. Blocks with
+    /// `ContentPolicyViolation`; the vault holds tokenised values to restore.
+    pub fn scrub_request(&self, request: &mut ChatCompletionRequest) -> Result<PiiVault, AppError> {
+        let mut vault = PiiVault::default();
+        let mut found = BTreeSet::new();
+        for message in &mut request.messages {
+            match &mut message.content {
+                Some(ChatContent::Text(text)) => self.apply(text, &mut vault, &mut found),
+                Some(ChatContent::Parts(parts)) => {
+                    for part in parts {
+                        if let ContentPart::Text { text } = part {
+                            self.apply(text, &mut vault, &mut found);
+                        }
+                    }
+                }
+                None => {}
+            }
+            for call in message.tool_calls.iter_mut().flatten() {
+                self.apply(&mut call.function.arguments, &mut vault, &mut found);
+            }
+        }
+        self.check_blocked(found, "request")?;
+        Ok(vault)
+    }
+
+    /// Scrub a transcription prompt; nothing comes back to restore from
+    /// audio, so tokenisation redacts
+    pub fn scrub_prompt(&self, prompt: &mut String) -> Result<(), AppError> {
+        let matches = self.find(prompt);
+        self.check_blocked(matches.iter().map(|m| m.label.to_lowercase()).collect(), "prompt")?;
+        if !matches.is_empty() {
+            *prompt = self.redact(prompt);
+        }
+        Ok(())
+    }
+
+    /// Redact a transcript's text, segments and words. Words are scanned as
+    /// one text so that values spoken as several words (phone numbers, IBANs)
+    /// are found; the words a value spans become one redacted word.
+    pub fn redact_transcript(&self, response: &mut TranscriptionResponse) {
+        response.text = self.redact(&response.text);
+        for segment in response.segments.iter_mut().flatten() {
+            segment.text = self.redact(&segment.text);
+        }
+        if let Some(words) = response.words.take() {
+            response.words = Some(self.redact_words(words));
+        }
+    }
+
+    fn redact_words(&self, words: Vec<TranscriptionWord>) -> Vec<TranscriptionWord> {
+        let mut text = String::new();
+        let mut spans = Vec::with_capacity(words.len());
+        for word in &words {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            let start = text.len();
+            text.push_str(word.word.trim());
+            spans.push((start, text.len()));
+        }
+
+        // Runs of words (first, last) overlapped by a match, merged when they touch
+        let mut runs: Vec<(usize, usize)> = Vec::new();
+        for m in self.find(&text) {
+            let mut overlapping = spans
+                .iter()
+                .enumerate()
+                .filter(|(_, (start, end))| *start < m.end && *end > m.start)
+                .map(|(index, _)| index);
+            let Some(first) = overlapping.next() else {
+                continue;
+            };
+            let last = overlapping.next_back().unwrap_or(first);
+            match runs.last_mut() {
+                Some(run) if first <= run.1 => run.1 = run.1.max(last),
+                _ => runs.push((first, last)),
+            }
+        }
+
+        let mut redacted = Vec::with_capacity(words.len());
+        let mut runs = runs.into_iter().peekable();
+        let mut words = words.into_iter().enumerate();
+        while let Some((index, word)) = words.next() {
+            let Some(&(first, last)) = runs.peek().filter(|(first, _)| *first == index) else {
+                redacted.push(word);
+                continue;
+            };
+            runs.next();
+            let end = words.by_ref().take(last - first).last().map_or(word.end, |(_, w)| w.end);
+            redacted.push(TranscriptionWord {
+                word: self.redact(&text[spans[first].0..spans[last].1]),
+                end,
+                ..word
+            });
+        }
+        redacted
+    }
+
+    fn check_blocked(&self, found: BTreeSet<String>, what: &str) -> Result<(), AppError> {
+        if self.mode == PiiMode::Block && !found.is_empty() {
+            return Err(AppError::ContentPolicyViolation(format!(
+                "PII detected in {}: {}",
+                what,
+                found.into_iter().collect::<Vec<_>>().join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Tokenised values for one request, keyed by placeholder
+#[derive(Debug, Default)]
+pub struct PiiVault {
+    values: HashMap<String, String>,
+    counters: HashMap<String, usize>,
+}
+
+impl PiiVault {
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Placeholder for a value, reusing it when the value repeats
+    fn placeholder(&mut self, label: &str, value: &str) -> String {
+        if let Some((placeholder, _)) = self.values.iter().find(|(_, v)| v.as_str() == value) {
+            return placeholder.clone();
+        }
+        let counter = self.counters.entry(label.to_string()).or_default();
+        *counter += 1;
+        let placeholder = format!("[{}_{}]", label, counter);
+        self.values.insert(placeholder.clone(), value.to_string());
+        placeholder
+    }
+
+    fn longest_placeholder(&self) -> usize {
+        self.values.keys().map(String::len).max().unwrap_or(0)
+    }
+
+    /// Put the original values back in place of their placeholders
+    pub fn restore(&self, text: &str) -> String {
+        self.restore_with(text, false)
+    }
+
+    /// Restore into tool-call arguments, escaping values as JSON string
+    /// content so the arguments stay valid JSON
+    pub fn restore_arguments(&self, text: &str) -> String {
+        self.restore_with(text, true)
+    }
+
+    fn restore_with(&self, text: &str, json: bool) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(open) = rest.find('[') {
+            out.push_str(&rest[..open]);
+            rest = &rest[open..];
+            let value = rest
+                .find(']')
+                .and_then(|close| Some((close, self.values.get(&rest[..=close])?)));
+            match value {
+                Some((close, value)) if json => {
+                    let quoted = serde_json::Value::String(value.clone()).to_string();
+                    out.push_str(&quoted[1..quoted.len() - 1]);
+                    rest = &rest[close + 1..];
+                }
+                Some((close, value)) => {
+                    out.push_str(value);
+                    rest = &rest[close + 1..];
+                }
+                None => {
+                    out.push('[');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Restore placeholders in a reply
+    pub fn restore_response(&self, response: &mut ChatCompletionResponse) {
+        for choice in &mut response.choices {
+            if let Some(ChatContent::Text(text)) = &mut choice.message.content {
+                *text = self.restore(text);
+            }
+            for call in choice.message.tool_calls.iter_mut().flatten() {
+                call.function.arguments = self.restore_arguments(&call.function.arguments);
+            }
+        }
+    }
+
+    /// Restore placeholders in a streamed reply, holding back text that may be
+    /// the start of a placeholder split across chunks
+    pub fn restore_stream(
+        self: Arc<Self>,
+        chunks: BoxStream<'static, Result<ChatCompletionChunk, AppError>>,
+    ) -> BoxStream<'static, Result<ChatCompletionChunk, AppError>> {
+        let mut restorer = StreamRestorer {
+            vault: self,
+            pending: HashMap::new(),
+        };
+        chunks
+            .map(move |chunk| {
+                let mut chunk = chunk?;
+                restorer.restore_chunk(&mut chunk);
+                Ok(chunk)
+            })
+            .boxed()
+    }
+}
+
+/// Per-choice restore state for a streamed reply
+struct StreamRestorer {
+    vault: Arc<PiiVault>,
+    /// Held-back text per (choice, tool call); `None` is the message content
+    pending: HashMap<(u32, Option<u32>), String>,
+}
+
+impl StreamRestorer {
+    fn restore_chunk(&mut self, chunk: &mut ChatCompletionChunk) {
+        for choice in &mut chunk.choices {
+            if let Some(content) = &mut choice.delta.content {
+                *content = self.push((choice.index, None), content);
+            }
+            for call in choice.delta.tool_calls.iter_mut().flatten() {
+                if let Some(arguments) = call.function.as_mut().and_then(|f| f.arguments.as_mut()) {
+                    *arguments = self.push((choice.index, Some(call.index)), arguments);
+                }
+            }
+
+            if choice.finish_reason.is_none() {
+                continue;
+            }
+            // Release whatever is still held back once the choice finishes
+            let mut held: Vec<_> = self
+                .pending
+                .keys()
+                .filter(|(index, _)| *index == choice.index)
+                .copied()
+                .collect();
+            held.sort();
+            for key in held {
+                let held = self.pending.remove(&key).unwrap_or_default();
+                let rest = self.vault.restore_with(&held, key.1.is_some());
+                match key.1 {
+                    None => choice.delta.content.get_or_insert_with(String::new).push_str(&rest),
+                    Some(index) => choice.delta.tool_calls.get_or_insert_with(Vec::new).push(ToolCallDelta {
+                        index,
+                        id: None,
+                        r#type: None,
+                        function: Some(FunctionCallDelta {
+                            name: None,
+                            arguments: Some(rest),
+                        }),
+                    }),
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, key: (u32, Option<u32>), text: &str) -> String {
+        let mut buffer = self.pending.remove(&key).unwrap_or_default();
+        buffer.push_str(text);
+
+        let hold_from = buffer
+            .rfind('[')
+            .filter(|&open| !buffer[open..].contains(']'))
+            .filter(|&open| buffer.len() - open < self.vault.longest_placeholder());
+        if let Some(open) = hold_from {
+            self.pending.insert(key, buffer.split_off(open));
+        }
+        // Tool-call arguments are JSON
+        self.vault.restore_with(&buffer, key.1.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::guardrail::CustomPiiPattern;
+
+    fn scanner(mode: PiiMode) -> PiiScanner {
+        PiiScanner::new(&PiiPolicy {
+            mode,
+            entities: Vec::new(),
+            custom_patterns: vec![CustomPiiPattern {
+                name: "customer_id".to_string(),
+                pattern: r"\bCUST-\d{6}\b".to_string(),
+            }],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn detects_checksummed_entities() {
+        let scanner = scanner(PiiMode::Redact);
+        let text = "Mail jo@example.com or call +44 20 7946 0958. Card 4111 1111 1111 1111, \
+                    not 4111 1111 1111 1112. IBAN GB82 WEST 1234 5698 7654 32, SSN 123-45-6789, \
+                    NINO AB 12 34 56 C, ref CUST-004211.";
+        assert_eq!(
+            scanner.redact(text),
+            "Mail [EMAIL] or call [PHONE]. Card [CREDIT_CARD], \
+             not 4111 1111 1111 1112. IBAN [IBAN], SSN [NATIONAL_ID], \
+             NINO [NATIONAL_ID], ref [CUSTOMER_ID]."
+        );
+        assert!(scanner.find("Order 12345 shipped on 2024-01-15").is_empty());
+
+        // A phone number spoken as several words is redacted as one word
+        let words = ["Call", "+44", "20", "7946", "0958", "today"]
+            .iter()
+            .enumerate()
+            .map(|(i, word)| TranscriptionWord {
+                word: word.to_string(),
+                start: i as f32,
+                end: i as f32 + 0.5,
+                speaker: None,
+            })
+            .collect();
+        let words = scanner.redact_words(words);
+        let text: Vec<_> = words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(text, vec!["Call", "[PHONE]", "today"]);
+        assert_eq!((words[1].start, words[1].end), (1.0, 4.5));
+    }
+
+    #[test]
+    fn tokenises_and_restores_across_chunks() {
+        let scanner = scanner(PiiMode::Tokenize);
+        let mut vault = PiiVault::default();
+        let text = scanner.tokenize("jo@example.com, cc ann@example.com, jo@example.com", &mut vault);
+        assert_eq!(text, "[EMAIL_1], cc [EMAIL_2], [EMAIL_1]");
+        assert_eq!(vault.restore("Reply to [EMAIL_2] [x]"), "Reply to ann@example.com [x]");
+
+        let mut restorer = StreamRestorer {
+            vault: Arc::new(vault),
+            pending: HashMap::new(),
+        };
+        let key = (0, None);
+        assert_eq!(restorer.push(key, "Sent to [EMA"), "Sent to ");
+        assert_eq!(restorer.push(key, "IL_1] and [a"), "jo@example.com and ");
+        assert_eq!(restorer.push(key, "b]"), "[ab]");
+
+        // Values restored into tool-call arguments keep them valid JSON
+        let mut vault = PiiVault::default();
+        let quoted = vault.placeholder("EMAIL", "\"jo\"\\@example.com");
+        let arguments = format!("{{\"to\": \"{}\"}}", quoted);
+        let restored = vault.restore_arguments(&arguments);
+        let parsed: serde_json::Value = serde_json::from_str(&restored).unwrap();
+        assert_eq!(parsed["to"], "\"jo\"\\@example.com");
+    }
+}
@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::transcription::{
+    TranscriptionProviderKind, TranscriptionResponse, TranscriptionSegment, TranscriptionWord,
+};
+use crate::shared::error::AppError;
+
+use super::transcription::{
+    require_key, shape_response, TranscriptionOptions, TranscriptionProvider,
+};
+
+/// Interval between transcript status polls
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Give up on transcripts still processing after this many polls (~1 hour)
+const MAX_POLLS: u32 = 1800;
+
+/// AssemblyAI asynchronous transcription API
+///
+/// The audio is uploaded, a transcript is created from it and polled until
+/// it completes, then sentences are fetched to build segments.
+pub struct AssemblyAIProvider {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl AssemblyAIProvider {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: "https://api.assemblyai.com/v2".to_string(),
+        }
+    }
+
+    async fn get_json<T: for<'de> Deserialize<'de>>(
+        &self,
+        api_key: &str,
+        url: String,
+    ) -> Result<T, AppError> {
+        let response = self
+            .client
+            .get(url)
+            .header("Authorization", api_key)
+            .send()
+            .await?;
+        Self::parse(response).await
+    }
+
+    async fn parse<T: for<'de> Deserialize<'de>>(response: reqwest::Response) -> Result<T, AppError> {
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(AppError::ExternalApiError(format!(
+                "AssemblyAI API error ({}): {}",
+                status, error_text
+            )));
+        }
+        Ok(response.json().await?)
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for AssemblyAIProvider {
+    fn kind(&self) -> TranscriptionProviderKind {
+        TranscriptionProviderKind::Assemblyai
+    }
+
+    fn default_model(&self) -> &'static str {
+        "universal"
+    }
+
+    fn supports_diarization(&self, _model: &str) -> bool {
+        true
+    }
+
+    fn price_per_minute(&self, model: &str) -> f64 {
+        // Price per hour
+        let hourly = match model {
+            "nano" => 0.12,
+            "best" | "slam-1" => 0.37,
+            _ => 0.15, // Universal
+        };
+
+        hourly / 60.0
+    }
+
+    async fn transcribe(
+        &self,
+        api_key: Option<&str>,
+        file_data: Bytes,
+        _file_name: String,
+        options: &TranscriptionOptions,
+    ) -> Result<TranscriptionResponse, AppError> {
+        let api_key = require_key(api_key, self.kind())?;
+
+        // Upload the audio
+        let response = self
+            .client
+            .post(format!("{}/upload", self.base_url))
+            .header("Authorization", api_key)
+            .header("Content-Type", "application/octet-stream")
+            .body(file_data)
+            .send()
+            .await?;
+        let upload: AssemblyAIUpload = Self::parse(response).await?;
+
+        // Create the transcript
+        let request = AssemblyAITranscriptRequest {
+            audio_url: upload.upload_url,
+            speech_model: options.model.clone(),
+            language_detection: options.language.is_none().then_some(true),
+            language_code: options.language.clone(),
+            speaker_labels: options.diarize,
+            punctuate: true,
+            format_text: true,
+        };
+        let response = self
+            .client
+            .post(format!("{}/transcript", self.base_url))
+            .header("Authorization", api_key)
+            .json(&request)
+            .send()
+            .await?;
+        let mut transcript: AssemblyAITranscript = Self::parse(response).await?;
+
+        // Wait for it to finish
+        let mut polls = 0;
+        while !matches!(transcript.status.as_str(), "completed" | "error") {
+            polls += 1;
+            if polls > MAX_POLLS {
+                return Err(AppError::ExternalApiError(format!(
+                    "AssemblyAI transcript {} did not complete in time",
+                    transcript.id
+                )));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+            transcript = self
+                .get_json(api_key, format!("{}/transcript/{}", self.base_url, transcript.id))
+                .await?;
+        }
+
+        if transcript.status == "error" {
+            return Err(AppError::ExternalApiError(format!(
+                "AssemblyAI transcription failed: {}",
+                transcript.error.unwrap_or_default()
+            )));
+        }
+
+        let sentences: AssemblyAISentences = self
+            .get_json(
+                api_key,
+                format!("{}/transcript/{}/sentences", self.base_url, transcript.id),
+            )
+            .await?;
+
+        let segments = sentences
+            .sentences
+            .into_iter()
+            .enumerate()
+            .map(|(id, s)| TranscriptionSegment {
+                id: id as i32,
+                start: s.start as f32 / 1000.0,
+                end: s.end as f32 / 1000.0,
+                text: s.text,
+                tokens: None,
+                temperature: None,
+                avg_logprob: None,
+                compression_ratio: None,
+                no_speech_prob: None,
+                speaker: s.speaker,
+            })
+            .collect();
+
+        let words = transcript
+            .words
+            .unwrap_or_default()
+            .into_iter()
+            .map(|w| TranscriptionWord {
+                word: w.text,
+                start: w.start as f32 / 1000.0,
+                end: w.end as f32 / 1000.0,
+                speaker: w.speaker,
+            })
+            .collect();
+
+        let model = options.model.as_deref().unwrap_or(self.default_model());
+
+        Ok(shape_response(
+            TranscriptionResponse {
+                text: transcript.text.unwrap_or_default(),
+                language: transcript.language_code,
+                duration: transcript.audio_duration,
+                segments: Some(segments),
+                words: Some(words),
+                usage: None,
+            },
+            options,
+            self.price_per_minute(model),
+        ))
+    }
+}
+
+// AssemblyAI API request/response structures
+#[derive(Debug, Deserialize)]
+struct AssemblyAIUpload {
+    upload_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AssemblyAITranscriptRequest {
+    audio_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speech_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language_detection: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language_code: Option<String>,
+    speaker_labels: bool,
+    punctuate: bool,
+    format_text: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssemblyAITranscript {
+    id: String,
+    status: String,
+    text: Option<String>,
+    words: Option<Vec<AssemblyAIWord>>,
+    audio_duration: Option<f32>,
+    language_code: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssemblyAIWord {
+    text: String,
+    start: u64,  // Milliseconds
+    end: u64,
+    speaker: Option<String>,  // "A", "B", ... with speaker_labels
+}
+
+#[derive(Debug, Deserialize)]
+struct AssemblyAISentences {
+    sentences: Vec<AssemblyAISentence>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssemblyAISentence {
+    text: String,
+    start: u64,  // Milliseconds
+    end: u64,
+    speaker: Option<String>,
+}
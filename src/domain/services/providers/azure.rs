@@ -0,0 +1,207 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use reqwest::multipart::Form;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::transcription::{
+    TranscriptionProviderKind, TranscriptionResponse, TranscriptionSegment, TranscriptionWord,
+};
+use crate::shared::error::AppError;
+
+use super::transcription::{
+    audio_content_type, audio_part, require_key, shape_response, speaker_label,
+    TranscriptionOptions, TranscriptionProvider,
+};
+
+/// Upper bound on speakers when diarization is requested
+const MAX_SPEAKERS: u32 = 10;
+
+/// Azure AI Speech fast transcription API
+pub struct AzureSpeechProvider {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+impl AzureSpeechProvider {
+    /// Create a provider for the Speech resource in `region` (e.g. `eastus`)
+    pub fn new(region: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: format!(
+                "https://{}.api.cognitive.microsoft.com/speechtotext/transcriptions:transcribe?api-version=2024-11-15",
+                region
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for AzureSpeechProvider {
+    fn kind(&self) -> TranscriptionProviderKind {
+        TranscriptionProviderKind::Azure
+    }
+
+    fn default_model(&self) -> &'static str {
+        "fast-transcription"
+    }
+
+    fn supports_diarization(&self, _model: &str) -> bool {
+        true
+    }
+
+    fn price_per_minute(&self, _model: &str) -> f64 {
+        1.0 / 60.0 // Standard tier, $1 per audio hour
+    }
+
+    async fn transcribe(
+        &self,
+        api_key: Option<&str>,
+        file_data: Bytes,
+        file_name: String,
+        options: &TranscriptionOptions,
+    ) -> Result<TranscriptionResponse, AppError> {
+        let api_key = require_key(api_key, self.kind())?;
+
+        // Without locales the service identifies the language itself
+        let definition = AzureDefinition {
+            locales: options.language.clone().map(|l| vec![l]),
+            diarization: options.diarize.then_some(AzureDiarization {
+                enabled: true,
+                max_speakers: MAX_SPEAKERS,
+            }),
+        };
+
+        let content_type = audio_content_type(&file_name);
+        let form = Form::new()
+            .part(
+                "audio",
+                audio_part(file_data, file_name)
+                    .mime_str(content_type)
+                    .map_err(|e| AppError::InternalError(format!("Invalid content type: {}", e)))?,
+            )
+            .text("definition", serde_json::to_string(&definition)?);
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("Ocp-Apim-Subscription-Key", api_key)
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(AppError::ExternalApiError(format!(
+                "Azure Speech API error ({}): {}",
+                status, error_text
+            )));
+        }
+
+        let azure_response: AzureTranscriptionResponse = response.json().await?;
+
+        let mut segments = Vec::new();
+        let mut words = Vec::new();
+        let mut language = None;
+        for phrase in &azure_response.phrases {
+            language = language.or_else(|| phrase.locale.clone());
+            // Speakers are numbered from 1
+            let speaker = phrase.speaker.map(|s| speaker_label(s.saturating_sub(1)));
+            segments.push(TranscriptionSegment {
+                id: segments.len() as i32,
+                start: ms(phrase.offset_milliseconds),
+                end: ms(phrase.offset_milliseconds + phrase.duration_milliseconds),
+                text: phrase.text.clone(),
+                tokens: None,
+                temperature: None,
+                avg_logprob: None,
+                compression_ratio: None,
+                no_speech_prob: None,
+                speaker: speaker.clone(),
+            });
+            for word in &phrase.words {
+                words.push(TranscriptionWord {
+                    word: word.text.clone(),
+                    start: ms(word.offset_milliseconds),
+                    end: ms(word.offset_milliseconds + word.duration_milliseconds),
+                    speaker: speaker.clone(),
+                });
+            }
+        }
+
+        let text = azure_response
+            .combined_phrases
+            .iter()
+            .map(|p| p.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Ok(shape_response(
+            TranscriptionResponse {
+                text,
+                language,
+                duration: azure_response.duration_milliseconds.map(ms),
+                segments: Some(segments),
+                words: Some(words),
+                usage: None,
+            },
+            options,
+            self.price_per_minute(self.default_model()),
+        ))
+    }
+}
+
+fn ms(milliseconds: u64) -> f32 {
+    milliseconds as f32 / 1000.0
+}
+
+// Azure fast transcription request/response structures
+#[derive(Debug, Serialize)]
+struct AzureDefinition {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locales: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diarization: Option<AzureDiarization>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureDiarization {
+    enabled: bool,
+    max_speakers: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureTranscriptionResponse {
+    duration_milliseconds: Option<u64>,
+    #[serde(default)]
+    combined_phrases: Vec<AzureCombinedPhrase>,
+    #[serde(default)]
+    phrases: Vec<AzurePhrase>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureCombinedPhrase {
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzurePhrase {
+    offset_milliseconds: u64,
+    duration_milliseconds: u64,
+    text: String,
+    locale: Option<String>,
+    speaker: Option<usize>,
+    #[serde(default)]
+    words: Vec<AzureWord>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureWord {
+    text: String,
+    offset_milliseconds: u64,
+    duration_milliseconds: u64,
+}
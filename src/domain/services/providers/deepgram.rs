@@ -0,0 +1,204 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde::Deserialize;
+
+use crate::domain::entities::transcription::{
+    TranscriptionProviderKind, TranscriptionResponse, TranscriptionSegment, TranscriptionWord,
+};
+use crate::shared::error::AppError;
+
+use super::transcription::{
+    audio_content_type, require_key, shape_response, speaker_label, TranscriptionOptions,
+    TranscriptionProvider,
+};
+
+/// Deepgram pre-recorded audio API
+pub struct DeepgramProvider {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl DeepgramProvider {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: "https://api.deepgram.com/v1".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for DeepgramProvider {
+    fn kind(&self) -> TranscriptionProviderKind {
+        TranscriptionProviderKind::Deepgram
+    }
+
+    fn default_model(&self) -> &'static str {
+        "nova-3"
+    }
+
+    fn supports_diarization(&self, _model: &str) -> bool {
+        true
+    }
+
+    /// Pay-as-you-go pricing
+    fn price_per_minute(&self, model: &str) -> f64 {
+        match model {
+            m if m.starts_with("nova") => 0.0043,
+            m if m.starts_with("whisper") => 0.0048,
+            m if m.starts_with("enhanced") => 0.0145,
+            m if m.starts_with("base") => 0.0125,
+            _ => 0.0043, // Default to Nova pricing
+        }
+    }
+
+    async fn transcribe(
+        &self,
+        api_key: Option<&str>,
+        file_data: Bytes,
+        file_name: String,
+        options: &TranscriptionOptions,
+    ) -> Result<TranscriptionResponse, AppError> {
+        let api_key = require_key(api_key, self.kind())?;
+        let model = options
+            .model
+            .clone()
+            .unwrap_or_else(|| self.default_model().to_string());
+
+        let mut query = vec![
+            ("model", model.clone()),
+            ("smart_format", "true".to_string()),
+            ("punctuate", "true".to_string()),
+            ("utterances", "true".to_string()),
+        ];
+        if options.diarize {
+            query.push(("diarize", "true".to_string()));
+        }
+        match &options.language {
+            Some(language) => query.push(("language", language.clone())),
+            None => query.push(("detect_language", "true".to_string())),
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/listen", self.base_url))
+            .query(&query)
+            .header("Authorization", format!("Token {}", api_key))
+            .header("Content-Type", audio_content_type(&file_name))
+            .body(file_data)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(AppError::ExternalApiError(format!(
+                "Deepgram API error ({}): {}",
+                status, error_text
+            )));
+        }
+
+        let deepgram_response: DeepgramResponse = response.json().await?;
+        let channel = deepgram_response.results.channels.into_iter().next();
+        let language = channel.as_ref().and_then(|c| c.detected_language.clone());
+        let alternative = channel.and_then(|c| c.alternatives.into_iter().next());
+
+        let (text, words) = match alternative {
+            Some(alternative) => (
+                alternative.transcript,
+                alternative
+                    .words
+                    .into_iter()
+                    .map(|w| TranscriptionWord {
+                        word: w.punctuated_word.unwrap_or(w.word),
+                        start: w.start,
+                        end: w.end,
+                        speaker: w.speaker.map(speaker_label),
+                    })
+                    .collect(),
+            ),
+            None => (String::new(), Vec::new()),
+        };
+
+        let segments = deepgram_response
+            .results
+            .utterances
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(id, u)| TranscriptionSegment {
+                id: id as i32,
+                start: u.start,
+                end: u.end,
+                text: u.transcript,
+                tokens: None,
+                temperature: None,
+                avg_logprob: None,
+                compression_ratio: None,
+                no_speech_prob: None,
+                speaker: u.speaker.map(speaker_label),
+            })
+            .collect();
+
+        Ok(shape_response(
+            TranscriptionResponse {
+                text,
+                language: language.or_else(|| options.language.clone()),
+                duration: deepgram_response.metadata.duration,
+                segments: Some(segments),
+                words: Some(words),
+                usage: None,
+            },
+            options,
+            self.price_per_minute(&model),
+        ))
+    }
+}
+
+// Deepgram API response structures
+#[derive(Debug, Deserialize)]
+struct DeepgramResponse {
+    metadata: DeepgramMetadata,
+    results: DeepgramResults,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramMetadata {
+    duration: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramResults {
+    channels: Vec<DeepgramChannel>,
+    utterances: Option<Vec<DeepgramUtterance>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramChannel {
+    detected_language: Option<String>,
+    alternatives: Vec<DeepgramAlternative>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramAlternative {
+    transcript: String,
+    #[serde(default)]
+    words: Vec<DeepgramWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramWord {
+    word: String,
+    punctuated_word: Option<String>,
+    start: f32,
+    end: f32,
+    speaker: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramUtterance {
+    start: f32,
+    end: f32,
+    transcript: String,
+    speaker: Option<usize>,
+}
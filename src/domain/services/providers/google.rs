@@ -0,0 +1,264 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::transcription::{
+    TranscriptionProviderKind, TranscriptionResponse, TranscriptionSegment, TranscriptionWord,
+};
+use crate::shared::error::AppError;
+
+use super::transcription::{
+    assign_segment_speakers, require_key, shape_response, speaker_label, TranscriptionOptions,
+    TranscriptionProvider,
+};
+
+/// Inline audio limit for synchronous recognition
+const MAX_INLINE_BYTES: usize = 10 * 1024 * 1024;
+
+/// Google Cloud Speech-to-Text v1 synchronous recognition
+///
+/// Encoding is taken from the WAV or FLAC header; synchronous recognition is
+/// limited by Google to about one minute of audio per request.
+pub struct GoogleSpeechProvider {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl GoogleSpeechProvider {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: "https://speech.googleapis.com/v1".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for GoogleSpeechProvider {
+    fn kind(&self) -> TranscriptionProviderKind {
+        TranscriptionProviderKind::Google
+    }
+
+    fn default_model(&self) -> &'static str {
+        "latest_long"
+    }
+
+    fn supports_diarization(&self, _model: &str) -> bool {
+        true
+    }
+
+    fn price_per_minute(&self, _model: &str) -> f64 {
+        0.024 // Standard recognition
+    }
+
+    fn max_upload_bytes(&self) -> Option<usize> {
+        Some(MAX_INLINE_BYTES)
+    }
+
+    async fn transcribe(
+        &self,
+        api_key: Option<&str>,
+        file_data: Bytes,
+        _file_name: String,
+        options: &TranscriptionOptions,
+    ) -> Result<TranscriptionResponse, AppError> {
+        let api_key = require_key(api_key, self.kind())?;
+
+        let request = GoogleRecognizeRequest {
+            config: GoogleRecognitionConfig {
+                language_code: options.language.clone().unwrap_or_else(|| "en-US".to_string()),
+                model: options
+                    .model
+                    .clone()
+                    .unwrap_or_else(|| self.default_model().to_string()),
+                enable_word_time_offsets: true,
+                enable_automatic_punctuation: true,
+                diarization_config: options.diarize.then_some(GoogleDiarizationConfig {
+                    enable_speaker_diarization: true,
+                }),
+            },
+            audio: GoogleRecognitionAudio {
+                content: BASE64.encode(&file_data),
+            },
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/speech:recognize", self.base_url))
+            .query(&[("key", api_key)])
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(AppError::ExternalApiError(format!(
+                "Google Speech API error ({}): {}",
+                status, error_text
+            )));
+        }
+
+        let google_response: GoogleRecognizeResponse = response.json().await?;
+
+        let mut texts = Vec::new();
+        let mut segments = Vec::new();
+        let mut words = Vec::new();
+        let mut language = None;
+        let mut segment_start = 0.0;
+
+        // With diarization the final result repeats every word with its speaker tag
+        let diarized_words: Vec<GoogleWord> = if options.diarize {
+            google_response
+                .results
+                .last()
+                .and_then(|r| r.alternatives.first())
+                .filter(|a| a.words.iter().any(|w| w.speaker_tag.is_some_and(|t| t > 0)))
+                .map(|a| a.words.clone())
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let mut results = google_response.results;
+        if !diarized_words.is_empty() {
+            results.pop();
+        }
+
+        // Each result covers the audio since the previous result ended
+        for result in results {
+            let end = result.result_end_time.as_deref().and_then(parse_duration);
+            language = language.or(result.language_code);
+            let Some(alternative) = result.alternatives.into_iter().next() else {
+                continue;
+            };
+
+            let transcript = alternative.transcript.trim().to_string();
+            if diarized_words.is_empty() {
+                words.extend(alternative.words.into_iter().map(|w| to_word(w, segment_start)));
+            }
+
+            let segment_end = end.unwrap_or(segment_start);
+            segments.push(TranscriptionSegment {
+                id: segments.len() as i32,
+                start: segment_start,
+                end: segment_end,
+                text: transcript.clone(),
+                tokens: None,
+                temperature: None,
+                avg_logprob: None,
+                compression_ratio: None,
+                no_speech_prob: None,
+                speaker: None,
+            });
+            texts.push(transcript);
+            segment_start = segment_end;
+        }
+
+        if !diarized_words.is_empty() {
+            words = diarized_words.into_iter().map(|w| to_word(w, 0.0)).collect();
+            assign_segment_speakers(&mut segments, &words);
+        }
+
+        // Billed time is rounded up; prefer the end of the last result
+        let duration = if segment_start > 0.0 {
+            Some(segment_start)
+        } else {
+            google_response.total_billed_time.as_deref().and_then(parse_duration)
+        };
+
+        Ok(shape_response(
+            TranscriptionResponse {
+                text: texts.join(" "),
+                language,
+                duration,
+                segments: Some(segments),
+                words: Some(words),
+                usage: None,
+            },
+            options,
+            self.price_per_minute(self.default_model()),
+        ))
+    }
+}
+
+fn to_word(word: GoogleWord, fallback_time: f32) -> TranscriptionWord {
+    TranscriptionWord {
+        word: word.word,
+        start: word.start_time.as_deref().and_then(parse_duration).unwrap_or(fallback_time),
+        end: word.end_time.as_deref().and_then(parse_duration).unwrap_or(fallback_time),
+        // Speaker tags start at 1; 0 means unassigned
+        speaker: word
+            .speaker_tag
+            .filter(|t| *t > 0)
+            .map(|t| speaker_label(t as usize - 1)),
+    }
+}
+
+/// Parse protobuf JSON durations such as `"1.300s"`
+fn parse_duration(value: &str) -> Option<f32> {
+    value.strip_suffix('s')?.parse().ok()
+}
+
+// Google Speech-to-Text request/response structures
+#[derive(Debug, Serialize)]
+struct GoogleRecognizeRequest {
+    config: GoogleRecognitionConfig,
+    audio: GoogleRecognitionAudio,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleRecognitionConfig {
+    language_code: String,
+    model: String,
+    enable_word_time_offsets: bool,
+    enable_automatic_punctuation: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diarization_config: Option<GoogleDiarizationConfig>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleDiarizationConfig {
+    enable_speaker_diarization: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct GoogleRecognitionAudio {
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleRecognizeResponse {
+    #[serde(default)]
+    results: Vec<GoogleRecognitionResult>,
+    total_billed_time: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleRecognitionResult {
+    #[serde(default)]
+    alternatives: Vec<GoogleAlternative>,
+    result_end_time: Option<String>,
+    language_code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleAlternative {
+    #[serde(default)]
+    transcript: String,
+    #[serde(default)]
+    words: Vec<GoogleWord>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleWord {
+    word: String,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    speaker_tag: Option<i32>,
+}
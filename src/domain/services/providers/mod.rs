@@ -1,3 +1,15 @@
+pub mod assemblyai;
+pub mod azure;
+pub mod deepgram;
+pub mod google;
 pub mod openai;
+pub mod transcription;
+pub mod whisper_server;
 
-pub use openai::OpenAIProvider;
\ No newline at end of file
+pub use assemblyai::AssemblyAIProvider;
+pub use azure::AzureSpeechProvider;
+pub use deepgram::DeepgramProvider;
+pub use google::GoogleSpeechProvider;
+pub use openai::OpenAIProvider;
+pub use transcription::{TranscriptionOptions, TranscriptionProvider};
+pub use whisper_server::WhisperServerProvider;
@@ -1,16 +1,26 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
 use reqwest::multipart::{Form, Part};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::api::dto::{
-    ChatChoice, ChatCompletionRequest, ChatCompletionResponse, ChatMessage, ChatRole, ChatUsage,
-    FinishReason,
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatMessage,
+    ChatResponseFormat, ChatTool, ImagesResponse, ModerationRequest, ModerationResponse,
+    ReasoningEffort, StopSequences, StreamOptions, ToolChoice,
 };
+use crate::domain::entities::image::{ImageOperation, ImageRequest, ImageUpload};
+use crate::domain::entities::speech::SpeechRequest;
 use crate::domain::entities::transcription::{
-    ResponseFormat, TimestampGranularity, TranscriptionResponse, TranscriptionSegment,
-    TranscriptionUsage, TranscriptionWord,
+    ResponseFormat, TimestampGranularity, TranscriptionProviderKind, TranscriptionResponse,
+    TranscriptionSegment, TranscriptionUsage, TranscriptionWord,
 };
 use crate::shared::error::AppError;
 
+use super::transcription::{audio_part, require_key, TranscriptionOptions, TranscriptionProvider};
+
 /// OpenAI provider service for API interactions
 pub struct OpenAIProvider {
     client: reqwest::Client,
@@ -25,11 +35,59 @@ impl OpenAIProvider {
         }
     }
 
+    /// Create a provider targeting an OpenAI-compatible server
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Synthesize speech using OpenAI TTS API
+    ///
+    /// Returns the upstream response once the status has been checked so the
+    /// caller can stream the audio body without buffering it.
+    pub async fn speech(
+        &self,
+        api_key: &str,
+        request: &SpeechRequest,
+    ) -> Result<reqwest::Response, AppError> {
+        let url = format!("{}/audio/speech", self.base_url);
+
+        let openai_request = OpenAISpeechRequest {
+            model: request.model.clone(),
+            input: request.input.clone(),
+            voice: request.voice.clone(),
+            response_format: request.response_format.as_str().to_string(),
+            speed: request.speed,
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(&openai_request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(AppError::ExternalApiError(format!(
+                "OpenAI API error ({}): {}",
+                status, error_text
+            )));
+        }
+
+        Ok(response)
+    }
+
     /// Transcribe audio using OpenAI Whisper API
+    #[allow(clippy::too_many_arguments)]
     pub async fn transcribe(
         &self,
         api_key: &str,
-        file_data: Vec<u8>,
+        file_data: Bytes,
         file_name: String,
         model: Option<String>,
         language: Option<String>,
@@ -39,11 +97,13 @@ impl OpenAIProvider {
         timestamp_granularities: Option<Vec<TimestampGranularity>>,
     ) -> Result<TranscriptionResponse, AppError> {
         let url = format!("{}/audio/transcriptions", self.base_url);
+        let model = model.unwrap_or_else(|| "whisper-1".to_string());
+        let price_per_minute = transcription_price_per_minute(&model);
 
         // Build multipart form
         let mut form = Form::new()
-            .part("file", Part::bytes(file_data).file_name(file_name))
-            .text("model", model.unwrap_or_else(|| "whisper-1".to_string()));
+            .part("file", audio_part(file_data, file_name))
+            .text("model", model);
 
         if let Some(lang) = language {
             form = form.text("language", lang);
@@ -53,6 +113,12 @@ impl OpenAIProvider {
             form = form.text("prompt", p);
         }
 
+        // Text, SRT and VTT come back as raw bodies rather than JSON
+        let raw_body = matches!(
+            response_format,
+            Some(ResponseFormat::Text | ResponseFormat::Srt | ResponseFormat::Vtt)
+        );
+
         if let Some(format) = response_format {
             let format_str = match format {
                 ResponseFormat::Json => "json",
@@ -60,6 +126,12 @@ impl OpenAIProvider {
                 ResponseFormat::Srt => "srt",
                 ResponseFormat::VerboseJson => "verbose_json",
                 ResponseFormat::Vtt => "vtt",
+                ResponseFormat::Tsv => {
+                    return Err(AppError::BadRequest(
+                        "OpenAI does not produce tsv; request verbose_json and render it locally"
+                            .to_string(),
+                    ))
+                }
             };
             form = form.text("response_format", format_str);
         }
@@ -69,15 +141,13 @@ impl OpenAIProvider {
         }
 
         if let Some(granularities) = timestamp_granularities {
-            let granularities_str = granularities
-                .iter()
-                .map(|g| match g {
+            for granularity in granularities {
+                let granularity_str = match granularity {
                     TimestampGranularity::Word => "word",
                     TimestampGranularity::Segment => "segment",
-                })
-                .collect::<Vec<_>>()
-                .join(",");
-            form = form.text("timestamp_granularities", granularities_str);
+                };
+                form = form.text("timestamp_granularities[]", granularity_str);
+            }
         }
 
         // Make API request
@@ -97,6 +167,17 @@ impl OpenAIProvider {
             )));
         }
 
+        if raw_body {
+            return Ok(TranscriptionResponse {
+                text: response.text().await?,
+                language: None,
+                duration: None,
+                segments: None,
+                words: None,
+                usage: None,
+            });
+        }
+
         // Parse response
         let openai_response: OpenAITranscriptionResponse = response.json().await?;
 
@@ -117,6 +198,7 @@ impl OpenAIProvider {
                         avg_logprob: s.avg_logprob,
                         compression_ratio: s.compression_ratio,
                         no_speech_prob: s.no_speech_prob,
+                        speaker: None,
                     })
                     .collect()
             }),
@@ -127,13 +209,90 @@ impl OpenAIProvider {
                         word: w.word,
                         start: w.start,
                         end: w.end,
+                        speaker: None,
                     })
                     .collect()
             }),
             usage: openai_response.duration.map(|dur| TranscriptionUsage {
                 audio_duration_seconds: dur,
                 tokens_used: None,
-                estimated_cost_usd: Some(dur as f64 * 0.006 / 60.0), // $0.006 per minute
+                estimated_cost_usd: Some(dur as f64 / 60.0 * price_per_minute),
+            }),
+        })
+    }
+
+    /// Transcribe with speaker labels using a diarization model
+    /// (`gpt-4o-transcribe-diarize`), which returns `diarized_json`
+    pub async fn transcribe_diarized(
+        &self,
+        api_key: &str,
+        file_data: Bytes,
+        file_name: String,
+        model: String,
+        language: Option<String>,
+    ) -> Result<TranscriptionResponse, AppError> {
+        let url = format!("{}/audio/transcriptions", self.base_url);
+        let price_per_minute = transcription_price_per_minute(&model);
+
+        let mut form = Form::new()
+            .part("file", audio_part(file_data, file_name))
+            .text("model", model)
+            .text("response_format", "diarized_json")
+            .text("chunking_strategy", "auto");
+
+        if let Some(lang) = language {
+            form = form.text("language", lang);
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(AppError::ExternalApiError(format!(
+                "OpenAI API error: {}",
+                error_text
+            )));
+        }
+
+        let openai_response: OpenAIDiarizedResponse = response.json().await?;
+        let duration = openai_response
+            .duration
+            .or_else(|| openai_response.segments.last().map(|s| s.end));
+
+        Ok(TranscriptionResponse {
+            text: openai_response.text,
+            language: None,
+            duration,
+            segments: Some(
+                openai_response
+                    .segments
+                    .into_iter()
+                    .enumerate()
+                    .map(|(id, s)| TranscriptionSegment {
+                        id: id as i32,
+                        start: s.start,
+                        end: s.end,
+                        text: s.text,
+                        tokens: None,
+                        temperature: None,
+                        avg_logprob: None,
+                        compression_ratio: None,
+                        no_speech_prob: None,
+                        speaker: s.speaker,
+                    })
+                    .collect(),
+            ),
+            words: None,
+            usage: duration.map(|dur| TranscriptionUsage {
+                audio_duration_seconds: dur,
+                tokens_used: None,
+                estimated_cost_usd: Some(dur as f64 / 60.0 * price_per_minute),
             }),
         })
     }
@@ -144,30 +303,11 @@ impl OpenAIProvider {
         api_key: &str,
         request: &ChatCompletionRequest,
     ) -> Result<ChatCompletionResponse, AppError> {
+        check_chat_parameters(request)?;
         let url = format!("{}/chat/completions", self.base_url);
 
         // Convert our request to OpenAI format
-        let openai_request = OpenAIChatRequest {
-            model: request.model.clone(),
-            messages: request
-                .messages
-                .iter()
-                .map(|m| OpenAIChatMessage {
-                    role: match m.role {
-                        ChatRole::System => "system".to_string(),
-                        ChatRole::User => "user".to_string(),
-                        ChatRole::Assistant => "assistant".to_string(),
-                    },
-                    content: m.content.clone(),
-                })
-                .collect(),
-            temperature: Some(request.temperature),
-            max_tokens: request.max_tokens,
-            stream: Some(false), // Non-streaming for now
-            top_p: Some(request.top_p),
-            frequency_penalty: Some(request.frequency_penalty),
-            presence_penalty: Some(request.presence_penalty),
-        };
+        let openai_request = OpenAIChatRequest::from_request(request, false);
 
         // Make API request
         let start_time = std::time::Instant::now();
@@ -191,61 +331,306 @@ impl OpenAIProvider {
             )));
         }
 
-        // Parse response
-        let openai_response: OpenAIChatResponse = response.json().await?;
+        // Deserialize straight into the DTO so fields pass through unchanged
+        let mut chat_response: ChatCompletionResponse = response.json().await?;
 
         // Calculate cost (simplified - should use actual pricing)
         let cost = calculate_openai_cost(
             &request.model,
-            openai_response.usage.prompt_tokens,
-            openai_response.usage.completion_tokens,
+            chat_response.usage.prompt_tokens,
+            chat_response.usage.completion_tokens,
         );
 
-        // Convert to our response format
-        Ok(ChatCompletionResponse {
-            id: openai_response.id,
-            object: "chat.completion".to_string(),
-            created: openai_response.created,
-            model: openai_response.model,
-            choices: openai_response
-                .choices
-                .into_iter()
-                .map(|c| ChatChoice {
-                    index: c.index,
-                    message: ChatMessage {
-                        role: match c.message.role.as_str() {
-                            "system" => ChatRole::System,
-                            "user" => ChatRole::User,
-                            "assistant" => ChatRole::Assistant,
-                            _ => ChatRole::Assistant,
-                        },
-                        content: c.message.content,
-                    },
-                    finish_reason: c.finish_reason.and_then(|r| match r.as_str() {
-                        "stop" => Some(FinishReason::Stop),
-                        "length" => Some(FinishReason::Length),
-                        "content_filter" => Some(FinishReason::ContentFilter),
-                        _ => None,
-                    }),
-                })
-                .collect(),
-            usage: ChatUsage {
-                prompt_tokens: openai_response.usage.prompt_tokens,
-                completion_tokens: openai_response.usage.completion_tokens,
-                total_tokens: openai_response.usage.total_tokens,
-            },
-            x_llmhub: Some(crate::api::dto::ChatMetadata {
-                provider: "openai".to_string(),
-                cached: false,
-                cost,
-                response_time,
-            }),
-        })
+        chat_response.x_llmhub = Some(crate::api::dto::ChatMetadata {
+            provider: "openai".to_string(),
+            cached: false,
+            cost,
+            response_time,
+            prompt_injection: None,
+        });
+
+        Ok(chat_response)
+    }
+
+    /// Create embeddings; the request body is forwarded unchanged
+    pub async fn embeddings(
+        &self,
+        api_key: &str,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, AppError> {
+        let url = format!("{}/embeddings", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(AppError::ExternalApiError(format!(
+                "OpenAI API error ({}): {}",
+                status, error_text
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Classify content with a moderation model
+    pub async fn moderations(
+        &self,
+        api_key: &str,
+        request: &ModerationRequest,
+    ) -> Result<ModerationResponse, AppError> {
+        let url = format!("{}/moderations", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(AppError::ExternalApiError(format!(
+                "OpenAI API error ({}): {}",
+                status, error_text
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Generate, edit or vary images. Generations are sent as JSON, edits
+    /// and variations as multipart forms with the source images.
+    pub async fn images(
+        &self,
+        api_key: &str,
+        request: &ImageRequest,
+    ) -> Result<ImagesResponse, AppError> {
+        let url = format!("{}/{}", self.base_url, request.operation.path());
+
+        let mut fields = request.options.clone();
+        fields.insert("model".to_string(), request.model.clone().into());
+        fields.insert("n".to_string(), request.n.into());
+        if let Some(prompt) = &request.prompt {
+            fields.insert("prompt".to_string(), prompt.clone().into());
+        }
+        if let Some(size) = &request.size {
+            fields.insert("size".to_string(), size.clone().into());
+        }
+        if let Some(quality) = &request.quality {
+            fields.insert("quality".to_string(), quality.clone().into());
+        }
+
+        let builder = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", api_key));
+        let builder = if request.operation == ImageOperation::Generation {
+            builder.json(&fields)
+        } else {
+            // gpt-image-1 accepts several source images as `image[]`
+            let image_field = if request.images.len() > 1 { "image[]" } else { "image" };
+            let mut form = Form::new();
+            for image in &request.images {
+                form = form.part(image_field, image_part(image)?);
+            }
+            if let Some(mask) = &request.mask {
+                form = form.part("mask", image_part(mask)?);
+            }
+            for (key, value) in fields {
+                let value = match value {
+                    serde_json::Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                form = form.text(key, value);
+            }
+            builder.multipart(form)
+        };
+
+        let response = builder.send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(AppError::ExternalApiError(format!(
+                "OpenAI API error ({}): {}",
+                status, error_text
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Create a streaming chat completion, yielding chunks as the upstream
+    /// sends them
+    pub async fn chat_completion_stream(
+        &self,
+        api_key: &str,
+        request: &ChatCompletionRequest,
+    ) -> Result<BoxStream<'static, Result<ChatCompletionChunk, AppError>>, AppError> {
+        check_chat_parameters(request)?;
+        let url = format!("{}/chat/completions", self.base_url);
+        let openai_request = OpenAIChatRequest::from_request(request, true);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(&openai_request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(AppError::ExternalApiError(format!(
+                "OpenAI API error ({}): {}",
+                status, error_text
+            )));
+        }
+
+        let chunks = sse_data(response).map(|data| Ok(serde_json::from_str(&data?)?));
+
+        Ok(chunks.boxed())
+    }
+}
+
+/// `data:` payloads of a server-sent event stream, ending at `[DONE]`
+fn sse_data(response: reqwest::Response) -> impl Stream<Item = Result<String, AppError>> {
+    let state = (response.bytes_stream(), Vec::new(), false);
+    futures::stream::unfold(state, |(mut body, mut buffer, done)| async move {
+        if done {
+            return None;
+        }
+        loop {
+            // Split on bytes so multi-byte characters never straddle a read
+            if let Some(newline) = buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=newline).collect();
+                let line = String::from_utf8_lossy(&line);
+                let Some(data) = line.trim_end().strip_prefix("data:") else {
+                    continue;
+                };
+                let data = data.trim_start();
+                if data == "[DONE]" {
+                    return None;
+                }
+                return Some((Ok(data.to_string()), (body, buffer, false)));
+            }
+
+            match body.next().await {
+                Some(Ok(bytes)) => buffer.extend_from_slice(&bytes),
+                Some(Err(e)) => return Some((Err(e.into()), (body, buffer, true))),
+                None => return None,
+            }
+        }
+    })
+}
+
+/// o-series and GPT-5 models, which reason before answering
+fn is_reasoning_model(model: &str) -> bool {
+    ["o1", "o3", "o4", "gpt-5"].iter().any(|prefix| model.starts_with(prefix))
+        && !model.starts_with("gpt-5-chat")
+}
+
+/// Reject parameters the model cannot honour rather than let them be
+/// dropped or defaulted upstream
+fn check_chat_parameters(request: &ChatCompletionRequest) -> Result<(), AppError> {
+    let unsupported = if is_reasoning_model(&request.model) {
+        [
+            ("temperature", request.temperature != 1.0),
+            ("top_p", request.top_p != 1.0),
+            ("frequency_penalty", request.frequency_penalty != 0.0),
+            ("presence_penalty", request.presence_penalty != 0.0),
+            ("logprobs", request.logprobs == Some(true)),
+            ("logit_bias", request.logit_bias.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(name, set)| set.then_some(name))
+    } else {
+        request.reasoning_effort.map(|_| "reasoning_effort")
+    };
+
+    match unsupported {
+        Some(name) => Err(AppError::BadRequest(format!(
+            "{} is not supported by model {}",
+            name, request.model
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for OpenAIProvider {
+    fn kind(&self) -> TranscriptionProviderKind {
+        TranscriptionProviderKind::Openai
+    }
+
+    fn default_model(&self) -> &'static str {
+        "whisper-1"
+    }
+
+    fn returns_timestamps(&self, model: &str) -> bool {
+        // gpt-4o-transcribe models only support json and text
+        model.starts_with("whisper")
+    }
+
+    fn supports_diarization(&self, model: &str) -> bool {
+        model.contains("diarize")
+    }
+
+    fn price_per_minute(&self, model: &str) -> f64 {
+        transcription_price_per_minute(model)
+    }
+
+    async fn transcribe(
+        &self,
+        api_key: Option<&str>,
+        file_data: Bytes,
+        file_name: String,
+        options: &TranscriptionOptions,
+    ) -> Result<TranscriptionResponse, AppError> {
+        if options.diarize {
+            return self
+                .transcribe_diarized(
+                    require_key(api_key, self.kind())?,
+                    file_data,
+                    file_name,
+                    options
+                        .model
+                        .clone()
+                        .unwrap_or_else(|| "gpt-4o-transcribe-diarize".to_string()),
+                    options.language.clone(),
+                )
+                .await;
+        }
+
+        OpenAIProvider::transcribe(
+            self,
+            require_key(api_key, self.kind())?,
+            file_data,
+            file_name,
+            options.model.clone(),
+            options.language.clone(),
+            options.prompt.clone(),
+            options.response_format.clone(),
+            options.temperature,
+            options.timestamp_granularities.clone(),
+        )
+        .await
     }
 }
 
 // Helper function to calculate OpenAI costs
-fn calculate_openai_cost(model: &str, prompt_tokens: u32, completion_tokens: u32) -> f64 {
+pub fn calculate_openai_cost(model: &str, prompt_tokens: u32, completion_tokens: u32) -> f64 {
     // Simplified pricing (as of 2024) - should be maintained separately
     let (input_price, output_price) = match model {
         m if m.starts_with("gpt-4-turbo") || m.starts_with("gpt-4-1106") => (0.01, 0.03),
@@ -260,11 +645,145 @@ fn calculate_openai_cost(model: &str, prompt_tokens: u32, completion_tokens: u32
     prompt_cost + completion_cost
 }
 
+/// Calculate OpenAI embeddings cost from the number of input tokens
+pub fn calculate_embedding_cost(model: &str, tokens: u32) -> f64 {
+    // Price per 1M tokens
+    let price = match model {
+        m if m.starts_with("text-embedding-3-large") => 0.13,
+        m if m.starts_with("text-embedding-ada-002") => 0.10,
+        _ => 0.02, // Default to text-embedding-3-small pricing
+    };
+
+    (tokens as f64 / 1_000_000.0) * price
+}
+
+/// Multipart part for an uploaded image, typed from its magic bytes
+fn image_part(image: &ImageUpload) -> Result<Part, AppError> {
+    let mime = image_content_type(&image.data).ok_or_else(|| {
+        AppError::UnsupportedMediaType(format!("{} is not a PNG, JPEG or WebP image", image.file_name))
+    })?;
+    let len = image.data.len() as u64;
+    Part::stream_with_length(image.data.clone(), len)
+        .file_name(image.file_name.clone())
+        .mime_str(mime)
+        .map_err(|e| AppError::InternalError(format!("Invalid image MIME type: {}", e)))
+}
+
+/// MIME type of a PNG, JPEG or WebP image
+pub fn image_content_type(data: &[u8]) -> Option<&'static str> {
+    match data {
+        [0x89, b'P', b'N', b'G', ..] => Some("image/png"),
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Calculate OpenAI image cost per generated image from model, size and quality
+pub fn calculate_image_cost(model: &str, size: Option<&str>, quality: Option<&str>, images: usize) -> f64 {
+    let square = matches!(size, None | Some("auto") | Some("1024x1024"));
+    let price = match model {
+        m if m.starts_with("gpt-image-1-mini") => match (quality.unwrap_or("auto"), square) {
+            ("low", true) => 0.005,
+            ("low", false) => 0.006,
+            ("high", true) => 0.036,
+            ("high", false) => 0.052,
+            (_, true) => 0.011, // medium and auto
+            (_, false) => 0.015,
+        },
+        m if m.starts_with("gpt-image-1") => match (quality.unwrap_or("auto"), square) {
+            ("low", true) => 0.011,
+            ("low", false) => 0.016,
+            ("high", true) => 0.167,
+            ("high", false) => 0.25,
+            (_, true) => 0.042, // medium and auto
+            (_, false) => 0.063,
+        },
+        m if m.starts_with("dall-e-3") => match (quality, square) {
+            (Some("hd"), true) => 0.08,
+            (Some("hd"), false) => 0.12,
+            (_, true) => 0.04,
+            (_, false) => 0.08,
+        },
+        _ => match size {
+            // dall-e-2
+            Some("256x256") => 0.016,
+            Some("512x512") => 0.018,
+            _ => 0.02,
+        },
+    };
+
+    price * images as f64
+}
+
+/// OpenAI transcription price per audio minute
+fn transcription_price_per_minute(model: &str) -> f64 {
+    match model {
+        m if m.starts_with("gpt-4o-mini-transcribe") => 0.003,
+        _ => 0.006, // whisper-1 and gpt-4o-transcribe
+    }
+}
+
+/// Calculate OpenAI text-to-speech cost from the number of input characters
+pub fn calculate_speech_cost(model: &str, characters: usize) -> f64 {
+    // Price per 1M characters
+    let price = match model {
+        m if m.starts_with("tts-1-hd") => 30.0,
+        m if m.starts_with("tts-1") => 15.0,
+        m if m.starts_with("gpt-4o-mini-tts") => 12.0, // Approximation, billed per token upstream
+        _ => 15.0, // Default to tts-1 pricing
+    };
+
+    (characters as f64 / 1_000_000.0) * price
+}
+
+/// Calculate OpenAI Realtime cost from text and audio token counts
+pub fn calculate_realtime_cost(
+    model: &str,
+    input_text_tokens: u64,
+    input_audio_tokens: u64,
+    output_text_tokens: u64,
+    output_audio_tokens: u64,
+) -> f64 {
+    // Price per 1M tokens: (text in, text out, audio in, audio out).
+    // Cached input discounts are not applied
+    let (text_in, text_out, audio_in, audio_out) = match model {
+        m if m.starts_with("gpt-4o-mini-realtime") => (0.6, 2.4, 10.0, 20.0),
+        m if m.starts_with("gpt-realtime") => (4.0, 16.0, 32.0, 64.0),
+        _ => (5.0, 20.0, 40.0, 80.0), // gpt-4o-realtime-preview
+    };
+
+    (input_text_tokens as f64 * text_in
+        + output_text_tokens as f64 * text_out
+        + input_audio_tokens as f64 * audio_in
+        + output_audio_tokens as f64 * audio_out)
+        / 1_000_000.0
+}
+
+// OpenAI API request structures for speech
+#[derive(Debug, Serialize)]
+struct OpenAISpeechRequest {
+    model: String,
+    input: String,
+    voice: String,
+    response_format: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speed: Option<f32>,
+}
+
 // OpenAI API request structures for chat
 #[derive(Debug, Serialize)]
 struct OpenAIChatRequest {
     model: String,
-    messages: Vec<OpenAIChatMessage>,
+    messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<ChatTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<ToolChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parallel_tool_calls: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ChatResponseFormat>,
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -277,36 +796,68 @@ struct OpenAIChatRequest {
     frequency_penalty: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    n: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<StopSequences>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logprobs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_logprobs: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logit_bias: Option<HashMap<String, f32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_completion_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<ReasoningEffort>,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct OpenAIChatMessage {
-    role: String,
-    content: String,
-}
-
-// OpenAI API response structures for chat
-#[derive(Debug, Deserialize)]
-struct OpenAIChatResponse {
-    id: String,
-    created: i64,
-    model: String,
-    choices: Vec<OpenAIChatChoice>,
-    usage: OpenAIChatUsage,
-}
-
-#[derive(Debug, Deserialize)]
-struct OpenAIChatChoice {
-    index: u32,
-    message: OpenAIChatMessage,
-    finish_reason: Option<String>,
-}
+impl OpenAIChatRequest {
+    fn from_request(request: &ChatCompletionRequest, stream: bool) -> Self {
+        // Reasoning models reject sampling controls and count reasoning
+        // tokens against max_completion_tokens rather than max_tokens
+        let reasoning = is_reasoning_model(&request.model);
+        let sampling = |value: f32| (!reasoning).then_some(value);
+        let (max_tokens, max_completion_tokens) = if reasoning {
+            (None, request.max_completion_tokens.or(request.max_tokens))
+        } else {
+            (request.max_tokens, request.max_completion_tokens)
+        };
 
-#[derive(Debug, Deserialize)]
-struct OpenAIChatUsage {
-    prompt_tokens: u32,
-    completion_tokens: u32,
-    total_tokens: u32,
+        Self {
+            model: request.model.clone(),
+            messages: request.messages.clone(),
+            tools: request.tools.clone(),
+            tool_choice: request.tool_choice.clone(),
+            parallel_tool_calls: request.parallel_tool_calls,
+            response_format: request.response_format.clone(),
+            temperature: sampling(request.temperature),
+            max_tokens,
+            stream: Some(stream),
+            top_p: sampling(request.top_p),
+            frequency_penalty: sampling(request.frequency_penalty),
+            presence_penalty: sampling(request.presence_penalty),
+            n: request.n,
+            stop: request.stop.clone(),
+            seed: request.seed,
+            logprobs: request.logprobs,
+            top_logprobs: request.top_logprobs,
+            logit_bias: request.logit_bias.clone(),
+            user: request.user.clone(),
+            max_completion_tokens,
+            stream_options: request.stream_options.clone(),
+            reasoning_effort: request.reasoning_effort,
+            extra: request.extra.clone(),
+        }
+    }
 }
 
 // OpenAI API response structures for transcription
@@ -332,9 +883,101 @@ struct OpenAISegment {
     no_speech_prob: Option<f32>,
 }
 
+#[derive(Debug, Deserialize)]
+struct OpenAIDiarizedResponse {
+    text: String,
+    duration: Option<f32>,
+    #[serde(default)]
+    segments: Vec<OpenAIDiarizedSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIDiarizedSegment {
+    start: f32,
+    end: f32,
+    text: String,
+    speaker: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct OpenAIWord {
     word: String,
     start: f32,
     end: f32,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(body: serde_json::Value) -> ChatCompletionRequest {
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn translates_parameters_for_reasoning_models() {
+        let o3 = request(serde_json::json!({
+            "model": "o3-mini",
+            "messages": [{ "role": "user", "content": "Hi" }],
+            "max_tokens": 500,
+            "reasoning_effort": "low",
+            "seed": 7,
+            "vendor_flag": true
+        }));
+        assert!(check_chat_parameters(&o3).is_ok());
+
+        let body = serde_json::to_value(OpenAIChatRequest::from_request(&o3, false)).unwrap();
+        assert_eq!(body["max_completion_tokens"], 500);
+        assert_eq!(body["reasoning_effort"], "low");
+        assert_eq!(body["seed"], 7);
+        assert_eq!(body["vendor_flag"], true);
+        assert!(body.get("max_tokens").is_none());
+        assert!(body.get("temperature").is_none());
+
+        let gpt4 = request(serde_json::json!({
+            "model": "gpt-4o",
+            "messages": [{ "role": "user", "content": "Hi" }],
+            "reasoning_effort": "high"
+        }));
+        assert!(matches!(check_chat_parameters(&gpt4), Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn round_trips_upstream_response_fields() {
+        let upstream = serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1700000000,
+            "model": "gpt-4o-2024-08-06",
+            "system_fingerprint": "fp_abc",
+            "service_tier": "default",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "developer", "content": null, "refusal": "I can't help with that." },
+                "logprobs": { "content": [{ "token": "I", "logprob": -0.1, "bytes": [73], "top_logprobs": [] }], "refusal": null },
+                "finish_reason": "end_turn"
+            }],
+            "usage": {
+                "prompt_tokens": 20,
+                "completion_tokens": 10,
+                "total_tokens": 30,
+                "prompt_tokens_details": { "cached_tokens": 16 },
+                "completion_tokens_details": { "reasoning_tokens": 4 }
+            }
+        });
+
+        let response: ChatCompletionResponse = serde_json::from_value(upstream.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&response).unwrap(), upstream);
+    }
+
+    #[test]
+    fn prices_images_by_model_size_and_quality() {
+        assert_eq!(calculate_image_cost("dall-e-2", Some("256x256"), None, 2), 0.032);
+        assert_eq!(calculate_image_cost("dall-e-3", Some("1792x1024"), Some("hd"), 1), 0.12);
+        assert_eq!(calculate_image_cost("gpt-image-1", Some("1024x1536"), Some("high"), 1), 0.25);
+        assert_eq!(calculate_image_cost("gpt-image-1", None, None, 1), 0.042);
+
+        assert_eq!(image_content_type(b"\x89PNG\r\n\x1a\n"), Some("image/png"));
+        assert_eq!(image_content_type(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(image_content_type(b"GIF89a"), None);
+    }
+}
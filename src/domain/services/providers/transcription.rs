@@ -0,0 +1,177 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use reqwest::multipart::Part;
+
+use crate::domain::entities::transcription::{
+    ResponseFormat, TimestampGranularity, TranscriptionProviderKind, TranscriptionRequest,
+    TranscriptionResponse, TranscriptionSegment, TranscriptionUsage, TranscriptionWord,
+};
+use crate::shared::error::AppError;
+
+/// Transcription parameters shared by all providers, without the audio itself
+#[derive(Debug, Clone)]
+pub struct TranscriptionOptions {
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub prompt: Option<String>,
+    pub response_format: Option<ResponseFormat>,
+    pub temperature: Option<f32>,
+    pub timestamp_granularities: Option<Vec<TimestampGranularity>>,
+    pub diarize: bool,
+}
+
+impl TranscriptionOptions {
+    pub fn from_request(request: &TranscriptionRequest) -> Self {
+        Self {
+            model: request.model.clone(),
+            language: request.language.clone(),
+            prompt: request.prompt.clone(),
+            response_format: request.response_format.clone(),
+            temperature: request.temperature,
+            timestamp_granularities: request.timestamp_granularities.clone(),
+            diarize: request.diarize,
+        }
+    }
+
+    pub fn words_requested(&self) -> bool {
+        self.timestamp_granularities
+            .as_ref()
+            .is_some_and(|g| g.iter().any(|g| matches!(g, TimestampGranularity::Word)))
+    }
+}
+
+/// Speech-to-text backend normalising its output into `TranscriptionResponse`
+#[async_trait]
+pub trait TranscriptionProvider: Send + Sync {
+    fn kind(&self) -> TranscriptionProviderKind;
+
+    /// Model used when the request does not name one
+    fn default_model(&self) -> &'static str;
+
+    /// Whether the model returns segment timestamps (needed for subtitles
+    /// and for stitching overlapping chunks)
+    fn returns_timestamps(&self, _model: &str) -> bool {
+        true
+    }
+
+    /// Whether the model can label speakers
+    fn supports_diarization(&self, _model: &str) -> bool {
+        false
+    }
+
+    /// List price per audio minute, used for usage logging and pre-flight
+    /// cost estimates
+    fn price_per_minute(&self, model: &str) -> f64;
+
+    /// Largest upload accepted in a single request, if tighter than the
+    /// configured provider upload limit
+    fn max_upload_bytes(&self) -> Option<usize> {
+        None
+    }
+
+    async fn transcribe(
+        &self,
+        api_key: Option<&str>,
+        file_data: Bytes,
+        file_name: String,
+        options: &TranscriptionOptions,
+    ) -> Result<TranscriptionResponse, AppError>;
+}
+
+/// Require an API key for providers that cannot run without one
+pub(crate) fn require_key(
+    api_key: Option<&str>,
+    kind: TranscriptionProviderKind,
+) -> Result<&str, AppError> {
+    api_key.ok_or_else(|| {
+        AppError::ConfigError(format!("No API key configured for transcription provider: {}", kind))
+    })
+}
+
+/// Trim a fully timestamped response down to what the requested format
+/// would return from OpenAI, and attach usage priced per audio minute
+pub(crate) fn shape_response(
+    mut response: TranscriptionResponse,
+    options: &TranscriptionOptions,
+    price_per_minute: f64,
+) -> TranscriptionResponse {
+    match options.response_format {
+        Some(ResponseFormat::VerboseJson) => {
+            if !options.words_requested() {
+                response.words = None;
+            }
+        }
+        _ => {
+            response.segments = None;
+            response.words = None;
+        }
+    }
+
+    response.usage = response.duration.map(|duration| TranscriptionUsage {
+        audio_duration_seconds: duration,
+        tokens_used: None,
+        estimated_cost_usd: Some(duration as f64 / 60.0 * price_per_minute),
+    });
+
+    response
+}
+
+/// Multipart file part that streams the audio without copying it
+pub(crate) fn audio_part(file_data: Bytes, file_name: String) -> Part {
+    let len = file_data.len() as u64;
+    Part::stream_with_length(file_data, len).file_name(file_name)
+}
+
+/// Best-effort MIME type from the file name, for providers that need one
+pub(crate) fn audio_content_type(file_name: &str) -> &'static str {
+    let extension = file_name
+        .rsplit('.')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match extension.as_str() {
+        "wav" => "audio/wav",
+        "flac" => "audio/flac",
+        "mp3" | "mpga" | "mpeg" => "audio/mpeg",
+        "m4a" | "mp4" => "audio/mp4",
+        "ogg" | "oga" => "audio/ogg",
+        "opus" => "audio/opus",
+        "webm" => "audio/webm",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Letter label for a zero-based speaker index: `0` → `"A"`, `26` → `"S26"`
+pub(crate) fn speaker_label(index: usize) -> String {
+    if index < 26 {
+        char::from(b'A' + index as u8).to_string()
+    } else {
+        format!("S{}", index)
+    }
+}
+
+/// Label each segment with the speaker of most of its words
+pub(crate) fn assign_segment_speakers(
+    segments: &mut [TranscriptionSegment],
+    words: &[TranscriptionWord],
+) {
+    for segment in segments.iter_mut().filter(|s| s.speaker.is_none()) {
+        let mut counts: Vec<(&str, usize)> = Vec::new();
+        for word in words
+            .iter()
+            .filter(|w| w.start >= segment.start && w.start < segment.end)
+        {
+            let Some(speaker) = word.speaker.as_deref() else {
+                continue;
+            };
+            match counts.iter_mut().find(|(s, _)| *s == speaker) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((speaker, 1)),
+            }
+        }
+        segment.speaker = counts
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(speaker, _)| speaker.to_string());
+    }
+}
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::domain::entities::transcription::{TranscriptionProviderKind, TranscriptionResponse};
+use crate::shared::error::AppError;
+
+use super::openai::OpenAIProvider;
+use super::transcription::{TranscriptionOptions, TranscriptionProvider};
+
+/// Self-hosted Whisper server exposing the OpenAI transcription API
+/// (whisper.cpp server, faster-whisper-server, speaches)
+pub struct WhisperServerProvider {
+    inner: OpenAIProvider,
+}
+
+impl WhisperServerProvider {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            inner: OpenAIProvider::with_base_url(base_url),
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for WhisperServerProvider {
+    fn kind(&self) -> TranscriptionProviderKind {
+        TranscriptionProviderKind::WhisperServer
+    }
+
+    fn default_model(&self) -> &'static str {
+        "whisper-1"
+    }
+
+    /// Local inference has no per-minute charge
+    fn price_per_minute(&self, _model: &str) -> f64 {
+        0.0
+    }
+
+    async fn transcribe(
+        &self,
+        api_key: Option<&str>,
+        file_data: Bytes,
+        file_name: String,
+        options: &TranscriptionOptions,
+    ) -> Result<TranscriptionResponse, AppError> {
+        let mut response = OpenAIProvider::transcribe(
+            &self.inner,
+            api_key.unwrap_or_default(),
+            file_data,
+            file_name,
+            options.model.clone(),
+            options.language.clone(),
+            options.prompt.clone(),
+            options.response_format.clone(),
+            options.temperature,
+            options.timestamp_granularities.clone(),
+        )
+        .await?;
+
+        if let Some(usage) = response.usage.as_mut() {
+            usage.estimated_cost_usd = Some(0.0);
+        }
+
+        Ok(response)
+    }
+}
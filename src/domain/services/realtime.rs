@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::domain::entities::usage::{
+    ApiEndpoint, CostData, RequestMetadata, ResponseMetadata, UsageLog,
+};
+use crate::domain::entities::LlmProvider;
+use crate::domain::repositories::usage_repository::UsageRepository;
+use crate::domain::services::llm_api_key::LlmApiKeyService;
+use crate::domain::services::providers::openai::calculate_realtime_cost;
+use crate::shared::config::RealtimeConfig;
+use crate::shared::error::AppError;
+
+/// WebSocket connection to the upstream realtime API
+pub type UpstreamSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Realtime service brokering WebSocket sessions with the project's stored
+/// provider key, so clients never see it
+pub struct RealtimeService {
+    usage_repository: Arc<dyn UsageRepository>,
+    llm_key_service: Arc<LlmApiKeyService>,
+    config: RealtimeConfig,
+    active_sessions: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+/// An open realtime session, accumulating usage until it is closed
+pub struct RealtimeSession {
+    pub project_id: String,
+    pub model: String,
+    pub usage: RealtimeUsage,
+    started_at: Instant,
+    _slot: SessionSlot,
+}
+
+/// Holds one of the project's concurrent session slots until dropped
+struct SessionSlot {
+    project_id: String,
+    active_sessions: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl Drop for SessionSlot {
+    fn drop(&mut self) {
+        let mut active = self.active_sessions.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = active.get_mut(&self.project_id) {
+            *count -= 1;
+            if *count == 0 {
+                active.remove(&self.project_id);
+            }
+        }
+    }
+}
+
+impl RealtimeService {
+    pub fn new(
+        usage_repository: Arc<dyn UsageRepository>,
+        llm_key_service: Arc<LlmApiKeyService>,
+        config: RealtimeConfig,
+    ) -> Self {
+        Self {
+            usage_repository,
+            llm_key_service,
+            config,
+            active_sessions: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Longest a session may stay open
+    pub fn max_session_duration(&self) -> Duration {
+        Duration::from_secs(self.config.max_session_seconds)
+    }
+
+    /// Reserve a session slot and connect upstream with the project's key
+    pub async fn open(
+        &self,
+        project_id: String,
+        model: Option<String>,
+        llm_api_key_id: Option<String>,
+    ) -> Result<(RealtimeSession, UpstreamSocket), AppError> {
+        let model = model.unwrap_or_else(|| self.config.default_model.clone());
+        // The model is passed through in the upstream query string
+        if model.is_empty()
+            || !model
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'))
+        {
+            return Err(AppError::BadRequest(format!("Invalid realtime model: {}", model)));
+        }
+
+        let slot = self.reserve_slot(&project_id)?;
+
+        let provider = LlmProvider::Openai;
+        let api_key = if let Some(key_id) = &llm_api_key_id {
+            self.llm_key_service
+                .get_project_key(&project_id, key_id, Some(&provider))
+                .await?
+        } else {
+            self.llm_key_service
+                .get_default_key_for_provider(&project_id, &provider)
+                .await?
+                .ok_or_else(|| {
+                    AppError::ConfigError(format!(
+                        "No LLM API key configured for provider: {:?}",
+                        provider
+                    ))
+                })?
+        };
+
+        let url = format!("{}?model={}", self.config.upstream_url, model);
+        let mut request = url
+            .into_client_request()
+            .map_err(|e| AppError::ConfigError(format!("Invalid realtime upstream URL: {}", e)))?;
+        let headers = request.headers_mut();
+        headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Bearer {}", api_key))
+                .map_err(|_| AppError::ConfigError("Invalid LLM API key".to_string()))?,
+        );
+        headers.insert("OpenAI-Beta", HeaderValue::from_static("realtime=v1"));
+
+        let (upstream, _) = tokio_tungstenite::connect_async(request)
+            .await
+            .map_err(|e| AppError::ExternalApiError(format!("Realtime upstream connection failed: {}", e)))?;
+
+        tracing::info!("Opened realtime session for project {} ({})", project_id, model);
+
+        Ok((
+            RealtimeSession {
+                project_id,
+                model,
+                usage: RealtimeUsage::default(),
+                started_at: Instant::now(),
+                _slot: slot,
+            },
+            upstream,
+        ))
+    }
+
+    fn reserve_slot(&self, project_id: &str) -> Result<SessionSlot, AppError> {
+        let mut active = self.active_sessions.lock().unwrap_or_else(|e| e.into_inner());
+        let count = active.entry(project_id.to_string()).or_insert(0);
+        if *count >= self.config.max_sessions_per_project {
+            return Err(AppError::RateLimitError(format!(
+                "Project already has {} open realtime sessions",
+                count
+            )));
+        }
+        *count += 1;
+
+        Ok(SessionSlot {
+            project_id: project_id.to_string(),
+            active_sessions: self.active_sessions.clone(),
+        })
+    }
+
+    /// Release the session and log its usage
+    pub fn close(&self, session: RealtimeSession, close_reason: &str) {
+        let duration = session.started_at.elapsed();
+        let usage = &session.usage;
+        let cost_usd = calculate_realtime_cost(
+            &session.model,
+            usage.input_text_tokens,
+            usage.input_audio_tokens,
+            usage.output_text_tokens,
+            usage.output_audio_tokens,
+        );
+        let input_tokens = usage.input_text_tokens + usage.input_audio_tokens;
+        let output_tokens = usage.output_text_tokens + usage.output_audio_tokens;
+
+        tracing::info!(
+            "Closed realtime session for project {} after {:.1}s ({}): {} input / {} output tokens",
+            session.project_id,
+            duration.as_secs_f32(),
+            close_reason,
+            input_tokens,
+            output_tokens
+        );
+
+        let log = UsageLog::new(
+            session.project_id.clone(),
+            ApiEndpoint::Realtime,
+            LlmProvider::Openai,
+            session.model.clone(),
+            RequestMetadata {
+                request_id: uuid::Uuid::new_v4().to_string(),
+                method: "GET".to_string(),
+                path: "/v1/realtime".to_string(),
+                ip_address: None,
+                user_agent: None,
+                prompt_tokens: Some(input_tokens as i32),
+                audio_duration_seconds: Some(usage.input_audio_seconds()),
+                file_size_bytes: None,
+                temperature: None,
+                max_tokens: None,
+                stream: true,
+            },
+            ResponseMetadata {
+                status_code: 101,
+                latency_ms: duration.as_millis() as u64,
+                provider_latency_ms: None,
+                completion_tokens: Some(output_tokens as i32),
+                total_tokens: Some((input_tokens + output_tokens) as i32),
+                finish_reason: Some(close_reason.to_string()),
+            },
+            CostData {
+                prompt_cost_usd: None,
+                completion_cost_usd: None,
+                audio_cost_usd: Some(cost_usd),
+                total_cost_usd: cost_usd,
+                cached_savings_usd: None,
+            },
+            None,
+            None,
+        );
+
+        // Log in background
+        let repo = self.usage_repository.clone();
+        tokio::spawn(async move {
+            if let Err(e) = repo.create(&log).await {
+                tracing::error!("Failed to log realtime usage: {}", e);
+            }
+        });
+    }
+}
+
+/// Usage observed on the wire during a realtime session
+#[derive(Debug, Default)]
+pub struct RealtimeUsage {
+    pub input_text_tokens: u64,
+    pub input_audio_tokens: u64,
+    pub output_text_tokens: u64,
+    pub output_audio_tokens: u64,
+    input_audio_bytes: u64,
+    /// Set from `session.update`; PCM16 at 24kHz unless G.711 is chosen
+    g711_input: bool,
+}
+
+impl RealtimeUsage {
+    /// Account for an event sent by the client
+    pub fn observe_client_event(&mut self, text: &str) {
+        // Only parse the few events that matter; audio appends dominate traffic
+        if text.contains("\"input_audio_buffer.append\"") {
+            if let Ok(event) = serde_json::from_str::<AudioAppendEvent>(text) {
+                // Avoid decoding: base64 carries 3 bytes per 4 characters
+                let padding = event.audio.bytes().rev().take_while(|b| *b == b'=').count();
+                self.input_audio_bytes += (event.audio.len() / 4 * 3).saturating_sub(padding) as u64;
+            }
+        } else if text.contains("\"session.update\"") {
+            if let Ok(event) = serde_json::from_str::<SessionUpdateEvent>(text) {
+                if let Some(format) = event.session.input_audio_format {
+                    self.g711_input = format.starts_with("g711");
+                }
+            }
+        }
+    }
+
+    /// Account for an event sent by the upstream
+    pub fn observe_server_event(&mut self, text: &str) {
+        if !text.contains("\"response.done\"") {
+            return;
+        }
+        let Ok(event) = serde_json::from_str::<ResponseDoneEvent>(text) else {
+            return;
+        };
+        let Some(usage) = event.response.usage else {
+            return;
+        };
+
+        match usage.input_token_details {
+            Some(details) => {
+                self.input_text_tokens += details.text_tokens;
+                self.input_audio_tokens += details.audio_tokens;
+            }
+            None => self.input_text_tokens += usage.input_tokens,
+        }
+        match usage.output_token_details {
+            Some(details) => {
+                self.output_text_tokens += details.text_tokens;
+                self.output_audio_tokens += details.audio_tokens;
+            }
+            None => self.output_text_tokens += usage.output_tokens,
+        }
+    }
+
+    /// Seconds of audio streamed in by the client
+    pub fn input_audio_seconds(&self) -> f32 {
+        let bytes_per_second = if self.g711_input { 8000.0 } else { 48000.0 };
+        self.input_audio_bytes as f32 / bytes_per_second
+    }
+}
+
+// Realtime API event structures (only the fields used for accounting)
+#[derive(Debug, Deserialize)]
+struct AudioAppendEvent {
+    audio: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionUpdateEvent {
+    session: SessionUpdate,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionUpdate {
+    input_audio_format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseDoneEvent {
+    response: RealtimeResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct RealtimeResponse {
+    usage: Option<RealtimeResponseUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RealtimeResponseUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+    input_token_details: Option<RealtimeTokenDetails>,
+    output_token_details: Option<RealtimeTokenDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RealtimeTokenDetails {
+    #[serde(default)]
+    text_tokens: u64,
+    #[serde(default)]
+    audio_tokens: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+
+    use super::*;
+
+    #[test]
+    fn accumulates_usage_from_events() {
+        let mut usage = RealtimeUsage::default();
+
+        // One second of PCM16 at 24kHz
+        let audio = BASE64.encode(vec![0u8; 48000]);
+        usage.observe_client_event(&format!(
+            r#"{{"type":"input_audio_buffer.append","audio":"{}"}}"#,
+            audio
+        ));
+        usage.observe_server_event(
+            r#"{"type":"response.done","response":{"usage":{"input_tokens":120,"output_tokens":80,
+            "input_token_details":{"text_tokens":20,"audio_tokens":100},
+            "output_token_details":{"text_tokens":30,"audio_tokens":50}}}}"#,
+        );
+        usage.observe_server_event(r#"{"type":"response.audio.delta","delta":"AAAA"}"#);
+
+        assert!((usage.input_audio_seconds() - 1.0).abs() < 0.001);
+        assert_eq!(usage.input_text_tokens, 20);
+        assert_eq!(usage.input_audio_tokens, 100);
+        assert_eq!(usage.output_text_tokens, 30);
+        assert_eq!(usage.output_audio_tokens, 50);
+    }
+}
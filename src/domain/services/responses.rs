@@ -0,0 +1,799 @@
+//! Translation between the OpenAI Responses API and the gateway's chat
+//! completions model, so `/v1/responses` reaches every chat backend. The
+//! conversation is kept with each stored response, which is what lets
+//! `previous_response_id` work regardless of the provider that served it.
+
+use std::collections::HashMap;
+
+use crate::api::dto::{
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatContent, ChatMessage,
+    ChatResponseFormat, ChatRole, ChatTool, ChatUsage, ContentPart, FinishReason, FunctionCall,
+    FunctionDefinition, ImageUrl, IncompleteDetails, InputContentPart, InputItem,
+    InputItemContent, InputItemMessage, InputTokensDetails, JsonSchemaFormat, NamedToolChoice,
+    OutputContent, OutputItem, OutputTokensDetails, ResponseEvent, ResponseInput, ResponseObject,
+    ResponseStreamEvent, ResponseTextFormat, ResponsesRequest, ResponsesTool, ResponsesToolChoice,
+    ResponsesTruncation, ResponsesUsage, StreamOptions, ToolCall, ToolChoice, ToolChoiceFunction,
+    ToolType, TruncationStrategy,
+};
+use crate::shared::error::AppError;
+
+/// Fields of the request echoed on the response
+#[derive(Debug, Clone)]
+pub struct ResponseContext {
+    pub id: String,
+    pub created_at: i64,
+    pub model: String,
+    pub instructions: Option<String>,
+    pub previous_response_id: Option<String>,
+    pub metadata: HashMap<String, String>,
+    pub store: bool,
+}
+
+impl ResponseContext {
+    fn response(&self, model: String, status: &str) -> ResponseObject {
+        ResponseObject {
+            id: self.id.clone(),
+            object: "response".to_string(),
+            created_at: self.created_at,
+            status: status.to_string(),
+            model,
+            output: Vec::new(),
+            instructions: self.instructions.clone(),
+            previous_response_id: self.previous_response_id.clone(),
+            incomplete_details: None,
+            usage: None,
+            metadata: self.metadata.clone(),
+            store: self.store,
+        }
+    }
+}
+
+/// A Responses request translated to chat completions
+pub struct PreparedResponse {
+    pub chat_request: ChatCompletionRequest,
+    pub context: ResponseContext,
+    /// Earlier conversation plus this request's input, without instructions
+    pub conversation: Vec<ChatMessage>,
+}
+
+/// Build the equivalent chat completion request, continuing `history` (the
+/// conversation of `previous_response_id`, if any)
+pub fn prepare(request: ResponsesRequest, history: Vec<ChatMessage>) -> Result<PreparedResponse, AppError> {
+    let mut conversation = history;
+    match request.input {
+        ResponseInput::Text(text) => conversation.push(text_message(ChatRole::User, text)),
+        ResponseInput::Items(items) => {
+            for item in items {
+                push_item(&mut conversation, item)?;
+            }
+        }
+    }
+
+    let mut messages = Vec::new();
+    if let Some(instructions) = &request.instructions {
+        messages.push(text_message(ChatRole::System, instructions.clone()));
+    }
+    messages.extend(conversation.iter().cloned());
+
+    let tools = request
+        .tools
+        .map(|tools| {
+            tools
+                .into_iter()
+                .map(|tool| match tool {
+                    ResponsesTool::Function { name, description, parameters, strict } => Ok(ChatTool {
+                        r#type: ToolType::Function,
+                        function: FunctionDefinition { name, description, parameters, strict },
+                    }),
+                    ResponsesTool::Other(tool) => Err(AppError::BadRequest(format!(
+                        "Unsupported tool type: {}",
+                        tool.get("type").and_then(|t| t.as_str()).unwrap_or("unknown")
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+
+    let tool_choice = request.tool_choice.map(|choice| match choice {
+        ResponsesToolChoice::Mode(mode) => ToolChoice::Mode(mode),
+        ResponsesToolChoice::Function { r#type, name } => ToolChoice::Function(NamedToolChoice {
+            r#type,
+            function: ToolChoiceFunction { name },
+        }),
+    });
+
+    let response_format = request.text.and_then(|text| text.format).and_then(|format| match format {
+        ResponseTextFormat::Text => None,
+        ResponseTextFormat::JsonObject => Some(ChatResponseFormat::JsonObject),
+        ResponseTextFormat::JsonSchema { name, description, schema, strict } => {
+            Some(ChatResponseFormat::JsonSchema {
+                json_schema: JsonSchemaFormat {
+                    name,
+                    description,
+                    schema: Some(schema),
+                    strict,
+                },
+            })
+        }
+    });
+
+    let context = ResponseContext {
+        id: format!("resp_{}", uuid::Uuid::new_v4().simple()),
+        created_at: chrono::Utc::now().timestamp(),
+        model: request.model.clone(),
+        instructions: request.instructions,
+        previous_response_id: request.previous_response_id,
+        metadata: request.metadata.unwrap_or_default(),
+        store: request.store.unwrap_or(true),
+    };
+
+    let chat_request = ChatCompletionRequest {
+        model: request.model,
+        messages,
+        temperature: request.temperature.unwrap_or(1.0),
+        max_tokens: None,
+        stream: request.stream,
+        top_p: request.top_p.unwrap_or(1.0),
+        frequency_penalty: 0.0,
+        presence_penalty: 0.0,
+        tools,
+        tool_choice,
+        parallel_tool_calls: request.parallel_tool_calls,
+        response_format,
+        n: None,
+        stop: None,
+        seed: None,
+        logprobs: None,
+        top_logprobs: None,
+        logit_bias: None,
+        user: request.user,
+        // max_output_tokens includes reasoning tokens
+        max_completion_tokens: request.max_output_tokens,
+        // Usage is reported on response.completed
+        stream_options: request.stream.then_some(StreamOptions {
+            include_usage: Some(true),
+        }),
+        reasoning_effort: request.reasoning.and_then(|reasoning| reasoning.effort),
+        truncation: (request.truncation == Some(ResponsesTruncation::Auto))
+            .then_some(TruncationStrategy::DropOldest),
+        extra: Default::default(),
+    };
+
+    Ok(PreparedResponse {
+        chat_request,
+        context,
+        conversation,
+    })
+}
+
+fn text_message(role: ChatRole, text: String) -> ChatMessage {
+    ChatMessage {
+        role,
+        content: Some(ChatContent::Text(text)),
+        name: None,
+        tool_calls: None,
+        tool_call_id: None,
+        refusal: None,
+    }
+}
+
+fn push_item(messages: &mut Vec<ChatMessage>, item: InputItem) -> Result<(), AppError> {
+    match item {
+        InputItem::Message(message) | InputItem::EasyMessage(message) => {
+            messages.push(input_message(message)?)
+        }
+        InputItem::FunctionCall { call_id, name, arguments } => {
+            let call = ToolCall {
+                id: call_id,
+                r#type: ToolType::Function,
+                function: FunctionCall { name, arguments },
+            };
+            // Parallel calls are separate items but one assistant message
+            match messages.last_mut() {
+                Some(last) if matches!(last.role, ChatRole::Assistant) => {
+                    last.tool_calls.get_or_insert_with(Vec::new).push(call)
+                }
+                _ => messages.push(ChatMessage {
+                    role: ChatRole::Assistant,
+                    content: None,
+                    name: None,
+                    tool_calls: Some(vec![call]),
+                    tool_call_id: None,
+                    refusal: None,
+                }),
+            }
+        }
+        InputItem::FunctionCallOutput { call_id, output } => messages.push(ChatMessage {
+            role: ChatRole::Tool,
+            content: Some(ChatContent::Text(output)),
+            name: None,
+            tool_calls: None,
+            tool_call_id: Some(call_id),
+            refusal: None,
+        }),
+    }
+
+    Ok(())
+}
+
+fn input_message(message: InputItemMessage) -> Result<ChatMessage, AppError> {
+    let role = match message.role.as_str() {
+        "user" => ChatRole::User,
+        "assistant" => ChatRole::Assistant,
+        "system" | "developer" => ChatRole::System,
+        role => return Err(AppError::BadRequest(format!("Unsupported message role: {}", role))),
+    };
+
+    let parts = match message.content {
+        InputItemContent::Text(text) => return Ok(text_message(role, text)),
+        InputItemContent::Parts(parts) => parts,
+    };
+
+    let mut content = Vec::new();
+    for part in parts {
+        content.push(match part {
+            InputContentPart::InputText { text } | InputContentPart::OutputText { text } => {
+                ContentPart::Text { text }
+            }
+            InputContentPart::Refusal { refusal } => ContentPart::Text { text: refusal },
+            InputContentPart::InputImage { image_url: Some(url), detail, .. } => {
+                ContentPart::ImageUrl {
+                    image_url: ImageUrl { url, detail },
+                }
+            }
+            InputContentPart::InputImage { .. } => {
+                return Err(AppError::BadRequest(
+                    "input_image requires image_url; file_id is not supported".to_string(),
+                ))
+            }
+        });
+    }
+
+    // Only user messages may carry images upstream; other roles take plain text
+    if !matches!(role, ChatRole::User) {
+        let text = content
+            .into_iter()
+            .filter_map(|part| match part {
+                ContentPart::Text { text } => Some(text),
+                _ => None,
+            })
+            .collect();
+        return Ok(text_message(role, text));
+    }
+
+    Ok(ChatMessage {
+        role,
+        content: Some(ChatContent::Parts(content)),
+        name: None,
+        tool_calls: None,
+        tool_call_id: None,
+        refusal: None,
+    })
+}
+
+/// The output as the assistant message that continues the conversation
+pub fn output_message(output: &[OutputItem]) -> Option<ChatMessage> {
+    let mut text = String::new();
+    let mut refusal = None;
+    let mut tool_calls = Vec::new();
+    for item in output {
+        match item {
+            OutputItem::Message { content, .. } => {
+                for part in content {
+                    match part {
+                        OutputContent::OutputText { text: part, .. } => text.push_str(part),
+                        OutputContent::Refusal { refusal: part } => refusal = Some(part.clone()),
+                    }
+                }
+            }
+            OutputItem::FunctionCall { call_id, name, arguments, .. } => tool_calls.push(ToolCall {
+                id: call_id.clone(),
+                r#type: ToolType::Function,
+                function: FunctionCall {
+                    name: name.clone(),
+                    arguments: arguments.clone(),
+                },
+            }),
+        }
+    }
+
+    if text.is_empty() && refusal.is_none() && tool_calls.is_empty() {
+        return None;
+    }
+    Some(ChatMessage {
+        role: ChatRole::Assistant,
+        content: (!text.is_empty()).then_some(ChatContent::Text(text)),
+        name: None,
+        tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+        tool_call_id: None,
+        refusal,
+    })
+}
+
+fn item_id(prefix: &str) -> String {
+    format!("{}_{}", prefix, uuid::Uuid::new_v4().simple())
+}
+
+/// Response status for a finish reason
+fn status(reason: Option<&FinishReason>) -> (&'static str, Option<IncompleteDetails>) {
+    let incomplete = |reason: &str| {
+        (
+            "incomplete",
+            Some(IncompleteDetails {
+                reason: reason.to_string(),
+            }),
+        )
+    };
+    match reason {
+        Some(FinishReason::Length) => incomplete("max_output_tokens"),
+        Some(FinishReason::ContentFilter) => incomplete("content_filter"),
+        _ => ("completed", None),
+    }
+}
+
+fn usage(usage: &ChatUsage) -> ResponsesUsage {
+    ResponsesUsage {
+        input_tokens: usage.prompt_tokens,
+        input_tokens_details: InputTokensDetails {
+            cached_tokens: usage
+                .prompt_tokens_details
+                .as_ref()
+                .and_then(|details| details.cached_tokens)
+                .unwrap_or(0),
+        },
+        output_tokens: usage.completion_tokens,
+        output_tokens_details: OutputTokensDetails {
+            reasoning_tokens: usage
+                .completion_tokens_details
+                .as_ref()
+                .and_then(|details| details.reasoning_tokens)
+                .unwrap_or(0),
+        },
+        total_tokens: usage.total_tokens,
+    }
+}
+
+/// Build the response object from a chat completion
+pub fn from_chat_response(context: &ResponseContext, response: ChatCompletionResponse) -> ResponseObject {
+    let choice = response.choices.into_iter().next();
+    let (status, incomplete_details) = status(choice.as_ref().and_then(|c| c.finish_reason.as_ref()));
+
+    let mut output = Vec::new();
+    if let Some(choice) = choice {
+        let message = choice.message;
+        let mut content = Vec::new();
+        let text = match message.content {
+            Some(ChatContent::Text(text)) => text,
+            Some(ChatContent::Parts(parts)) => parts
+                .into_iter()
+                .filter_map(|part| match part {
+                    ContentPart::Text { text } => Some(text),
+                    _ => None,
+                })
+                .collect(),
+            None => String::new(),
+        };
+        if !text.is_empty() {
+            content.push(OutputContent::OutputText {
+                text,
+                annotations: Vec::new(),
+            });
+        }
+        if let Some(refusal) = message.refusal {
+            content.push(OutputContent::Refusal { refusal });
+        }
+        if !content.is_empty() {
+            output.push(OutputItem::Message {
+                id: item_id("msg"),
+                role: "assistant".to_string(),
+                status: "completed".to_string(),
+                content,
+            });
+        }
+        for call in message.tool_calls.into_iter().flatten() {
+            output.push(OutputItem::FunctionCall {
+                id: item_id("fc"),
+                call_id: call.id,
+                name: call.function.name,
+                arguments: call.function.arguments,
+                status: "completed".to_string(),
+            });
+        }
+    }
+
+    ResponseObject {
+        output,
+        incomplete_details,
+        usage: Some(usage(&response.usage)),
+        ..context.response(response.model, status)
+    }
+}
+
+/// Text message item being streamed
+struct TextItem {
+    output_index: u32,
+    id: String,
+    text: String,
+}
+
+/// Function call item being streamed
+struct CallItem {
+    index: u32,
+    output_index: u32,
+    id: String,
+    call_id: String,
+    name: String,
+    arguments: String,
+}
+
+/// Converts a chat completion chunk stream into Responses stream events
+pub struct ResponseStream {
+    context: ResponseContext,
+    response: Option<ResponseObject>,
+    sequence_number: u64,
+    text: Option<TextItem>,
+    refusal: String,
+    calls: Vec<CallItem>,
+    finish_reason: Option<FinishReason>,
+    usage: Option<ResponsesUsage>,
+}
+
+impl ResponseStream {
+    pub fn new(context: ResponseContext) -> Self {
+        Self {
+            context,
+            response: None,
+            sequence_number: 0,
+            text: None,
+            refusal: String::new(),
+            calls: Vec::new(),
+            finish_reason: None,
+            usage: None,
+        }
+    }
+
+    /// The response as streamed so far; complete once `finish` has run
+    pub fn response(&self) -> Option<&ResponseObject> {
+        self.response.as_ref()
+    }
+
+    /// Events for one upstream chunk
+    pub fn push(&mut self, chunk: ChatCompletionChunk) -> Vec<ResponseStreamEvent> {
+        let mut events = Vec::new();
+
+        if self.response.is_none() {
+            let response = self.context.response(chunk.model.clone(), "in_progress");
+            self.emit(&mut events, ResponseEvent::Created { response: response.clone() });
+            self.emit(&mut events, ResponseEvent::InProgress { response: response.clone() });
+            self.response = Some(response);
+        }
+
+        if let Some(chunk_usage) = &chunk.usage {
+            self.usage = Some(usage(chunk_usage));
+        }
+
+        // Only the first choice is relayed; Responses has no `n`
+        let Some(choice) = chunk.choices.into_iter().find(|c| c.index == 0) else {
+            return events;
+        };
+
+        if let Some(refusal) = choice.delta.refusal {
+            self.refusal.push_str(&refusal);
+        }
+
+        if let Some(delta) = choice.delta.content.filter(|text| !text.is_empty()) {
+            if self.text.is_none() {
+                let output_index = self.next_output_index();
+                let item = TextItem {
+                    output_index,
+                    id: item_id("msg"),
+                    text: String::new(),
+                };
+                self.emit(&mut events, ResponseEvent::OutputItemAdded {
+                    output_index,
+                    item: message_item(&item.id, "in_progress", Vec::new()),
+                });
+                self.emit(&mut events, ResponseEvent::ContentPartAdded {
+                    item_id: item.id.clone(),
+                    output_index,
+                    content_index: 0,
+                    part: OutputContent::OutputText {
+                        text: String::new(),
+                        annotations: Vec::new(),
+                    },
+                });
+                self.text = Some(item);
+            }
+            if let Some(item) = self.text.as_mut() {
+                item.text.push_str(&delta);
+                let event = ResponseEvent::OutputTextDelta {
+                    item_id: item.id.clone(),
+                    output_index: item.output_index,
+                    content_index: 0,
+                    delta,
+                };
+                self.emit(&mut events, event);
+            }
+        }
+
+        for call in choice.delta.tool_calls.into_iter().flatten() {
+            let position = match self.calls.iter().position(|item| item.index == call.index) {
+                Some(position) => position,
+                None => {
+                    // Text that preceded the calls is complete
+                    self.close_text(&mut events);
+                    let output_index = self.next_output_index();
+                    let item = CallItem {
+                        index: call.index,
+                        output_index,
+                        id: item_id("fc"),
+                        call_id: call.id.clone().unwrap_or_default(),
+                        name: call.function.as_ref().and_then(|f| f.name.clone()).unwrap_or_default(),
+                        arguments: String::new(),
+                    };
+                    self.emit(&mut events, ResponseEvent::OutputItemAdded {
+                        output_index,
+                        item: call_item(&item, "in_progress"),
+                    });
+                    self.calls.push(item);
+                    self.calls.len() - 1
+                }
+            };
+            if let Some(delta) = call.function.and_then(|f| f.arguments).filter(|a| !a.is_empty()) {
+                let item = &mut self.calls[position];
+                item.arguments.push_str(&delta);
+                let event = ResponseEvent::FunctionCallArgumentsDelta {
+                    item_id: item.id.clone(),
+                    output_index: item.output_index,
+                    delta,
+                };
+                self.emit(&mut events, event);
+            }
+        }
+
+        if let Some(reason) = choice.finish_reason {
+            self.finish_reason = Some(reason);
+        }
+
+        events
+    }
+
+    /// Closing events once the upstream stream has ended
+    pub fn finish(&mut self) -> Vec<ResponseStreamEvent> {
+        let mut events = Vec::new();
+        self.close_text(&mut events);
+
+        for item in std::mem::take(&mut self.calls) {
+            self.emit(&mut events, ResponseEvent::FunctionCallArgumentsDone {
+                item_id: item.id.clone(),
+                output_index: item.output_index,
+                arguments: item.arguments.clone(),
+            });
+            let done = call_item(&item, "completed");
+            self.push_output(done.clone());
+            self.emit(&mut events, ResponseEvent::OutputItemDone {
+                output_index: item.output_index,
+                item: done,
+            });
+        }
+
+        let (status, incomplete_details) = status(self.finish_reason.as_ref());
+        let context = &self.context;
+        let response = self
+            .response
+            .get_or_insert_with(|| context.response(context.model.clone(), status));
+        response.status = status.to_string();
+        response.incomplete_details = incomplete_details;
+        response.usage = self.usage.take();
+
+        let response = response.clone();
+        let event = match status {
+            "incomplete" => ResponseEvent::Incomplete { response },
+            _ => ResponseEvent::Completed { response },
+        };
+        self.emit(&mut events, event);
+        events
+    }
+
+    /// Event reporting an upstream error in-band
+    pub fn error(&mut self, code: &str, message: String) -> ResponseStreamEvent {
+        self.sequenced(ResponseEvent::Error {
+            code: code.to_string(),
+            message,
+        })
+    }
+
+    fn sequenced(&mut self, event: ResponseEvent) -> ResponseStreamEvent {
+        self.sequence_number += 1;
+        ResponseStreamEvent {
+            sequence_number: self.sequence_number - 1,
+            event,
+        }
+    }
+
+    fn emit(&mut self, events: &mut Vec<ResponseStreamEvent>, event: ResponseEvent) {
+        let event = self.sequenced(event);
+        events.push(event);
+    }
+
+    fn next_output_index(&self) -> u32 {
+        let closed = self.response.as_ref().map_or(0, |r| r.output.len());
+        (closed + self.text.iter().count() + self.calls.len()) as u32
+    }
+
+    fn push_output(&mut self, item: OutputItem) {
+        if let Some(response) = self.response.as_mut() {
+            response.output.push(item);
+        }
+    }
+
+    /// Complete the open text item, adding any refusal to it
+    fn close_text(&mut self, events: &mut Vec<ResponseStreamEvent>) {
+        let refusal = std::mem::take(&mut self.refusal);
+        let item = match self.text.take() {
+            Some(item) => item,
+            None if refusal.is_empty() => return,
+            // A refusal without text still needs a message item
+            None => {
+                let item = TextItem {
+                    output_index: self.next_output_index(),
+                    id: item_id("msg"),
+                    text: String::new(),
+                };
+                self.emit(events, ResponseEvent::OutputItemAdded {
+                    output_index: item.output_index,
+                    item: message_item(&item.id, "in_progress", Vec::new()),
+                });
+                item
+            }
+        };
+
+        let mut content = Vec::new();
+        if !item.text.is_empty() {
+            let part = OutputContent::OutputText {
+                text: item.text.clone(),
+                annotations: Vec::new(),
+            };
+            self.emit(events, ResponseEvent::OutputTextDone {
+                item_id: item.id.clone(),
+                output_index: item.output_index,
+                content_index: 0,
+                text: item.text,
+            });
+            self.emit(events, ResponseEvent::ContentPartDone {
+                item_id: item.id.clone(),
+                output_index: item.output_index,
+                content_index: 0,
+                part: part.clone(),
+            });
+            content.push(part);
+        }
+        if !refusal.is_empty() {
+            content.push(OutputContent::Refusal { refusal });
+        }
+
+        let done = message_item(&item.id, "completed", content);
+        self.push_output(done.clone());
+        self.emit(events, ResponseEvent::OutputItemDone {
+            output_index: item.output_index,
+            item: done,
+        });
+    }
+}
+
+fn message_item(id: &str, status: &str, content: Vec<OutputContent>) -> OutputItem {
+    OutputItem::Message {
+        id: id.to_string(),
+        role: "assistant".to_string(),
+        status: status.to_string(),
+        content,
+    }
+}
+
+fn call_item(item: &CallItem, status: &str) -> OutputItem {
+    OutputItem::FunctionCall {
+        id: item.id.clone(),
+        call_id: item.call_id.clone(),
+        name: item.name.clone(),
+        arguments: item.arguments.clone(),
+        status: status.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn continues_stored_conversation() {
+        let first: ResponsesRequest = serde_json::from_value(json!({
+            "model": "gpt-4o",
+            "instructions": "Be brief.",
+            "input": "Weather in Paris?",
+            "tools": [{ "type": "function", "name": "get_weather", "parameters": { "type": "object" } }]
+        }))
+        .unwrap();
+        let prepared = prepare(first, Vec::new()).unwrap();
+        assert_eq!(prepared.chat_request.messages.len(), 2);
+
+        let mut conversation = prepared.conversation;
+        conversation.extend(output_message(&[OutputItem::FunctionCall {
+            id: "fc_1".to_string(),
+            call_id: "call_1".to_string(),
+            name: "get_weather".to_string(),
+            arguments: r#"{"city":"Paris"}"#.to_string(),
+            status: "completed".to_string(),
+        }]));
+
+        let second: ResponsesRequest = serde_json::from_value(json!({
+            "model": "gpt-4o",
+            "previous_response_id": "resp_1",
+            "input": [
+                { "type": "function_call_output", "call_id": "call_1", "output": "18C" },
+                { "role": "user", "content": [{ "type": "input_text", "text": "Thanks" }] }
+            ]
+        }))
+        .unwrap();
+        let prepared = prepare(second, conversation).unwrap();
+        assert!(prepared.chat_request.validate().is_ok());
+        let roles: Vec<_> = prepared
+            .chat_request
+            .messages
+            .iter()
+            .map(|m| serde_json::to_value(&m.role).unwrap())
+            .collect();
+        // Instructions are not carried over from the previous response
+        assert_eq!(roles, ["user", "assistant", "tool", "user"]);
+        assert_eq!(prepared.chat_request.messages[2].tool_call_id.as_deref(), Some("call_1"));
+    }
+
+    #[test]
+    fn streams_items_in_order() {
+        let chunk = |delta: serde_json::Value, finish: Option<&str>| -> ChatCompletionChunk {
+            serde_json::from_value(json!({
+                "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4o",
+                "choices": [{ "index": 0, "delta": delta, "finish_reason": finish }]
+            }))
+            .unwrap()
+        };
+        let context = prepare(
+            serde_json::from_value(json!({ "model": "gpt-4o", "input": "Hi", "stream": true })).unwrap(),
+            Vec::new(),
+        )
+        .unwrap()
+        .context;
+
+        let mut stream = ResponseStream::new(context);
+        let mut events = stream.push(chunk(json!({ "content": "Checking" }), None));
+        events.extend(stream.push(chunk(json!({ "tool_calls": [{
+            "index": 0, "id": "call_1", "type": "function",
+            "function": { "name": "get_weather", "arguments": "{}" }
+        }]}), Some("tool_calls"))));
+        events.extend(stream.finish());
+
+        let types: Vec<_> = events.iter().map(|e| e.event.event_type()).collect();
+        assert_eq!(
+            types,
+            [
+                "response.created",
+                "response.in_progress",
+                "response.output_item.added",
+                "response.content_part.added",
+                "response.output_text.delta",
+                "response.output_text.done",
+                "response.content_part.done",
+                "response.output_item.done",
+                "response.output_item.added",
+                "response.function_call_arguments.delta",
+                "response.function_call_arguments.done",
+                "response.output_item.done",
+                "response.completed"
+            ]
+        );
+        assert!(events.iter().enumerate().all(|(i, e)| e.sequence_number == i as u64));
+        let response = stream.response().unwrap();
+        assert_eq!(response.output.len(), 2);
+        assert_eq!(response.status, "completed");
+    }
+}
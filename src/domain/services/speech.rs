@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::domain::entities::speech::SpeechRequest;
+use crate::domain::entities::usage::{
+    ApiEndpoint, CostData, RequestMetadata, ResponseMetadata, UsageLog,
+};
+use crate::domain::entities::LlmProvider;
+use crate::domain::repositories::usage_repository::UsageRepository;
+use crate::domain::services::llm_api_key::LlmApiKeyService;
+use crate::domain::services::providers::openai::calculate_speech_cost;
+use crate::domain::services::providers::OpenAIProvider;
+use crate::shared::error::AppError;
+
+/// Speech service orchestrating text-to-speech workflow
+pub struct SpeechService {
+    usage_repository: Arc<dyn UsageRepository>,
+    llm_key_service: Arc<LlmApiKeyService>,
+    openai_provider: OpenAIProvider,
+}
+
+impl SpeechService {
+    pub fn new(
+        usage_repository: Arc<dyn UsageRepository>,
+        llm_key_service: Arc<LlmApiKeyService>,
+        base_url: Option<String>,
+    ) -> Self {
+        Self {
+            usage_repository,
+            llm_key_service,
+            openai_provider: base_url
+                .map(OpenAIProvider::with_base_url)
+                .unwrap_or_else(OpenAIProvider::new),
+        }
+    }
+
+    /// Synthesize speech, returning the upstream response for streaming
+    pub async fn synthesize(
+        &self,
+        project_id: String,
+        request: SpeechRequest,
+    ) -> Result<reqwest::Response, AppError> {
+        let start_time = Instant::now();
+
+        let provider = LlmProvider::Openai;
+
+        // Get LLM API key
+        let api_key = if let Some(key_id) = &request.llm_api_key_id {
+            self.llm_key_service
+                .get_project_key(&project_id, key_id, Some(&provider))
+                .await?
+        } else {
+            self.llm_key_service
+                .get_default_key_for_provider(&project_id, &provider)
+                .await?
+                .ok_or_else(|| {
+                    AppError::ConfigError(format!(
+                        "No LLM API key configured for provider: {:?}",
+                        provider
+                    ))
+                })?
+        };
+
+        let response = self.openai_provider.speech(&api_key, &request).await?;
+
+        // Latency until upstream starts sending audio
+        let response_time_ms = start_time.elapsed().as_millis() as u64;
+
+        self.log_usage(project_id, &request, response.status().as_u16(), response_time_ms);
+
+        Ok(response)
+    }
+
+    /// Log speech usage, charged per input character
+    fn log_usage(
+        &self,
+        project_id: String,
+        request: &SpeechRequest,
+        status_code: u16,
+        response_time_ms: u64,
+    ) {
+        let characters = request.input.chars().count();
+        let cost_usd = calculate_speech_cost(&request.model, characters);
+
+        let log = UsageLog::new(
+            project_id,
+            ApiEndpoint::AudioSpeech,
+            LlmProvider::Openai,
+            request.model.clone(),
+            RequestMetadata {
+                request_id: uuid::Uuid::new_v4().to_string(),
+                method: "POST".to_string(),
+                path: "/v1/audio/speech".to_string(),
+                ip_address: None,
+                user_agent: None,
+                prompt_tokens: None,
+                audio_duration_seconds: None,
+                file_size_bytes: None,
+                temperature: None,
+                max_tokens: None,
+                stream: true,
+            },
+            ResponseMetadata {
+                status_code,
+                latency_ms: response_time_ms,
+                provider_latency_ms: Some(response_time_ms),
+                completion_tokens: None,
+                total_tokens: None,
+                finish_reason: None,
+            },
+            CostData {
+                prompt_cost_usd: None,
+                completion_cost_usd: None,
+                audio_cost_usd: Some(cost_usd),
+                total_cost_usd: cost_usd,
+                cached_savings_usd: None,
+            },
+            None,
+            None,
+        );
+
+        // Log in background
+        let repo = self.usage_repository.clone();
+        tokio::spawn(async move {
+            if let Err(e) = repo.create(&log).await {
+                tracing::error!("Failed to log speech usage: {}", e);
+            }
+        });
+    }
+}
@@ -0,0 +1,354 @@
+//! Structured outputs: `response_format` is passed through to models that
+//! support it natively and emulated for the rest, with the schema in a system
+//! message and the reply validated by the gateway.
+
+use std::future::Future;
+
+use jsonschema::Validator;
+use serde_json::json;
+use tracing::warn;
+
+use crate::api::dto::{
+    ChatCompletionRequest, ChatCompletionResponse, ChatContent, ChatMessage, ChatResponseFormat,
+    ChatRole, ChatUsage, FinishReason,
+};
+use crate::domain::services::chat_usage::ChatUsageRecorder;
+use crate::domain::services::providers::openai::OpenAIProvider;
+use crate::shared::error::AppError;
+
+/// Follow-up requests sent after an emulated reply fails validation
+const MAX_REPAIR_ATTEMPTS: usize = 1;
+
+/// Whether the model accepts `json_schema` response formats
+fn supports_json_schema(model: &str) -> bool {
+    (model.starts_with("gpt-4o") && model != "gpt-4o-2024-05-13")
+        || model.starts_with("gpt-4.1")
+        || model.starts_with("gpt-5")
+        || model.starts_with("o3")
+        || model.starts_with("o4")
+        || (model.starts_with("o1") && !model.starts_with("o1-mini") && !model.starts_with("o1-preview"))
+}
+
+/// Whether the model accepts the `json_object` response format
+fn supports_json_object(model: &str) -> bool {
+    supports_json_schema(model)
+        || model.starts_with("gpt-4o")
+        || model.starts_with("gpt-4-turbo")
+        || model.starts_with("gpt-3.5-turbo")
+        || (model.starts_with("gpt-4-") && model.ends_with("-preview"))
+}
+
+/// Gateway-side enforcement of a response format the model lacks
+pub struct EmulatedFormat {
+    /// None for `json_object`, which only has to parse as an object
+    validator: Option<Validator>,
+    instruction: String,
+    /// Weaker native format still worth asking for
+    upstream_format: Option<ChatResponseFormat>,
+}
+
+impl EmulatedFormat {
+    /// Emulation needed for the request, or None when the model handles its
+    /// response format natively
+    pub fn for_request(request: &ChatCompletionRequest) -> Result<Option<Self>, AppError> {
+        let model = request.model.as_str();
+        match &request.response_format {
+            None | Some(ChatResponseFormat::Text) => Ok(None),
+            Some(ChatResponseFormat::JsonObject) if supports_json_object(model) => Ok(None),
+            Some(ChatResponseFormat::JsonSchema { .. }) if supports_json_schema(model) => Ok(None),
+            Some(ChatResponseFormat::JsonObject) => Ok(Some(Self {
+                validator: None,
+                instruction: "Respond with a single JSON object only, without code fences or commentary."
+                    .to_string(),
+                upstream_format: None,
+            })),
+            Some(ChatResponseFormat::JsonSchema { json_schema }) => {
+                let schema = json_schema
+                    .schema
+                    .clone()
+                    .unwrap_or_else(|| json!({ "type": "object" }));
+                let validator = jsonschema::validator_for(&schema)
+                    .map_err(|e| AppError::BadRequest(format!("Invalid json_schema.schema: {}", e)))?;
+
+                let mut instruction = format!(
+                    "Respond with JSON only, without code fences or commentary. The reply must validate against the JSON Schema \"{}\"",
+                    json_schema.name
+                );
+                if let Some(description) = &json_schema.description {
+                    instruction.push_str(&format!(" ({})", description));
+                }
+                instruction.push_str(&format!(":\n{}", schema));
+
+                Ok(Some(Self {
+                    validator: Some(validator),
+                    instruction,
+                    upstream_format: supports_json_object(model).then_some(ChatResponseFormat::JsonObject),
+                }))
+            }
+        }
+    }
+
+    /// Rewrite the request for the upstream
+    fn apply(&self, request: &mut ChatCompletionRequest) {
+        request.messages.insert(0, text_message(ChatRole::System, self.instruction.clone()));
+        request.response_format = self.upstream_format.clone();
+    }
+
+    /// Validate every choice, trimming accepted replies to the bare JSON
+    fn check(&self, response: &mut ChatCompletionResponse) -> Result<(), String> {
+        for choice in &mut response.choices {
+            if matches!(choice.finish_reason, Some(FinishReason::ToolCalls)) {
+                continue;
+            }
+            let text = match &choice.message.content {
+                Some(ChatContent::Text(text)) => extract_json(text),
+                _ => "",
+            };
+            let value: serde_json::Value =
+                serde_json::from_str(text).map_err(|e| format!("reply is not valid JSON: {}", e))?;
+
+            match &self.validator {
+                Some(validator) => {
+                    let errors: Vec<String> = validator
+                        .iter_errors(&value)
+                        .take(5)
+                        .map(|e| format!("{} (at \"{}\")", e, e.instance_path))
+                        .collect();
+                    if !errors.is_empty() {
+                        return Err(errors.join("; "));
+                    }
+                }
+                None if !value.is_object() => return Err("reply is not a JSON object".to_string()),
+                None => {}
+            }
+
+            let text = text.to_string();
+            choice.message.content = Some(ChatContent::Text(text));
+        }
+        Ok(())
+    }
+
+    /// Ask the model to correct a reply that failed validation
+    fn repair(&self, request: &mut ChatCompletionRequest, response: ChatCompletionResponse, reason: &str) {
+        if let Some(choice) = response.choices.into_iter().next() {
+            request.messages.push(choice.message);
+        }
+        request.messages.push(text_message(
+            ChatRole::User,
+            format!(
+                "Your reply did not match the required format: {}. Reply again with corrected JSON only.",
+                reason
+            ),
+        ));
+    }
+}
+
+fn text_message(role: ChatRole, text: String) -> ChatMessage {
+    ChatMessage {
+        role,
+        content: Some(ChatContent::Text(text)),
+        name: None,
+        tool_calls: None,
+        tool_call_id: None,
+        refusal: None,
+    }
+}
+
+/// The JSON in a reply, without surrounding whitespace or Markdown fences
+fn extract_json(text: &str) -> &str {
+    let text = text.trim();
+    text.strip_prefix("```json")
+        .or_else(|| text.strip_prefix("```"))
+        .and_then(|inner| inner.strip_suffix("```"))
+        .map(str::trim)
+        .unwrap_or(text)
+}
+
+/// Create a chat completion honouring `response_format`, emulating it with a
+/// bounded repair retry where the model has no native support. Usage of
+/// attempts that end in an error is logged with `recorder`; the caller
+/// records the usage of a successful reply.
+pub async fn chat_completion(
+    provider: &OpenAIProvider,
+    api_key: &str,
+    request: &ChatCompletionRequest,
+    recorder: &ChatUsageRecorder,
+) -> Result<ChatCompletionResponse, AppError> {
+    let Some(format) = EmulatedFormat::for_request(request)? else {
+        return provider.chat_completion(api_key, request).await;
+    };
+
+    let send = |upstream: ChatCompletionRequest| async move {
+        provider.chat_completion(api_key, &upstream).await
+    };
+    emulate(&format, request, send).await.map_err(|(error, usage)| {
+        if let Some(usage) = usage {
+            let status_code = match error {
+                AppError::StructuredOutputError(_) => 422,
+                _ => 502,
+            };
+            recorder.record_failed(&usage, status_code, error.to_string());
+        }
+        error
+    })
+}
+
+/// Run the emulated format's attempts through `send`. On failure, returns the
+/// usage already billed by earlier attempts alongside the error.
+async fn emulate<F, Fut>(
+    format: &EmulatedFormat,
+    request: &ChatCompletionRequest,
+    mut send: F,
+) -> Result<ChatCompletionResponse, (AppError, Option<ChatUsage>)>
+where
+    F: FnMut(ChatCompletionRequest) -> Fut,
+    Fut: Future<Output = Result<ChatCompletionResponse, AppError>>,
+{
+    let mut upstream = request.clone();
+    format.apply(&mut upstream);
+
+    // Usage of failed attempts is still billed, so carry it into the reply
+    let (mut prompt_tokens, mut completion_tokens, mut cost, mut response_time) = (0, 0, 0.0, 0);
+    let mut attempt = 0;
+    loop {
+        let mut response = match send(upstream.clone()).await {
+            Ok(response) => response,
+            Err(e) if attempt == 0 => return Err((e, None)),
+            Err(e) => return Err((e, Some(billed_usage(prompt_tokens, completion_tokens)))),
+        };
+        prompt_tokens += response.usage.prompt_tokens;
+        completion_tokens += response.usage.completion_tokens;
+        if let Some(meta) = &response.x_llmhub {
+            cost += meta.cost;
+            response_time += meta.response_time;
+        }
+
+        match format.check(&mut response) {
+            Ok(()) => {
+                response.usage.prompt_tokens = prompt_tokens;
+                response.usage.completion_tokens = completion_tokens;
+                response.usage.total_tokens = prompt_tokens + completion_tokens;
+                if let Some(meta) = &mut response.x_llmhub {
+                    meta.cost = cost;
+                    meta.response_time = response_time;
+                }
+                return Ok(response);
+            }
+            Err(reason) if attempt < MAX_REPAIR_ATTEMPTS => {
+                warn!("Emulated response_format not met by {}, retrying: {}", request.model, reason);
+                attempt += 1;
+                format.repair(&mut upstream, response, &reason);
+            }
+            Err(reason) => {
+                let error = AppError::StructuredOutputError(format!(
+                    "Model output did not match response_format after {} attempts: {}",
+                    attempt + 1,
+                    reason
+                ));
+                return Err((error, Some(billed_usage(prompt_tokens, completion_tokens))));
+            }
+        }
+    }
+}
+
+fn billed_usage(prompt_tokens: u32, completion_tokens: u32) -> ChatUsage {
+    ChatUsage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+        prompt_tokens_details: None,
+        completion_tokens_details: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::dto::ChatChoice;
+
+    fn request(model: &str, format: serde_json::Value) -> ChatCompletionRequest {
+        serde_json::from_value(json!({
+            "model": model,
+            "messages": [{ "role": "user", "content": "Name a colour" }],
+            "response_format": format,
+        }))
+        .unwrap()
+    }
+
+    fn response(content: &str) -> ChatCompletionResponse {
+        ChatCompletionResponse {
+            id: "chatcmpl-1".to_string(),
+            object: "chat.completion".to_string(),
+            created: 0,
+            model: "gpt-4".to_string(),
+            choices: vec![ChatChoice {
+                index: 0,
+                message: text_message(ChatRole::Assistant, content.to_string()),
+                finish_reason: Some(FinishReason::Stop),
+                logprobs: None,
+            }],
+            usage: ChatUsage {
+                prompt_tokens: 0,
+                completion_tokens: 0,
+                total_tokens: 0,
+                prompt_tokens_details: None,
+                completion_tokens_details: None,
+            },
+            system_fingerprint: None,
+            service_tier: None,
+            x_llmhub: None,
+        }
+    }
+
+    #[test]
+    fn emulates_json_schema_for_models_without_it() {
+        let schema = json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "colour",
+                "schema": {
+                    "type": "object",
+                    "properties": { "name": { "type": "string" } },
+                    "required": ["name"]
+                }
+            }
+        });
+        assert!(EmulatedFormat::for_request(&request("gpt-4o-mini", schema.clone())).unwrap().is_none());
+
+        let mut upstream = request("gpt-4", schema);
+        let format = EmulatedFormat::for_request(&upstream).unwrap().unwrap();
+        format.apply(&mut upstream);
+        assert!(matches!(upstream.messages[0].role, ChatRole::System));
+        assert!(upstream.response_format.is_none());
+
+        let mut fenced = response("```json\n{\"name\": \"teal\"}\n```");
+        assert!(format.check(&mut fenced).is_ok());
+        assert!(matches!(
+            &fenced.choices[0].message.content,
+            Some(ChatContent::Text(text)) if text == "{\"name\": \"teal\"}"
+        ));
+
+        assert!(format.check(&mut response("{\"colour\": \"teal\"}")).is_err());
+        assert!(format.check(&mut response("teal")).is_err());
+    }
+
+    #[tokio::test]
+    async fn keeps_usage_of_attempts_that_never_match() {
+        let request = request("gpt-4", json!({ "type": "json_object" }));
+        let format = EmulatedFormat::for_request(&request).unwrap().unwrap();
+
+        let mut attempts = 0;
+        let send = |_: ChatCompletionRequest| {
+            attempts += 1;
+            let mut reply = response("teal");
+            reply.usage = billed_usage(10, 5);
+            async move { Ok(reply) }
+        };
+        let (error, usage) = emulate(&format, &request, send).await.unwrap_err();
+
+        assert_eq!(attempts, MAX_REPAIR_ATTEMPTS + 1);
+        assert!(matches!(error, AppError::StructuredOutputError(_)));
+        let usage = usage.unwrap();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens, usage.total_tokens), (20, 10, 30));
+    }
+}
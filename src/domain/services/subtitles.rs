@@ -0,0 +1,449 @@
+//! Subtitle rendering from timestamped transcriptions.
+//!
+//! Builds SRT, WebVTT and TSV locally from segment and word timings, so any
+//! provider that returns timestamps can serve subtitle formats. Speaker
+//! labels from diarization start a new cue and are written as a `Speaker X:`
+//! prefix (SRT), a voice span (WebVTT) or a `speaker` column (TSV).
+
+use crate::domain::entities::transcription::{
+    ResponseFormat, SubtitleOptions, TranscriptionResponse, TranscriptionSegment,
+    TranscriptionWord,
+};
+use crate::shared::error::AppError;
+
+/// Subtitle output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+    Tsv,
+}
+
+impl SubtitleFormat {
+    /// Subtitle format for a requested response format, if it is one
+    pub fn from_response_format(format: &ResponseFormat) -> Option<Self> {
+        match format {
+            ResponseFormat::Srt => Some(SubtitleFormat::Srt),
+            ResponseFormat::Vtt => Some(SubtitleFormat::Vtt),
+            ResponseFormat::Tsv => Some(SubtitleFormat::Tsv),
+            ResponseFormat::Json | ResponseFormat::Text | ResponseFormat::VerboseJson => None,
+        }
+    }
+}
+
+/// Word-level timing used while building cues
+#[derive(Debug, Clone)]
+struct Token {
+    text: String,
+    start: f32,
+    end: f32,
+    speaker: Option<String>,
+}
+
+/// A single subtitle cue; `highlight` is the index of the emphasised token
+#[derive(Debug)]
+struct Cue {
+    start: f32,
+    end: f32,
+    speaker: Option<String>,
+    tokens: Vec<Token>,
+    highlight: Option<usize>,
+}
+
+/// Render a timestamped transcription as SRT, WebVTT or TSV
+pub fn render_subtitles(
+    response: &TranscriptionResponse,
+    format: SubtitleFormat,
+    options: &SubtitleOptions,
+) -> Result<String, AppError> {
+    let segments = response.segments.as_deref().unwrap_or_default();
+    let words = response.words.as_deref().unwrap_or_default();
+    if segments.is_empty() && words.is_empty() && !response.text.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "Subtitle formats require a transcription with segment or word timestamps".to_string(),
+        ));
+    }
+
+    let mut cues = Vec::new();
+    for tokens in segment_tokens(segments, words) {
+        for group in split_tokens(tokens, options.max_cue_chars) {
+            let start = group[0].start;
+            let end = group[group.len() - 1].end.max(start);
+            let speaker = group[0].speaker.clone();
+
+            // TSV is a flat table; word highlighting only applies to SRT and VTT
+            if options.highlight_words && format != SubtitleFormat::Tsv && group.len() > 1 {
+                for i in 0..group.len() {
+                    let word_start = if i == 0 { start } else { group[i].start };
+                    let word_end = group.get(i + 1).map(|t| t.start).unwrap_or(end);
+                    cues.push(Cue {
+                        start: word_start,
+                        end: word_end.max(word_start),
+                        speaker: speaker.clone(),
+                        tokens: group.clone(),
+                        highlight: Some(i),
+                    });
+                }
+            } else {
+                cues.push(Cue {
+                    start,
+                    end,
+                    speaker,
+                    tokens: group,
+                    highlight: None,
+                });
+            }
+        }
+    }
+
+    let mut output = String::new();
+    match format {
+        SubtitleFormat::Srt => {
+            for (index, cue) in cues.iter().enumerate() {
+                output.push_str(&format!(
+                    "{}\n{} --> {}\n{}\n\n",
+                    index + 1,
+                    timestamp(cue.start, ','),
+                    timestamp(cue.end, ','),
+                    cue_text(cue, format, options.max_line_length),
+                ));
+            }
+        }
+        SubtitleFormat::Vtt => {
+            output.push_str("WEBVTT\n\n");
+            for cue in &cues {
+                output.push_str(&format!(
+                    "{} --> {}\n{}\n\n",
+                    timestamp(cue.start, '.'),
+                    timestamp(cue.end, '.'),
+                    cue_text(cue, format, options.max_line_length),
+                ));
+            }
+        }
+        SubtitleFormat::Tsv => {
+            // Millisecond offsets, matching Whisper's own TSV writer
+            let diarized = cues.iter().any(|c| c.speaker.is_some());
+            output.push_str(if diarized { "start\tend\tspeaker\ttext\n" } else { "start\tend\ttext\n" });
+            for cue in &cues {
+                let text = cue_text(cue, format, None).replace('\t', " ");
+                let speaker = if diarized {
+                    format!("{}\t", cue.speaker.as_deref().unwrap_or_default())
+                } else {
+                    String::new()
+                };
+                output.push_str(&format!(
+                    "{}\t{}\t{}{}\n",
+                    (cue.start * 1000.0).round() as u64,
+                    (cue.end * 1000.0).round() as u64,
+                    speaker,
+                    text,
+                ));
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Plain-text transcript with one paragraph per speaker turn,
+/// e.g. `"Speaker A: Hello.\n\nSpeaker B: Hi!"`
+pub fn render_speaker_turns(response: &TranscriptionResponse) -> String {
+    let mut turns: Vec<(Option<&str>, Vec<&str>)> = Vec::new();
+    for segment in response.segments.as_deref().unwrap_or_default() {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let speaker = segment.speaker.as_deref();
+        match turns.last_mut() {
+            Some((current, texts)) if *current == speaker => texts.push(text),
+            _ => turns.push((speaker, vec![text])),
+        }
+    }
+
+    if turns.is_empty() {
+        return response.text.clone();
+    }
+
+    turns
+        .into_iter()
+        .map(|(speaker, texts)| match speaker {
+            Some(speaker) => format!("Speaker {}: {}", speaker, texts.join(" ")),
+            None => texts.join(" "),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Timed tokens for each segment.
+///
+/// Word timestamps are used when the provider returned them; otherwise the
+/// segment span is spread over its words in proportion to their length.
+fn segment_tokens(segments: &[TranscriptionSegment], words: &[TranscriptionWord]) -> Vec<Vec<Token>> {
+    let word_token = |w: &TranscriptionWord, segment_speaker: Option<&String>| Token {
+        text: w.word.trim().to_string(),
+        start: w.start,
+        end: w.end,
+        speaker: w.speaker.clone().or_else(|| segment_speaker.cloned()),
+    };
+
+    if segments.is_empty() {
+        let tokens: Vec<Token> = words
+            .iter()
+            .map(|w| word_token(w, None))
+            .filter(|t| !t.text.is_empty())
+            .collect();
+        return if tokens.is_empty() { Vec::new() } else { vec![tokens] };
+    }
+
+    let mut result = Vec::new();
+    let mut next_word = 0;
+    for (i, segment) in segments.iter().enumerate() {
+        let is_last = i + 1 == segments.len();
+        let mut tokens = Vec::new();
+        while next_word < words.len() && (is_last || words[next_word].start < segment.end) {
+            tokens.push(word_token(&words[next_word], segment.speaker.as_ref()));
+            next_word += 1;
+        }
+        tokens.retain(|t| !t.text.is_empty());
+
+        if tokens.is_empty() {
+            tokens = interpolate(segment);
+        }
+        if !tokens.is_empty() {
+            result.push(tokens);
+        }
+    }
+
+    result
+}
+
+/// Spread a segment's duration over its words by character count
+fn interpolate(segment: &TranscriptionSegment) -> Vec<Token> {
+    let texts: Vec<&str> = segment.text.split_whitespace().collect();
+    let total_chars: usize = texts.iter().map(|t| t.chars().count()).sum();
+    if total_chars == 0 {
+        return Vec::new();
+    }
+
+    let span = (segment.end - segment.start).max(0.0);
+    let mut elapsed = 0;
+    texts
+        .into_iter()
+        .map(|text| {
+            let chars = text.chars().count();
+            let start = segment.start + span * elapsed as f32 / total_chars as f32;
+            elapsed += chars;
+            let end = segment.start + span * elapsed as f32 / total_chars as f32;
+            Token {
+                text: text.to_string(),
+                start,
+                end,
+                speaker: segment.speaker.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Split tokens into cues holding at most `max_chars` characters each,
+/// starting a new cue whenever the speaker changes
+fn split_tokens(tokens: Vec<Token>, max_chars: Option<usize>) -> Vec<Vec<Token>> {
+    let mut groups = Vec::new();
+    let mut current: Vec<Token> = Vec::new();
+    let mut length = 0;
+    for token in tokens {
+        let token_length = token.text.chars().count();
+        let added = if current.is_empty() { token_length } else { token_length + 1 };
+        let too_long = max_chars.is_some_and(|max| length + added > max);
+        let new_speaker = current.last().is_some_and(|t| t.speaker != token.speaker);
+        if !current.is_empty() && (too_long || new_speaker) {
+            groups.push(std::mem::take(&mut current));
+            length = 0;
+        }
+        length += if current.is_empty() { token_length } else { token_length + 1 };
+        current.push(token);
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+
+    groups
+}
+
+/// Cue text with optional highlighting, wrapped to `max_line_length`.
+/// SRT and VTT cues are prefixed with their speaker.
+fn cue_text(cue: &Cue, format: SubtitleFormat, max_line_length: Option<usize>) -> String {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_length = 0;
+
+    for (i, token) in cue.tokens.iter().enumerate() {
+        let token_length = token.text.chars().count();
+        if let Some(max) = max_line_length {
+            if line_length > 0 && line_length + 1 + token_length > max {
+                lines.push(std::mem::take(&mut line));
+                line_length = 0;
+            }
+        }
+        if line_length > 0 {
+            line.push(' ');
+            line_length += 1;
+        }
+        if cue.highlight == Some(i) {
+            line.push_str(&format!("<u>{}</u>", token.text));
+        } else {
+            line.push_str(&token.text);
+        }
+        line_length += token_length;
+    }
+    lines.push(line);
+
+    let text = lines.join("\n");
+    match (&cue.speaker, format) {
+        (Some(speaker), SubtitleFormat::Srt) => format!("Speaker {}: {}", speaker, text),
+        (Some(speaker), SubtitleFormat::Vtt) => format!("<v Speaker {}>{}", speaker, text),
+        _ => text,
+    }
+}
+
+/// `HH:MM:SS<sep>mmm`
+fn timestamp(seconds: f32, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total_ms / 3_600_000,
+        (total_ms / 60_000) % 60,
+        (total_ms / 1000) % 60,
+        separator,
+        total_ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(id: i32, start: f32, end: f32, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            id,
+            start,
+            end,
+            text: text.to_string(),
+            tokens: None,
+            temperature: None,
+            avg_logprob: None,
+            compression_ratio: None,
+            no_speech_prob: None,
+            speaker: None,
+        }
+    }
+
+    fn word(word: &str, start: f32, end: f32) -> TranscriptionWord {
+        TranscriptionWord {
+            word: word.to_string(),
+            start,
+            end,
+            speaker: None,
+        }
+    }
+
+    fn response(segments: Vec<TranscriptionSegment>, words: Option<Vec<TranscriptionWord>>) -> TranscriptionResponse {
+        TranscriptionResponse {
+            text: String::new(),
+            language: None,
+            duration: None,
+            segments: Some(segments),
+            words,
+            usage: None,
+        }
+    }
+
+    #[test]
+    fn renders_srt_and_vtt_from_segments() {
+        let response = response(
+            vec![
+                segment(0, 0.0, 2.5, " Hello there."),
+                segment(1, 2.5, 3661.25, " General Kenobi."),
+            ],
+            None,
+        );
+
+        let srt = render_subtitles(&response, SubtitleFormat::Srt, &SubtitleOptions::default()).unwrap();
+        assert_eq!(
+            srt,
+            "1\n00:00:00,000 --> 00:00:02,500\nHello there.\n\n\
+             2\n00:00:02,500 --> 01:01:01,250\nGeneral Kenobi.\n\n"
+        );
+
+        let vtt = render_subtitles(&response, SubtitleFormat::Vtt, &SubtitleOptions::default()).unwrap();
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.000 --> 00:00:02.500\nHello there.\n"));
+    }
+
+    #[test]
+    fn splits_cues_and_wraps_lines() {
+        let response = response(
+            vec![segment(0, 0.0, 4.0, " one two three four")],
+            Some(vec![
+                word("one", 0.0, 1.0),
+                word("two", 1.0, 2.0),
+                word("three", 2.0, 3.0),
+                word("four", 3.0, 4.0),
+            ]),
+        );
+        let options = SubtitleOptions {
+            max_line_length: Some(7),
+            max_cue_chars: Some(13),
+            highlight_words: false,
+        };
+
+        let tsv = render_subtitles(&response, SubtitleFormat::Tsv, &options).unwrap();
+        assert_eq!(tsv, "start\tend\ttext\n0\t3000\tone two three\n3000\t4000\tfour\n");
+
+        let srt = render_subtitles(&response, SubtitleFormat::Srt, &options).unwrap();
+        assert!(srt.starts_with("1\n00:00:00,000 --> 00:00:03,000\none two\nthree\n\n"));
+    }
+
+    #[test]
+    fn highlights_each_word() {
+        let response = response(
+            vec![segment(0, 0.0, 2.0, " Hi all")],
+            Some(vec![word("Hi", 0.2, 0.8), word("all", 1.0, 1.6)]),
+        );
+        let options = SubtitleOptions {
+            highlight_words: true,
+            ..Default::default()
+        };
+
+        let vtt = render_subtitles(&response, SubtitleFormat::Vtt, &options).unwrap();
+        assert_eq!(
+            vtt,
+            "WEBVTT\n\n\
+             00:00:00.200 --> 00:00:01.000\n<u>Hi</u> all\n\n\
+             00:00:01.000 --> 00:00:01.600\nHi <u>all</u>\n\n"
+        );
+    }
+
+    #[test]
+    fn splits_cues_on_speaker_change() {
+        let mut words = vec![
+            word("Hi", 0.0, 0.5),
+            word("there", 0.5, 1.0),
+            word("Hello", 1.2, 1.8),
+        ];
+        words[0].speaker = Some("A".to_string());
+        words[1].speaker = Some("A".to_string());
+        words[2].speaker = Some("B".to_string());
+        let response = response(vec![segment(0, 0.0, 2.0, " Hi there Hello")], Some(words));
+
+        let vtt = render_subtitles(&response, SubtitleFormat::Vtt, &SubtitleOptions::default()).unwrap();
+        assert_eq!(
+            vtt,
+            "WEBVTT\n\n\
+             00:00:00.000 --> 00:00:01.000\n<v Speaker A>Hi there\n\n\
+             00:00:01.200 --> 00:00:01.800\n<v Speaker B>Hello\n\n"
+        );
+
+        let tsv = render_subtitles(&response, SubtitleFormat::Tsv, &SubtitleOptions::default()).unwrap();
+        assert_eq!(tsv, "start\tend\tspeaker\ttext\n0\t1000\tA\tHi there\n1200\t1800\tB\tHello\n");
+    }
+}
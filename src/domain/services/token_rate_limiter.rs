@@ -0,0 +1,186 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::shared::error::AppError;
+
+/// Window over which `tokens_per_minute` is counted
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// How often a waiting reservation checks the window again
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+type Windows = Arc<Mutex<HashMap<String, VecDeque<Reserved>>>>;
+
+/// Enforces each project's `tokens_per_minute` on chat requests. A request
+/// reserves its pre-flight prompt count plus its completion budget, and the
+/// reservation is corrected to the tokens actually used once they are known.
+///
+/// Counters live in this process, so each gateway replica enforces the limit
+/// on its own share of traffic.
+#[derive(Default)]
+pub struct TokenRateLimiter {
+    windows: Windows,
+    next_id: AtomicU64,
+}
+
+/// Tokens reserved by one request in its project's window
+struct Reserved {
+    id: u64,
+    at: Instant,
+    tokens: u64,
+}
+
+/// A request's share of the project's budget. Dropping it without
+/// [`settle`](Self::settle) gives the tokens back, so requests that fail or
+/// are refused don't count against the limit.
+pub struct TokenReservation {
+    project_id: String,
+    id: u64,
+    settled: AtomicBool,
+    windows: Windows,
+}
+
+impl TokenReservation {
+    /// Replace the estimate with the tokens the provider reported
+    pub fn settle(&self, tokens: u64) {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(reserved) = windows
+            .get_mut(&self.project_id)
+            .and_then(|window| window.iter_mut().find(|r| r.id == self.id))
+        {
+            reserved.tokens = tokens;
+        }
+        self.settled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for TokenReservation {
+    fn drop(&mut self) {
+        if self.settled.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(window) = windows.get_mut(&self.project_id) {
+            window.retain(|r| r.id != self.id);
+            if window.is_empty() {
+                windows.remove(&self.project_id);
+            }
+        }
+    }
+}
+
+impl TokenRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve `tokens` for the project, or fail when they don't fit in the
+    /// last minute's budget. Unset or zero limits aren't enforced.
+    pub fn reserve(
+        &self,
+        project_id: &str,
+        tokens_per_minute: Option<u32>,
+        tokens: u64,
+    ) -> Result<Option<TokenReservation>, AppError> {
+        self.reserve_at(project_id, tokens_per_minute, tokens, Instant::now())
+    }
+
+    /// Reserve `tokens` for the project, waiting until they fit in the
+    /// window rather than failing. Requests larger than the whole budget
+    /// still fail.
+    pub async fn reserve_when_available(
+        &self,
+        project_id: &str,
+        tokens_per_minute: Option<u32>,
+        tokens: u64,
+    ) -> Result<Option<TokenReservation>, AppError> {
+        loop {
+            match self.reserve(project_id, tokens_per_minute, tokens) {
+                Err(AppError::RateLimitError(_))
+                    if tokens_per_minute.is_some_and(|limit| tokens <= limit as u64) =>
+                {
+                    tokio::time::sleep(RETRY_INTERVAL).await
+                }
+                outcome => return outcome,
+            }
+        }
+    }
+
+    fn reserve_at(
+        &self,
+        project_id: &str,
+        tokens_per_minute: Option<u32>,
+        tokens: u64,
+        now: Instant,
+    ) -> Result<Option<TokenReservation>, AppError> {
+        let limit = match tokens_per_minute {
+            Some(limit) if limit > 0 => limit as u64,
+            _ => return Ok(None),
+        };
+        if tokens > limit {
+            return Err(AppError::RateLimitError(format!(
+                "Request needs {} tokens, more than the project's {} tokens per minute",
+                tokens, limit
+            )));
+        }
+
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let window = windows.entry(project_id.to_string()).or_default();
+        while window
+            .front()
+            .is_some_and(|reserved| now.duration_since(reserved.at) >= RATE_WINDOW)
+        {
+            window.pop_front();
+        }
+
+        let used: u64 = window.iter().map(|reserved| reserved.tokens).sum();
+        if used + tokens > limit {
+            return Err(AppError::RateLimitError(format!(
+                "Project exceeded {} tokens per minute ({} used, {} requested)",
+                limit, used, tokens
+            )));
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        window.push_back(Reserved { id, at: now, tokens });
+        Ok(Some(TokenReservation {
+            project_id: project_id.to_string(),
+            id,
+            settled: AtomicBool::new(false),
+            windows: self.windows.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_tokens_beyond_the_minute_budget() {
+        let limiter = TokenRateLimiter::new();
+        let limit = Some(1000);
+        let start = Instant::now();
+
+        let first = limiter.reserve_at("p", limit, 600, start).unwrap().unwrap();
+        first.settle(600);
+        assert!(limiter.reserve_at("p", limit, 500, start).is_err());
+        assert!(limiter.reserve_at("other", limit, 500, start).unwrap().is_some());
+        assert!(limiter.reserve_at("p", limit, 2000, start).is_err());
+
+        // Unsettled reservations are released; settled ones keep actual usage
+        let refused = limiter.reserve_at("p", limit, 400, start).unwrap();
+        drop(refused);
+        let second = limiter.reserve_at("p", limit, 400, start).unwrap().unwrap();
+        second.settle(100);
+        assert!(limiter.reserve_at("p", limit, 300, start).unwrap().is_some());
+
+        let later = start + RATE_WINDOW;
+        assert!(limiter.reserve_at("p", limit, 1000, later).unwrap().is_some());
+
+        assert!(limiter.reserve_at("p", None, 1_000_000, start).unwrap().is_none());
+        assert!(limiter.reserve_at("p", Some(0), 1_000_000, start).unwrap().is_none());
+    }
+}
@@ -0,0 +1,197 @@
+//! Prompt token counting and context-window limits per model family
+
+use tiktoken_rs::CoreBPE;
+
+use crate::api::dto::{ChatCompletionRequest, ChatContent, ChatMessage, ChatRole, ChatTool, ContentPart};
+
+/// Tokens OpenAI adds around every message (`<|start|>role<|message|>...<|end|>`)
+const TOKENS_PER_MESSAGE: usize = 3;
+/// Tokens priming the assistant reply
+const REPLY_PRIMING_TOKENS: usize = 3;
+/// Claude's tokenizer isn't public; it yields about 10% more tokens than
+/// cl100k_base on English text and code
+const ANTHROPIC_CL100K_RATIO: f64 = 1.1;
+
+/// Tokenizer family of a model. OpenAI families are counted exactly with the
+/// bundled BPE vocabularies; others are approximated from the closest one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenizerFamily {
+    /// GPT-4o, GPT-4.1, GPT-5 and o-series models
+    O200k,
+    /// GPT-4 and GPT-3.5 models
+    Cl100k,
+    /// Claude models, approximated from cl100k_base
+    Anthropic,
+    /// Gemini and Gemma models, whose large vocabularies track o200k_base
+    Google,
+    /// Any other model, approximated with cl100k_base
+    Other,
+}
+
+impl TokenizerFamily {
+    pub fn for_model(model: &str) -> Self {
+        let model = model.rsplit('/').next().unwrap_or(model);
+        if model.starts_with("gpt-4o")
+            || model.starts_with("gpt-4.1")
+            || model.starts_with("gpt-4.5")
+            || model.starts_with("gpt-5")
+            || model.starts_with("chatgpt-4o")
+            || ["o1", "o3", "o4"].iter().any(|prefix| model.starts_with(prefix))
+        {
+            TokenizerFamily::O200k
+        } else if model.starts_with("gpt-4") || model.starts_with("gpt-3.5") {
+            TokenizerFamily::Cl100k
+        } else if model.starts_with("claude") {
+            TokenizerFamily::Anthropic
+        } else if model.starts_with("gemini") || model.starts_with("gemma") {
+            TokenizerFamily::Google
+        } else {
+            TokenizerFamily::Other
+        }
+    }
+
+    /// Name of the vocabulary used to count
+    pub fn name(&self) -> &'static str {
+        match self {
+            TokenizerFamily::O200k | TokenizerFamily::Google => "o200k_base",
+            TokenizerFamily::Cl100k | TokenizerFamily::Anthropic | TokenizerFamily::Other => {
+                "cl100k_base"
+            }
+        }
+    }
+
+    /// Whether counts match what the provider bills
+    pub fn is_exact(&self) -> bool {
+        matches!(self, TokenizerFamily::O200k | TokenizerFamily::Cl100k)
+    }
+
+    fn bpe(&self) -> &'static CoreBPE {
+        match self {
+            TokenizerFamily::O200k | TokenizerFamily::Google => tiktoken_rs::o200k_base_singleton(),
+            TokenizerFamily::Cl100k | TokenizerFamily::Anthropic | TokenizerFamily::Other => {
+                tiktoken_rs::cl100k_base_singleton()
+            }
+        }
+    }
+
+    /// Token ids of the text in the family's vocabulary
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        self.bpe().encode_ordinary(text)
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        let tokens = self.bpe().encode_ordinary(text).len();
+        match self {
+            TokenizerFamily::Anthropic => (tokens as f64 * ANTHROPIC_CL100K_RATIO).ceil() as usize,
+            _ => tokens,
+        }
+    }
+}
+
+/// Context window in tokens (prompt plus completion), when the model is known
+pub fn context_window(model: &str) -> Option<usize> {
+    let model = model.rsplit('/').next().unwrap_or(model);
+    let window = match model {
+        m if m.starts_with("gpt-4.1") => 1_047_576,
+        m if m.starts_with("gpt-5") => 400_000,
+        m if m.starts_with("gpt-4o") || m.starts_with("chatgpt-4o") || m.starts_with("gpt-4.5") => 128_000,
+        m if m.starts_with("o1-mini") || m.starts_with("o1-preview") => 128_000,
+        m if m.starts_with("o1") || m.starts_with("o3") || m.starts_with("o4") => 200_000,
+        m if m.starts_with("gpt-4-turbo") || m.starts_with("gpt-4-1106") || m.starts_with("gpt-4-0125") => 128_000,
+        m if m.starts_with("gpt-4-32k") => 32_768,
+        m if m.starts_with("gpt-4") => 8_192,
+        "gpt-3.5-turbo-instruct" => 4_096,
+        m if m.starts_with("gpt-3.5-turbo") => 16_385,
+        m if m.starts_with("claude") => 200_000,
+        m if m.starts_with("gemini-1.5-pro") => 2_097_152,
+        m if m.starts_with("gemini-1.5") || m.starts_with("gemini-2") => 1_048_576,
+        _ => return None,
+    };
+    Some(window)
+}
+
+/// Prompt tokens of one message, including its framing
+pub fn count_message(family: TokenizerFamily, message: &ChatMessage) -> usize {
+    let role = match &message.role {
+        ChatRole::System => "system",
+        ChatRole::User => "user",
+        ChatRole::Assistant => "assistant",
+        ChatRole::Tool => "tool",
+        ChatRole::Other(role) => role,
+    };
+    let mut tokens = TOKENS_PER_MESSAGE + family.count(role);
+    match &message.content {
+        Some(ChatContent::Text(text)) => tokens += family.count(text),
+        Some(ChatContent::Parts(parts)) => {
+            for part in parts {
+                tokens += match part {
+                    ContentPart::Text { text } => family.count(text),
+                    ContentPart::ImageUrl { image_url } => image_url.estimated_tokens() as usize,
+                    // Audio is billed by duration, not prompt text
+                    ContentPart::InputAudio { .. } => 0,
+                };
+            }
+        }
+        None => {}
+    }
+    if let Some(name) = &message.name {
+        tokens += family.count(name) + 1;
+    }
+    for call in message.tool_calls.iter().flatten() {
+        tokens += family.count(&call.function.name) + family.count(&call.function.arguments);
+    }
+    tokens
+}
+
+/// Prompt tokens of a request, split into messages and tool definitions
+pub struct PromptTokens {
+    pub messages: Vec<usize>,
+    pub tools: usize,
+}
+
+impl PromptTokens {
+    pub fn total(&self) -> usize {
+        self.messages.iter().sum::<usize>() + self.tools + REPLY_PRIMING_TOKENS
+    }
+}
+
+/// Count a chat request's prompt tokens with its model's tokenizer
+pub fn count_prompt(request: &ChatCompletionRequest) -> PromptTokens {
+    count_chat(&request.model, &request.messages, request.tools.as_deref())
+}
+
+/// Count the prompt tokens of chat messages and tool definitions
+pub fn count_chat(model: &str, messages: &[ChatMessage], tools: Option<&[ChatTool]>) -> PromptTokens {
+    let family = TokenizerFamily::for_model(model);
+    let messages = messages
+        .iter()
+        .map(|message| count_message(family, message))
+        .collect();
+    // Tool definitions are rendered into the prompt; their JSON is a close estimate
+    let tools = tools
+        .and_then(|tools| serde_json::to_string(tools).ok())
+        .map_or(0, |json| family.count(&json));
+
+    PromptTokens { messages, tools }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_with_the_model_family_tokenizer() {
+        assert_eq!(TokenizerFamily::for_model("gpt-4o-mini"), TokenizerFamily::O200k);
+        assert_eq!(TokenizerFamily::for_model("o3-mini"), TokenizerFamily::O200k);
+        assert_eq!(TokenizerFamily::for_model("gpt-4-0613"), TokenizerFamily::Cl100k);
+        assert_eq!(TokenizerFamily::for_model("claude-3-5-sonnet"), TokenizerFamily::Anthropic);
+        assert_eq!(TokenizerFamily::for_model("google/gemini-2.0-flash"), TokenizerFamily::Google);
+
+        assert_eq!(TokenizerFamily::O200k.encode("Hello, world!"), vec![13225, 11, 2375, 0]);
+        assert_eq!(TokenizerFamily::Cl100k.count("Hello, world!"), 4);
+        assert_eq!(TokenizerFamily::Anthropic.count("Hello, world!"), 5);
+        assert_eq!(context_window("gpt-4o-2024-08-06"), Some(128_000));
+        assert_eq!(context_window("gpt-4-0613"), Some(8_192));
+        assert_eq!(context_window("llama-3-70b"), None);
+    }
+}
@@ -1,33 +1,68 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
+use futures::{StreamExt, TryStreamExt};
 use sha2::{Digest, Sha256};
 
-use crate::domain::entities::LlmProvider;
 use crate::domain::entities::transcription::{
-    TranscriptionHistory, TranscriptionRequest, TranscriptionResponse,
+    ResponseFormat, TimestampGranularity, TranscriptionHistory, TranscriptionProviderKind,
+    TranscriptionRequest, TranscriptionResponse, TranscriptionUsage,
 };
+use crate::domain::repositories::guardrail_repository::GuardrailRepository;
 use crate::domain::repositories::transcription_repository::TranscriptionRepository;
+use crate::domain::services::audio::{probe_audio, split_audio, AudioInfo, SplittableFormat};
 use crate::domain::services::llm_api_key::LlmApiKeyService;
-use crate::domain::services::providers::OpenAIProvider;
+use crate::domain::services::pii::PiiScanner;
+use crate::domain::services::providers::{
+    AssemblyAIProvider, AzureSpeechProvider, DeepgramProvider, GoogleSpeechProvider,
+    OpenAIProvider, TranscriptionOptions, TranscriptionProvider, WhisperServerProvider,
+};
+use crate::domain::services::subtitles::{render_speaker_turns, render_subtitles, SubtitleFormat};
+use crate::shared::config::{ProvidersConfig, TranscriptionConfig};
 use crate::shared::error::AppError;
 
 /// Transcription service orchestrating transcription workflow
 pub struct TranscriptionService {
     repository: Arc<dyn TranscriptionRepository>,
+    guardrail_repository: Arc<dyn GuardrailRepository>,
     llm_key_service: Arc<LlmApiKeyService>,
-    openai_provider: OpenAIProvider,
+    providers: HashMap<TranscriptionProviderKind, Box<dyn TranscriptionProvider>>,
+    provider_keys: ProvidersConfig,
+    config: TranscriptionConfig,
 }
 
 impl TranscriptionService {
     pub fn new(
         repository: Arc<dyn TranscriptionRepository>,
+        guardrail_repository: Arc<dyn GuardrailRepository>,
         llm_key_service: Arc<LlmApiKeyService>,
+        config: TranscriptionConfig,
+        provider_keys: ProvidersConfig,
     ) -> Self {
+        let mut providers: HashMap<TranscriptionProviderKind, Box<dyn TranscriptionProvider>> =
+            HashMap::new();
+        providers.insert(TranscriptionProviderKind::Openai, Box::new(OpenAIProvider::new()));
+        providers.insert(TranscriptionProviderKind::Deepgram, Box::new(DeepgramProvider::new()));
+        providers.insert(TranscriptionProviderKind::Assemblyai, Box::new(AssemblyAIProvider::new()));
+        providers.insert(TranscriptionProviderKind::Google, Box::new(GoogleSpeechProvider::new()));
+        if let Some(region) = &provider_keys.azure_speech_region {
+            providers.insert(TranscriptionProviderKind::Azure, Box::new(AzureSpeechProvider::new(region)));
+        }
+        if let Some(url) = &provider_keys.whisper_server_url {
+            providers.insert(
+                TranscriptionProviderKind::WhisperServer,
+                Box::new(WhisperServerProvider::new(url.clone())),
+            );
+        }
+
         Self {
             repository,
+            guardrail_repository,
             llm_key_service,
-            openai_provider: OpenAIProvider::new(),
+            providers,
+            provider_keys,
+            config,
         }
     }
 
@@ -35,54 +70,144 @@ impl TranscriptionService {
     pub async fn transcribe(
         &self,
         project_id: String,
-        request: TranscriptionRequest,
+        mut request: TranscriptionRequest,
     ) -> Result<TranscriptionResponse, AppError> {
         let start_time = Instant::now();
 
+        // Reject unsupported, corrupt or overlong media before calling out,
+        // and name the upload after its real container
+        let audio = self.inspect_audio(&request.file_data)?;
+        request.file_name = audio.format.file_name(&request.file_name);
+
         // Calculate file hash for deduplication
-        let file_hash = self.calculate_file_hash(&request.file_data);
+        let file_hash = match &request.file_hash {
+            Some(hash) => hash.clone(),
+            None => self.calculate_file_hash(&request.file_data),
+        };
 
-        // Determine provider (default to OpenAI for now)
-        let provider = LlmProvider::Openai;
+        let kind = request.provider.unwrap_or_default();
+        let provider = self.providers.get(&kind).ok_or_else(|| {
+            AppError::ConfigError(format!("Transcription provider {} is not configured", kind))
+        })?;
+        let model = request
+            .model
+            .clone()
+            .unwrap_or_else(|| provider.default_model().to_string());
 
-        // Get LLM API key
-        let api_key = if let Some(key_id) = &request.llm_api_key_id {
-            // Use specified key
-            self.llm_key_service.get_decrypted_key(key_id).await?
-        } else {
-            // Use default key for provider
-            self.llm_key_service
-                .get_default_key_for_provider(&project_id, &provider)
-                .await?
-                .ok_or_else(|| {
-                    AppError::ConfigError(format!(
-                        "No LLM API key configured for provider: {:?}",
-                        provider
-                    ))
-                })?
+        // Subtitle formats are rendered locally from verbose timestamps
+        let subtitle_format = request
+            .response_format
+            .as_ref()
+            .and_then(SubtitleFormat::from_response_format);
+        if request.diarize && !provider.supports_diarization(&model) {
+            return Err(AppError::BadRequest(format!(
+                "Model {} on provider {} does not support diarization",
+                model, kind
+            )));
+        }
+        if subtitle_format.is_some() && !request.diarize && !provider.returns_timestamps(&model) {
+            return Err(AppError::BadRequest(format!(
+                "Model {} does not return the timestamps needed for srt, vtt or tsv output",
+                model
+            )));
+        }
+
+        let estimated_cost = audio
+            .duration_seconds
+            .map(|d| d as f64 / 60.0 * provider.price_per_minute(&model));
+        if let (Some(duration), Some(cost)) = (audio.duration_seconds, estimated_cost) {
+            tracing::debug!(
+                "Transcribing {:.1}s of {} audio with {} {} (estimated ${:.4})",
+                duration,
+                audio.format.extension(),
+                kind,
+                model,
+                cost
+            );
+        }
+
+        let api_key = self.resolve_api_key(&project_id, &request, kind).await?;
+
+        // The prompt goes to the provider as text; transcripts are redacted
+        // before they are stored or returned
+        let pii = match self.guardrail_repository.find_by_project(&project_id).await? {
+            Some(policy) => policy.pii.as_ref().map(PiiScanner::new).transpose()?,
+            None => None,
         };
+        if let (Some(scanner), Some(prompt)) = (&pii, request.prompt.as_mut()) {
+            scanner.scrub_prompt(prompt)?;
+        }
+
+        // Subtitles and speaker labels are built from verbose segments; with
+        // diarization json responses include the labelled segments too
+        let mut options = TranscriptionOptions::from_request(&request);
+        if subtitle_format.is_some() || request.diarize {
+            let mut granularities = vec![TimestampGranularity::Segment];
+            if options.words_requested()
+                || (subtitle_format.is_some() && request.subtitle_options.needs_word_timestamps())
+            {
+                granularities.push(TimestampGranularity::Word);
+            }
+            options.response_format = Some(ResponseFormat::VerboseJson);
+            options.timestamp_granularities = Some(granularities);
+        }
 
-        // Call provider API
-        let response = self
-            .openai_provider
-            .transcribe(
-                &api_key,
-                request.file_data.clone(),
-                request.file_name.clone(),
-                request.model.clone(),
-                request.language.clone(),
-                request.prompt.clone(),
-                request.response_format.clone(),
-                request.temperature,
-                request.timestamp_granularities.clone(),
+        // Call provider API, splitting recordings above the upload limit
+        let configured_limit = self.config.provider_upload_limit_mb as usize * 1024 * 1024;
+        let upload_limit = provider
+            .max_upload_bytes()
+            .map_or(configured_limit, |limit| limit.min(configured_limit));
+        let mut response = if request.file_data.len() > upload_limit {
+            if request.diarize {
+                // Speaker labels are assigned independently per upload
+                return Err(AppError::BadRequest(format!(
+                    "diarize is not supported for audio above the {}MB provider upload limit",
+                    upload_limit / (1024 * 1024)
+                )));
+            }
+            self.transcribe_chunked(
+                provider.as_ref(),
+                api_key.as_deref(),
+                &request,
+                options,
+                &model,
+                upload_limit,
             )
-            .await?;
+            .await?
+        } else {
+            provider
+                .transcribe(
+                    api_key.as_deref(),
+                    request.file_data.clone(),
+                    request.file_name.clone(),
+                    &options,
+                )
+                .await?
+        };
 
         let response_time_ms = start_time.elapsed().as_millis() as u64;
 
+        // Plain text and json responses carry no duration; bill from the header
+        if response.usage.is_none() {
+            response.usage = audio.duration_seconds.map(|duration| TranscriptionUsage {
+                audio_duration_seconds: duration,
+                tokens_used: None,
+                estimated_cost_usd: estimated_cost,
+            });
+        }
+
+        // Nothing comes back to restore from audio, so every mode redacts; the
+        // audio has already been transcribed and billed, so blocking would
+        // only discard it
+        if let Some(scanner) = &pii {
+            scanner.redact_transcript(&mut response);
+        }
+
         // Log usage
         self.log_usage(
             project_id,
+            kind,
+            &model,
             &request,
             &response,
             &file_hash,
@@ -91,6 +216,153 @@ impl TranscriptionService {
         )
         .await?;
 
+        if let Some(format) = subtitle_format {
+            response.text = render_subtitles(&response, format, &request.subtitle_options)?;
+        } else if request.diarize && matches!(request.response_format, Some(ResponseFormat::Text)) {
+            response.text = render_speaker_turns(&response);
+        }
+
+        Ok(response)
+    }
+
+    /// Sniff the container and enforce the configured duration limit
+    pub fn inspect_audio(&self, data: &[u8]) -> Result<AudioInfo, AppError> {
+        let audio = probe_audio(data)?;
+
+        let max_minutes = self.config.max_duration_minutes;
+        if audio
+            .duration_seconds
+            .is_some_and(|d| d > max_minutes as f32 * 60.0)
+        {
+            return Err(AppError::BadRequest(format!(
+                "Audio is longer than the {} minute limit",
+                max_minutes
+            )));
+        }
+
+        Ok(audio)
+    }
+
+    /// Resolve the API key for a provider: an explicit key, the project's
+    /// default key for the matching LLM provider, then the gateway-wide key
+    async fn resolve_api_key(
+        &self,
+        project_id: &str,
+        request: &TranscriptionRequest,
+        kind: TranscriptionProviderKind,
+    ) -> Result<Option<String>, AppError> {
+        if let Some(key_id) = &request.llm_api_key_id {
+            let key = self
+                .llm_key_service
+                .get_project_key(project_id, key_id, kind.llm_provider().as_ref())
+                .await?;
+            return Ok(Some(key));
+        }
+
+        if let Some(llm_provider) = kind.llm_provider() {
+            if let Some(key) = self
+                .llm_key_service
+                .get_default_key_for_provider(project_id, &llm_provider)
+                .await?
+            {
+                return Ok(Some(key));
+            }
+        }
+
+        let configured = match kind {
+            TranscriptionProviderKind::Openai => &self.provider_keys.openai_api_key,
+            TranscriptionProviderKind::Deepgram => &self.provider_keys.deepgram_api_key,
+            TranscriptionProviderKind::Assemblyai => &self.provider_keys.assemblyai_api_key,
+            TranscriptionProviderKind::Google => &self.provider_keys.google_api_key,
+            TranscriptionProviderKind::Azure => &self.provider_keys.azure_speech_key,
+            // Local servers usually run without authentication
+            TranscriptionProviderKind::WhisperServer => {
+                return Ok(self.provider_keys.whisper_server_api_key.clone())
+            }
+        };
+
+        configured.clone().map(Some).ok_or_else(|| {
+            AppError::ConfigError(format!(
+                "No API key configured for transcription provider: {}",
+                kind
+            ))
+        })
+    }
+
+    /// Transcribe a recording too large for a single upload by splitting it
+    /// into overlapping chunks, transcribing them in parallel and stitching
+    /// the results back onto the original timeline
+    async fn transcribe_chunked(
+        &self,
+        provider: &dyn TranscriptionProvider,
+        api_key: Option<&str>,
+        request: &TranscriptionRequest,
+        options: TranscriptionOptions,
+        model: &str,
+        upload_limit: usize,
+    ) -> Result<TranscriptionResponse, AppError> {
+        // Timestamps are needed to de-duplicate overlaps
+        let with_timestamps = provider.returns_timestamps(model);
+        let overlap = if with_timestamps {
+            self.config.chunk_overlap_seconds
+        } else {
+            0.0
+        };
+
+        let extension = SplittableFormat::detect(&request.file_data)
+            .map(|f| f.extension())
+            .unwrap_or("wav");
+        let chunks = split_audio(&request.file_data, upload_limit, overlap)?;
+        let spans: Vec<(f32, f32)> = chunks
+            .iter()
+            .map(|c| (c.offset_seconds, c.duration_seconds))
+            .collect();
+
+        tracing::info!(
+            "Splitting {} ({} bytes) into {} chunks for {}",
+            request.file_name,
+            request.file_data.len(),
+            chunks.len(),
+            provider.kind()
+        );
+
+        let verbose = matches!(options.response_format, Some(ResponseFormat::VerboseJson));
+        let words_requested = options.words_requested();
+        let mut chunk_options = options;
+        if with_timestamps {
+            let mut granularities = vec![TimestampGranularity::Segment];
+            if words_requested {
+                granularities.push(TimestampGranularity::Word);
+            }
+            chunk_options.response_format = Some(ResponseFormat::VerboseJson);
+            chunk_options.timestamp_granularities = Some(granularities);
+        } else {
+            chunk_options.response_format = Some(ResponseFormat::Json);
+            chunk_options.timestamp_granularities = None;
+        }
+
+        let parts: Vec<TranscriptionResponse> = futures::stream::iter(chunks.into_iter().enumerate())
+            .map(|(index, chunk)| {
+                // Built as each chunk is sent, so at most max_parallel_chunks are held at once
+                provider.transcribe(
+                    api_key,
+                    chunk.data(&request.file_data),
+                    format!("chunk_{}.{}", index, extension),
+                    &chunk_options,
+                )
+            })
+            .buffered(self.config.max_parallel_chunks)
+            .try_collect()
+            .await?;
+
+        let mut response = stitch_chunks(&spans, parts, provider.price_per_minute(model));
+        if !verbose {
+            response.segments = None;
+        }
+        if !words_requested {
+            response.words = None;
+        }
+
         Ok(response)
     }
 
@@ -103,9 +375,12 @@ impl TranscriptionService {
     }
 
     /// Log transcription usage
+    #[allow(clippy::too_many_arguments)]
     async fn log_usage(
         &self,
         project_id: String,
+        provider: TranscriptionProviderKind,
+        model: &str,
         request: &TranscriptionRequest,
         response: &TranscriptionResponse,
         file_hash: &str,
@@ -124,17 +399,17 @@ impl TranscriptionService {
 
         let history = TranscriptionHistory::new(
             project_id,
-            LlmProvider::Openai,
+            provider,
             file_hash.to_string(),
             request.file_name.clone(),
             request.file_data.len(),
-            response.duration,
-            request
-                .model
-                .clone()
-                .unwrap_or_else(|| "whisper-1".to_string()),
+            response
+                .duration
+                .or(response.usage.as_ref().map(|u| u.audio_duration_seconds)),
+            model.to_string(),
             response.language.clone(),
             response.text.clone(),
+            request.diarize.then(|| speakers(response)),
             cost_usd,
             response_time_ms,
             from_cache,
@@ -150,4 +425,108 @@ impl TranscriptionService {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Distinct speaker labels in order of first appearance
+fn speakers(response: &TranscriptionResponse) -> Vec<String> {
+    let segment_speakers = response.segments.iter().flatten().filter_map(|s| s.speaker.as_ref());
+    let word_speakers = response.words.iter().flatten().filter_map(|w| w.speaker.as_ref());
+
+    let mut speakers: Vec<String> = Vec::new();
+    for speaker in segment_speakers.chain(word_speakers) {
+        if !speakers.contains(speaker) {
+            speakers.push(speaker.clone());
+        }
+    }
+    speakers
+}
+
+/// Merge per-chunk transcriptions into one response on the original timeline.
+///
+/// Neighbouring chunks share an overlap window; segments and words are kept
+/// from whichever chunk they start in the nearer half of that window, so
+/// speech in the overlap is neither dropped nor duplicated. Chunks whose
+/// reply carries no cost (plain `json` and `text`) are priced from their span.
+fn stitch_chunks(
+    spans: &[(f32, f32)],
+    parts: Vec<TranscriptionResponse>,
+    price_per_minute: f64,
+) -> TranscriptionResponse {
+    // Cut point between chunk i and i + 1: middle of their shared window
+    let cut = |i: usize| {
+        let (offset, duration) = spans[i];
+        (spans[i + 1].0 + offset + duration) / 2.0
+    };
+
+    let mut texts = Vec::new();
+    let mut segments = Vec::new();
+    let mut words = Vec::new();
+    let mut language = None;
+    let mut cost = 0.0;
+    let mut has_timestamps = false;
+
+    let count = parts.len();
+    for (i, part) in parts.into_iter().enumerate() {
+        let offset = spans[i].0;
+        let keep_from = if i == 0 { f32::MIN } else { cut(i - 1) };
+        let keep_until = if i + 1 == count { f32::MAX } else { cut(i) };
+        let keeps = |start: f32| start >= keep_from && start < keep_until;
+
+        if let Some(part_segments) = part.segments {
+            has_timestamps = true;
+            for mut segment in part_segments {
+                segment.start += offset;
+                segment.end += offset;
+                if keeps(segment.start) {
+                    segments.push(segment);
+                }
+            }
+        } else {
+            texts.push(part.text.trim().to_string());
+        }
+
+        for mut word in part.words.unwrap_or_default() {
+            word.start += offset;
+            word.end += offset;
+            if keeps(word.start) {
+                words.push(word);
+            }
+        }
+
+        language = language.or(part.language);
+        cost += part
+            .usage
+            .and_then(|u| u.estimated_cost_usd)
+            .unwrap_or(spans[i].1 as f64 / 60.0 * price_per_minute);
+    }
+
+    for (id, segment) in segments.iter_mut().enumerate() {
+        segment.id = id as i32;
+    }
+
+    let text = if has_timestamps {
+        segments
+            .iter()
+            .map(|s| s.text.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        texts.join(" ")
+    };
+
+    let duration = spans.last().map(|(offset, duration)| offset + duration);
+
+    TranscriptionResponse {
+        text,
+        language,
+        duration,
+        segments: Some(segments),
+        words: Some(words),
+        usage: duration.map(|d| TranscriptionUsage {
+            audio_duration_seconds: d,
+            tokens_used: None,
+            estimated_cost_usd: Some(cost),
+        }),
+    }
+}
@@ -0,0 +1,369 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::api::dto::TranscriptionJobDto;
+use crate::domain::entities::transcription::TranscriptionRequest;
+use crate::domain::entities::transcription_job::{TranscriptionJob, TranscriptionJobStatus};
+use crate::domain::repositories::transcription_job_repository::TranscriptionJobRepository;
+use crate::domain::services::transcription::TranscriptionService;
+use crate::shared::config::TranscriptionConfig;
+use crate::shared::error::AppError;
+use crate::shared::utils::EncryptionService;
+
+/// Attempts before a job interrupted by worker crashes is failed
+const MAX_JOB_ATTEMPTS: u32 = 3;
+
+/// Webhook delivery attempts per job
+const WEBHOOK_ATTEMPTS: u32 = 3;
+
+/// Completion callback requested by the client
+#[derive(Debug, Clone)]
+pub struct JobWebhook {
+    pub url: String,
+    pub secret: Option<String>,
+}
+
+/// Service running transcriptions asynchronously on a background worker pool
+pub struct TranscriptionJobService {
+    repository: Arc<dyn TranscriptionJobRepository>,
+    transcription_service: Arc<TranscriptionService>,
+    encryption: EncryptionService,
+    config: TranscriptionConfig,
+}
+
+impl TranscriptionJobService {
+    pub fn new(
+        repository: Arc<dyn TranscriptionJobRepository>,
+        transcription_service: Arc<TranscriptionService>,
+        encryption: EncryptionService,
+        config: TranscriptionConfig,
+    ) -> Self {
+        Self {
+            repository,
+            transcription_service,
+            encryption,
+            config,
+        }
+    }
+
+    /// Store the upload and queue a job for it
+    pub async fn submit(
+        &self,
+        project_id: String,
+        mut request: TranscriptionRequest,
+        webhook: Option<JobWebhook>,
+    ) -> Result<TranscriptionJob, AppError> {
+        // Fail fast instead of queueing media the worker would reject
+        let audio = self.transcription_service.inspect_audio(&request.file_data)?;
+        request.file_name = audio.format.file_name(&request.file_name);
+
+        if let Some(webhook) = &webhook {
+            resolve_webhook(&webhook.url).await?;
+        }
+
+        let (webhook_url, webhook_secret) = match webhook {
+            Some(JobWebhook { url, secret }) => {
+                let secret = secret.map(|s| self.encryption.encrypt(&s)).transpose()?;
+                (Some(url), secret)
+            }
+            None => (None, None),
+        };
+
+        let upload_id = self
+            .repository
+            .store_upload(&request.file_name, &request.file_data)
+            .await?;
+
+        let job = TranscriptionJob::new(project_id, upload_id, &request, webhook_url, webhook_secret);
+        self.repository.create(&job).await?;
+
+        tracing::info!("Queued transcription job {} ({} bytes)", job.job_id, job.file_size_bytes);
+
+        Ok(job)
+    }
+
+    /// Get job by ID within a project
+    pub async fn get(&self, project_id: &str, job_id: &str) -> Result<TranscriptionJob, AppError> {
+        self.repository.find_by_id(project_id, job_id).await
+    }
+
+    /// Recover jobs interrupted by a previous shutdown and start the worker pool
+    pub fn start_workers(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let stale_after = chrono::Duration::seconds(service.config.job_stale_after_seconds as i64);
+            match service
+                .repository
+                .requeue_stale(Utc::now() - stale_after, MAX_JOB_ATTEMPTS)
+                .await
+            {
+                Ok(0) => {}
+                Ok(count) => tracing::warn!("Requeued {} interrupted transcription jobs", count),
+                Err(e) => tracing::error!("Failed to requeue interrupted transcription jobs: {}", e),
+            }
+
+            for worker in 0..service.config.job_workers {
+                tokio::spawn(service.clone().run_worker(worker));
+            }
+        });
+    }
+
+    async fn run_worker(self: Arc<Self>, worker: usize) {
+        let poll_interval = Duration::from_millis(self.config.job_poll_interval_ms);
+        tracing::debug!("Transcription job worker {} started", worker);
+
+        loop {
+            match self.repository.claim_next().await {
+                Ok(Some(job)) => self.process(job).await,
+                Ok(None) => tokio::time::sleep(poll_interval).await,
+                Err(e) => {
+                    tracing::error!("Worker {} failed to claim transcription job: {}", worker, e);
+                    tokio::time::sleep(poll_interval).await;
+                }
+            }
+        }
+    }
+
+    /// Run a claimed job to completion and notify the client
+    async fn process(self: &Arc<Self>, mut job: TranscriptionJob) {
+        tracing::info!("Processing transcription job {} (attempt {})", job.job_id, job.attempts);
+
+        let outcome = match self.repository.load_upload(&job.upload_id).await {
+            Ok(data) => {
+                self.transcription_service
+                    .transcribe(job.project_id.clone(), job.to_request(data))
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let recorded = match outcome {
+            Ok(result) => {
+                let recorded = self.repository.mark_succeeded(&job.job_id, &result).await;
+                job.status = TranscriptionJobStatus::Succeeded;
+                job.result = Some(result);
+                recorded
+            }
+            Err(e) => {
+                tracing::warn!("Transcription job {} failed: {}", job.job_id, e);
+                let message = e.to_string();
+                let recorded = self.repository.mark_failed(&job.job_id, &message).await;
+                job.status = TranscriptionJobStatus::Failed;
+                job.error = Some(message);
+                recorded
+            }
+        };
+
+        if let Err(e) = recorded {
+            // Leave the upload in place so the job can be requeued
+            tracing::error!("Failed to record result of transcription job {}: {}", job.job_id, e);
+            return;
+        }
+
+        job.completed_at = Some(Utc::now());
+
+        if let Err(e) = self.repository.delete_upload(&job.upload_id).await {
+            tracing::warn!("Failed to delete upload for transcription job {}: {}", job.job_id, e);
+        }
+
+        // Delivery retries with backoff, so it runs on its own task and the
+        // worker moves straight on to the next job
+        if job.webhook_url.is_some() {
+            let service = self.clone();
+            tokio::spawn(async move { service.send_webhook(&job).await });
+        }
+    }
+
+    /// POST the final job state to the client's webhook.
+    ///
+    /// When a secret was supplied the body is signed as
+    /// `X-LLMHub-Signature: t=<unix time>,v1=<hex HMAC-SHA256 of "<t>.<body>">`.
+    async fn send_webhook(&self, job: &TranscriptionJob) {
+        let Some(url) = &job.webhook_url else {
+            return;
+        };
+        // Resolved again at delivery, and the connection pinned to the checked
+        // addresses, so DNS changes since submission can't reach internal hosts
+        let client = match resolve_webhook(url).await {
+            Ok((host, addrs)) => webhook_client().resolve_to_addrs(&host, &addrs).build(),
+            Err(e) => {
+                tracing::error!("Refusing webhook for job {}: {}", job.job_id, e);
+                return;
+            }
+        };
+        let client = match client {
+            Ok(client) => client,
+            Err(e) => {
+                tracing::error!("Failed to build webhook client for job {}: {}", job.job_id, e);
+                return;
+            }
+        };
+
+        let body = match serde_json::to_vec(&TranscriptionJobDto::from(job.clone())) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!("Failed to serialize webhook for job {}: {}", job.job_id, e);
+                return;
+            }
+        };
+
+        let secret = match job.webhook_secret.as_deref().map(|s| self.encryption.decrypt(s)) {
+            Some(Ok(secret)) => Some(secret),
+            Some(Err(e)) => {
+                tracing::error!("Failed to decrypt webhook secret for job {}: {}", job.job_id, e);
+                return;
+            }
+            None => None,
+        };
+
+        let event = match job.status {
+            TranscriptionJobStatus::Succeeded => "transcription.job.succeeded",
+            _ => "transcription.job.failed",
+        };
+
+        for attempt in 1..=WEBHOOK_ATTEMPTS {
+            let mut request = client
+                .post(url)
+                .header("Content-Type", "application/json")
+                .header("X-LLMHub-Event", event)
+                .body(body.clone());
+
+            if let Some(secret) = &secret {
+                let timestamp = Utc::now().timestamp();
+                request = request.header(
+                    "X-LLMHub-Signature",
+                    format!("t={},v1={}", timestamp, sign_webhook(secret, timestamp, &body)),
+                );
+            }
+
+            match request.send().await {
+                Ok(response) if response.status().is_success() => return,
+                Ok(response) => tracing::warn!(
+                    "Webhook for job {} returned {} (attempt {})",
+                    job.job_id,
+                    response.status(),
+                    attempt
+                ),
+                Err(e) => tracing::warn!(
+                    "Webhook for job {} failed: {} (attempt {})",
+                    job.job_id,
+                    e,
+                    attempt
+                ),
+            }
+
+            tokio::time::sleep(Duration::from_secs(2u64.pow(attempt))).await;
+        }
+
+        tracing::error!("Giving up on webhook for transcription job {}", job.job_id);
+    }
+}
+
+fn webhook_client() -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        // A redirect could point at an internal host
+        .redirect(reqwest::redirect::Policy::none())
+}
+
+/// Check a webhook URL is http(s) and resolves only to public addresses,
+/// returning its host and addresses
+async fn resolve_webhook(webhook_url: &str) -> Result<(String, Vec<SocketAddr>), AppError> {
+    let url = reqwest::Url::parse(webhook_url)
+        .map_err(|e| AppError::BadRequest(format!("Invalid webhook_url: {}", e)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(AppError::BadRequest(
+            "webhook_url must use http or https".to_string(),
+        ));
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| AppError::BadRequest("webhook_url must have a host".to_string()))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Cannot resolve webhook_url host {}: {}", host, e)))?
+        .collect();
+    if addrs.is_empty() || addrs.iter().any(|addr| !is_public(addr.ip())) {
+        return Err(AppError::BadRequest(
+            "webhook_url must resolve to a public address".to_string(),
+        ));
+    }
+    Ok((host, addrs))
+}
+
+/// Whether an address is reachable on the public internet rather than the
+/// gateway's own host or network
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || a == 0
+                // Carrier-grade NAT, 100.64.0.0/10
+                || (a == 100 && (b & 0xc0) == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_public(IpAddr::V4(v4)),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    // Unique local fc00::/7 and link-local fe80::/10
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80)
+            }
+        },
+    }
+}
+
+/// Hex HMAC-SHA256 over `"<timestamp>.<body>"`
+fn sign_webhook(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rejects_webhooks_to_internal_addresses() {
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://localhost:8080/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://10.0.0.5/hook",
+            "https://192.168.1.1/hook",
+            "http://[::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+            "http://0.0.0.0/hook",
+            "ftp://example.com/hook",
+        ] {
+            assert!(resolve_webhook(url).await.is_err(), "{} was accepted", url);
+        }
+
+        assert!(is_public("93.184.216.34".parse().unwrap()));
+        assert!(is_public("2606:2800:220:1::".parse().unwrap()));
+        assert!(!is_public("100.64.0.1".parse().unwrap()));
+        assert!(!is_public("fd00::1".parse().unwrap()));
+    }
+}
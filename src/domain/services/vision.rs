@@ -0,0 +1,129 @@
+//! Image inputs for chat: data URL handling, dimension sniffing and
+//! OpenAI-style image token estimates.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+/// Image types accepted in data URLs
+pub const SUPPORTED_IMAGE_TYPES: [&str; 4] = ["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Largest inline image accepted
+pub const MAX_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+/// Largest inline `input_audio` clip accepted
+pub const MAX_INPUT_AUDIO_BYTES: usize = 25 * 1024 * 1024;
+
+/// Base64 characters decoded when sniffing dimensions; JPEG frame headers
+/// can sit behind large EXIF blocks
+const HEADER_CHARS: usize = 128 * 1024;
+
+/// Split `data:<mime>;base64,<data>` into its MIME type and payload
+pub fn split_data_url(url: &str) -> Option<(&str, &str)> {
+    let (header, data) = url.strip_prefix("data:")?.split_once(',')?;
+    let mime = header.strip_suffix(";base64")?;
+    Some((mime, data))
+}
+
+/// Decode the start of a base64 payload
+pub fn decode_header(data: &str) -> Option<Vec<u8>> {
+    let end = data.len().min(HEADER_CHARS) / 4 * 4;
+    let prefix = data.get(..end)?;
+    // A full-length payload may end in padding; a truncated one never does
+    BASE64.decode(prefix).ok()
+}
+
+/// Width and height from a PNG, GIF, JPEG or WebP header
+pub fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| Some(u16::from_be_bytes([*data.get(at)?, *data.get(at + 1)?]) as u32);
+    let le16 = |at: usize| Some(u16::from_le_bytes([*data.get(at)?, *data.get(at + 1)?]) as u32);
+    let le24 = |at: usize| Some(le16(at)? | (*data.get(at + 2)? as u32) << 16);
+
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        let width = u32::from_be_bytes(data.get(16..20)?.try_into().ok()?);
+        let height = u32::from_be_bytes(data.get(20..24)?.try_into().ok()?);
+        return Some((width, height));
+    }
+
+    if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        return Some((le16(6)?, le16(8)?));
+    }
+
+    if data.starts_with(b"RIFF") && data.get(8..12)? == b"WEBP" {
+        return match data.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3FFF, le16(28)? & 0x3FFF)),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(data.get(21..25)?.try_into().ok()?);
+                Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+            }
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            _ => None,
+        };
+    }
+
+    if data.starts_with(&[0xFF, 0xD8]) {
+        // Walk the marker segments to the first start-of-frame
+        let mut pos = 2;
+        while pos + 4 <= data.len() {
+            if data[pos] != 0xFF {
+                return None;
+            }
+            let marker = data[pos + 1];
+            let len = be16(pos + 2)? as usize;
+            let is_frame = matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+            if is_frame {
+                return Some((be16(pos + 7)?, be16(pos + 5)?));
+            }
+            pos += 2 + len;
+        }
+    }
+
+    None
+}
+
+/// Prompt tokens OpenAI charges for an image: a flat 85 at low detail,
+/// otherwise 85 plus 170 per 512px tile after scaling to fit 2048x2048
+/// with the shortest side at most 768
+pub fn image_tokens((width, height): (u32, u32), low_detail: bool) -> u32 {
+    if low_detail || width == 0 || height == 0 {
+        return 85;
+    }
+
+    let (mut w, mut h) = (width as f64, height as f64);
+    if w.max(h) > 2048.0 {
+        let scale = 2048.0 / w.max(h);
+        w *= scale;
+        h *= scale;
+    }
+    if w.min(h) > 768.0 {
+        let scale = 768.0 / w.min(h);
+        w *= scale;
+        h *= scale;
+    }
+
+    let tiles = (w / 512.0).ceil() * (h / 512.0).ceil();
+    85 + 170 * tiles as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_tokens_from_data_url_dimensions() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        png.extend_from_slice(&2048u32.to_be_bytes());
+        png.extend_from_slice(&4096u32.to_be_bytes());
+        png.extend_from_slice(&[8, 6, 0, 0, 0]);
+        let url = format!("data:image/png;base64,{}", BASE64.encode(&png));
+
+        let (mime, data) = split_data_url(&url).unwrap();
+        assert_eq!(mime, "image/png");
+        let dimensions = image_dimensions(&decode_header(data).unwrap()).unwrap();
+        assert_eq!(dimensions, (2048, 4096));
+
+        // 1024x2048 → 768x1536 → 2x3 tiles
+        assert_eq!(image_tokens(dimensions, false), 85 + 170 * 6);
+        assert_eq!(image_tokens((1024, 1024), false), 765);
+        assert_eq!(image_tokens(dimensions, true), 85);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod mongodb;
 
 pub use mongodb::{
-    connect_mongodb, MongoLlmApiKeyRepository, MongoProjectRepository,
-    MongoTranscriptionRepository, MongoUsageRepository,
+    connect_mongodb, MongoBatchRepository, MongoFileRepository, MongoGuardrailRepository,
+    MongoLlmApiKeyRepository, MongoProjectRepository, MongoResponseRepository,
+    MongoTranscriptionJobRepository, MongoTranscriptionRepository, MongoUsageRepository,
 };
\ No newline at end of file
@@ -0,0 +1,207 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use mongodb::{bson::doc, options::ReturnDocument, Collection, Database};
+
+use crate::domain::entities::batch::{BatchJob, BatchRequestCounts, BatchStatus};
+use crate::domain::repositories::batch_repository::BatchRepository;
+use crate::shared::error::AppError;
+
+pub struct MongoBatchRepository {
+    batches: Collection<BatchJob>,
+}
+
+impl MongoBatchRepository {
+    pub fn new(db: Database) -> Self {
+        Self {
+            batches: db.collection::<BatchJob>("batches"),
+        }
+    }
+}
+
+#[async_trait]
+impl BatchRepository for MongoBatchRepository {
+    async fn create(&self, batch: &BatchJob) -> Result<(), AppError> {
+        self.batches.insert_one(batch).await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, project_id: &str, batch_id: &str) -> Result<BatchJob, AppError> {
+        self.batches
+            .find_one(doc! { "batch_id": batch_id, "project_id": project_id })
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Batch {} not found", batch_id)))
+    }
+
+    async fn list(
+        &self,
+        project_id: &str,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<BatchJob>, AppError> {
+        let mut filter = doc! { "project_id": project_id };
+        if let Some(before) = before {
+            filter.insert("created_at", doc! { "$lt": bson::to_bson(&before)? });
+        }
+
+        Ok(self
+            .batches
+            .find(filter)
+            .sort(doc! { "created_at": -1 })
+            .limit(limit)
+            .await?
+            .try_collect()
+            .await?)
+    }
+
+    async fn status(&self, batch_id: &str) -> Result<BatchStatus, AppError> {
+        self.batches
+            .find_one(doc! { "batch_id": batch_id })
+            .await?
+            .map(|batch| batch.status)
+            .ok_or_else(|| AppError::NotFound(format!("Batch {} not found", batch_id)))
+    }
+
+    async fn claim_next(&self) -> Result<Option<BatchJob>, AppError> {
+        let now = bson::to_bson(&Utc::now())?;
+
+        Ok(self
+            .batches
+            .find_one_and_update(
+                doc! { "status": bson::to_bson(&BatchStatus::Validating)? },
+                doc! {
+                    "$set": {
+                        "status": bson::to_bson(&BatchStatus::InProgress)?,
+                        "in_progress_at": now.clone(),
+                        "updated_at": now,
+                    },
+                    "$inc": { "attempts": 1 },
+                },
+            )
+            .sort(doc! { "created_at": 1 })
+            .return_document(ReturnDocument::After)
+            .await?)
+    }
+
+    async fn update_progress(
+        &self,
+        batch_id: &str,
+        counts: &BatchRequestCounts,
+        cost_usd: f64,
+    ) -> Result<(), AppError> {
+        self.batches
+            .update_one(
+                doc! { "batch_id": batch_id },
+                doc! { "$set": {
+                    "request_counts": bson::to_bson(counts)?,
+                    "cost_usd": cost_usd,
+                    "updated_at": bson::to_bson(&Utc::now())?,
+                } },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn save(&self, batch: &BatchJob) -> Result<(), AppError> {
+        self.batches
+            .replace_one(doc! { "batch_id": &batch.batch_id }, batch)
+            .await?;
+        Ok(())
+    }
+
+    async fn cancel(&self, project_id: &str, batch_id: &str) -> Result<BatchJob, AppError> {
+        let now = bson::to_bson(&Utc::now())?;
+
+        // Queued batches are cancelled outright; running ones are stopped by their worker
+        let transitions = [
+            (BatchStatus::Validating, BatchStatus::Cancelled, "cancelled_at"),
+            (BatchStatus::InProgress, BatchStatus::Cancelling, "cancelling_at"),
+        ];
+        for (from, to, timestamp) in transitions {
+            let cancelled = self
+                .batches
+                .find_one_and_update(
+                    doc! {
+                        "batch_id": batch_id,
+                        "project_id": project_id,
+                        "status": bson::to_bson(&from)?,
+                    },
+                    doc! { "$set": {
+                        "status": bson::to_bson(&to)?,
+                        timestamp: now.clone(),
+                        "updated_at": now.clone(),
+                    } },
+                )
+                .return_document(ReturnDocument::After)
+                .await?;
+            if let Some(batch) = cancelled {
+                return Ok(batch);
+            }
+        }
+
+        let batch = self.find_by_id(project_id, batch_id).await?;
+        match batch.status {
+            BatchStatus::Cancelling | BatchStatus::Cancelled => Ok(batch),
+            status => Err(AppError::BadRequest(format!(
+                "Batch {} cannot be cancelled in status {:?}",
+                batch_id, status
+            ))),
+        }
+    }
+
+    async fn requeue_stale(&self, started_before: DateTime<Utc>, max_attempts: u32) -> Result<u64, AppError> {
+        let now = bson::to_bson(&Utc::now())?;
+        let running = doc! { "$in": [
+            bson::to_bson(&BatchStatus::InProgress)?,
+            bson::to_bson(&BatchStatus::Finalizing)?,
+        ] };
+        let cutoff = bson::to_bson(&started_before)?;
+
+        self.batches
+            .update_many(
+                doc! {
+                    "status": bson::to_bson(&BatchStatus::Cancelling)?,
+                    "in_progress_at": { "$lt": cutoff.clone() },
+                },
+                doc! { "$set": {
+                    "status": bson::to_bson(&BatchStatus::Cancelled)?,
+                    "cancelled_at": now.clone(),
+                    "updated_at": now.clone(),
+                } },
+            )
+            .await?;
+
+        self.batches
+            .update_many(
+                doc! {
+                    "status": running.clone(),
+                    "in_progress_at": { "$lt": cutoff.clone() },
+                    "attempts": { "$gte": max_attempts },
+                },
+                doc! { "$set": {
+                    "status": bson::to_bson(&BatchStatus::Failed)?,
+                    "errors": [{ "code": "worker_interrupted", "message": "Batch exceeded the maximum number of attempts", "line": null }],
+                    "failed_at": now.clone(),
+                    "updated_at": now.clone(),
+                } },
+            )
+            .await?;
+
+        let result = self
+            .batches
+            .update_many(
+                doc! {
+                    "status": running,
+                    "in_progress_at": { "$lt": cutoff },
+                },
+                doc! { "$set": {
+                    "status": bson::to_bson(&BatchStatus::Validating)?,
+                    "updated_at": now,
+                } },
+            )
+            .await?;
+
+        Ok(result.modified_count)
+    }
+}
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use futures::{AsyncReadExt, AsyncWriteExt, TryStreamExt};
+use mongodb::{
+    bson::{doc, oid::ObjectId, Bson},
+    gridfs::GridFsBucket,
+    options::GridFsBucketOptions,
+    Collection, Database,
+};
+
+use crate::domain::entities::file::StoredFile;
+use crate::domain::repositories::file_repository::FileRepository;
+use crate::shared::error::AppError;
+
+pub struct MongoFileRepository {
+    files: Collection<StoredFile>,
+    contents: GridFsBucket,
+}
+
+impl MongoFileRepository {
+    pub fn new(db: Database) -> Self {
+        Self {
+            files: db.collection::<StoredFile>("files"),
+            contents: db.gridfs_bucket(
+                GridFsBucketOptions::builder()
+                    .bucket_name("file_contents".to_string())
+                    .build(),
+            ),
+        }
+    }
+}
+
+fn parse_upload_id(upload_id: &str) -> Result<Bson, AppError> {
+    ObjectId::parse_str(upload_id)
+        .map(Bson::ObjectId)
+        .map_err(|_| AppError::InternalError(format!("Invalid upload ID: {}", upload_id)))
+}
+
+#[async_trait]
+impl FileRepository for MongoFileRepository {
+    async fn create(&self, mut file: StoredFile, data: &[u8]) -> Result<StoredFile, AppError> {
+        let mut stream = self.contents.open_upload_stream(&file.filename).await?;
+        stream.write_all(data).await?;
+        stream.close().await?;
+
+        file.upload_id = match stream.id() {
+            Bson::ObjectId(id) => id.to_hex(),
+            other => {
+                return Err(AppError::InternalError(format!(
+                    "Unexpected GridFS file ID: {}",
+                    other
+                )))
+            }
+        };
+
+        self.files.insert_one(&file).await?;
+        Ok(file)
+    }
+
+    async fn find_by_id(&self, project_id: &str, file_id: &str) -> Result<StoredFile, AppError> {
+        self.files
+            .find_one(doc! { "file_id": file_id, "project_id": project_id })
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("File {} not found", file_id)))
+    }
+
+    async fn list(&self, project_id: &str, purpose: Option<&str>, limit: i64) -> Result<Vec<StoredFile>, AppError> {
+        let mut filter = doc! { "project_id": project_id };
+        if let Some(purpose) = purpose {
+            filter.insert("purpose", purpose);
+        }
+
+        Ok(self
+            .files
+            .find(filter)
+            .sort(doc! { "created_at": -1 })
+            .limit(limit)
+            .await?
+            .try_collect()
+            .await?)
+    }
+
+    async fn load_content(&self, file: &StoredFile) -> Result<Vec<u8>, AppError> {
+        let mut stream = self
+            .contents
+            .open_download_stream(parse_upload_id(&file.upload_id)?)
+            .await?;
+
+        let mut data = Vec::new();
+        stream.read_to_end(&mut data).await?;
+        Ok(data)
+    }
+
+    async fn delete(&self, project_id: &str, file_id: &str) -> Result<(), AppError> {
+        let file = self.find_by_id(project_id, file_id).await?;
+        self.contents.delete(parse_upload_id(&file.upload_id)?).await?;
+        self.files
+            .delete_one(doc! { "file_id": file_id, "project_id": project_id })
+            .await?;
+        Ok(())
+    }
+}
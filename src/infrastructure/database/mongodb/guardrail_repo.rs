@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use mongodb::{bson::doc, Collection, Database};
+
+use crate::domain::entities::guardrail::GuardrailPolicy;
+use crate::domain::repositories::guardrail_repository::GuardrailRepository;
+use crate::shared::error::AppError;
+
+pub struct MongoGuardrailRepository {
+    collection: Collection<GuardrailPolicy>,
+}
+
+impl MongoGuardrailRepository {
+    pub fn new(db: Database) -> Self {
+        Self {
+            collection: db.collection::<GuardrailPolicy>("guardrail_policies"),
+        }
+    }
+}
+
+#[async_trait]
+impl GuardrailRepository for MongoGuardrailRepository {
+    async fn find_by_project(&self, project_id: &str) -> Result<Option<GuardrailPolicy>, AppError> {
+        Ok(self.collection.find_one(doc! { "project_id": project_id }).await?)
+    }
+}
@@ -28,6 +28,15 @@ impl LlmApiKeyRepository for MongoLlmApiKeyRepository {
             .ok_or_else(|| AppError::NotFound(format!("LLM API key {} not found", key_id)))
     }
 
+    async fn find_by_project(&self, project_id: &str, key_id: &str) -> Result<LlmApiKey, AppError> {
+        let collection = self.db.collection::<LlmApiKey>("llm_api_keys");
+
+        collection
+            .find_one(doc! { "key_id": key_id, "project_id": project_id })
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("LLM API key {} not found", key_id)))
+    }
+
     async fn find_default_for_provider(
         &self,
         project_id: &str,
@@ -1,12 +1,22 @@
+pub mod batch_repo;
+pub mod file_repo;
+pub mod guardrail_repo;
 pub mod llm_api_key_repo;
 pub mod project_repo;
+pub mod response_repo;
+pub mod transcription_job_repo;
 pub mod transcription_repo;
 pub mod usage_repo;
 
 use mongodb::{Client, Database};
 
+pub use batch_repo::MongoBatchRepository;
+pub use file_repo::MongoFileRepository;
+pub use guardrail_repo::MongoGuardrailRepository;
 pub use llm_api_key_repo::MongoLlmApiKeyRepository;
 pub use project_repo::MongoProjectRepository;
+pub use response_repo::MongoResponseRepository;
+pub use transcription_job_repo::MongoTranscriptionJobRepository;
 pub use transcription_repo::MongoTranscriptionRepository;
 pub use usage_repo::MongoUsageRepository;
 
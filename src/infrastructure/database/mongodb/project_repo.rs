@@ -93,7 +93,6 @@ impl ProjectRepository for MongoProjectRepository {
 
                         // Find associated project
                         return self.find_by_id(&key_doc.project_id).await;
-                    } else {
                     }
                 }
                 Err(e) => {
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use mongodb::{bson::doc, Collection, Database};
+
+use crate::domain::entities::stored_response::StoredResponse;
+use crate::domain::repositories::response_repository::ResponseRepository;
+use crate::shared::error::AppError;
+
+pub struct MongoResponseRepository {
+    collection: Collection<StoredResponse>,
+}
+
+impl MongoResponseRepository {
+    pub fn new(db: Database) -> Self {
+        Self {
+            collection: db.collection::<StoredResponse>("responses"),
+        }
+    }
+}
+
+#[async_trait]
+impl ResponseRepository for MongoResponseRepository {
+    async fn create(&self, response: &StoredResponse) -> Result<(), AppError> {
+        self.collection.insert_one(response).await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, project_id: &str, response_id: &str) -> Result<StoredResponse, AppError> {
+        self.collection
+            .find_one(doc! { "response_id": response_id, "project_id": project_id })
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Response {} not found", response_id)))
+    }
+
+    async fn delete(&self, project_id: &str, response_id: &str) -> Result<(), AppError> {
+        let result = self
+            .collection
+            .delete_one(doc! { "response_id": response_id, "project_id": project_id })
+            .await?;
+
+        if result.deleted_count == 0 {
+            return Err(AppError::NotFound(format!("Response {} not found", response_id)));
+        }
+        Ok(())
+    }
+}
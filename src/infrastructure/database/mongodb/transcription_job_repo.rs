@@ -0,0 +1,178 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{AsyncReadExt, AsyncWriteExt};
+use mongodb::{
+    bson::{doc, oid::ObjectId, Bson},
+    gridfs::GridFsBucket,
+    options::{GridFsBucketOptions, ReturnDocument},
+    Collection, Database,
+};
+
+use crate::domain::entities::transcription::TranscriptionResponse;
+use crate::domain::entities::transcription_job::{TranscriptionJob, TranscriptionJobStatus};
+use crate::domain::repositories::transcription_job_repository::TranscriptionJobRepository;
+use crate::shared::error::AppError;
+
+pub struct MongoTranscriptionJobRepository {
+    jobs: Collection<TranscriptionJob>,
+    uploads: GridFsBucket,
+}
+
+impl MongoTranscriptionJobRepository {
+    pub fn new(db: Database) -> Self {
+        Self {
+            jobs: db.collection::<TranscriptionJob>("transcription_jobs"),
+            uploads: db.gridfs_bucket(
+                GridFsBucketOptions::builder()
+                    .bucket_name("transcription_uploads".to_string())
+                    .build(),
+            ),
+        }
+    }
+}
+
+fn parse_upload_id(upload_id: &str) -> Result<Bson, AppError> {
+    ObjectId::parse_str(upload_id)
+        .map(Bson::ObjectId)
+        .map_err(|_| AppError::InternalError(format!("Invalid upload ID: {}", upload_id)))
+}
+
+#[async_trait]
+impl TranscriptionJobRepository for MongoTranscriptionJobRepository {
+    async fn store_upload(&self, file_name: &str, data: &[u8]) -> Result<String, AppError> {
+        let mut stream = self.uploads.open_upload_stream(file_name).await?;
+        stream.write_all(data).await?;
+        stream.close().await?;
+
+        match stream.id() {
+            Bson::ObjectId(id) => Ok(id.to_hex()),
+            other => Err(AppError::InternalError(format!(
+                "Unexpected GridFS file ID: {}",
+                other
+            ))),
+        }
+    }
+
+    async fn load_upload(&self, upload_id: &str) -> Result<Vec<u8>, AppError> {
+        let mut stream = self
+            .uploads
+            .open_download_stream(parse_upload_id(upload_id)?)
+            .await?;
+
+        let mut data = Vec::new();
+        stream.read_to_end(&mut data).await?;
+        Ok(data)
+    }
+
+    async fn delete_upload(&self, upload_id: &str) -> Result<(), AppError> {
+        self.uploads.delete(parse_upload_id(upload_id)?).await?;
+        Ok(())
+    }
+
+    async fn create(&self, job: &TranscriptionJob) -> Result<(), AppError> {
+        self.jobs.insert_one(job).await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, project_id: &str, job_id: &str) -> Result<TranscriptionJob, AppError> {
+        self.jobs
+            .find_one(doc! { "job_id": job_id, "project_id": project_id })
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Transcription job {} not found", job_id)))
+    }
+
+    async fn claim_next(&self) -> Result<Option<TranscriptionJob>, AppError> {
+        let now = bson::to_bson(&Utc::now())?;
+
+        Ok(self
+            .jobs
+            .find_one_and_update(
+                doc! { "status": bson::to_bson(&TranscriptionJobStatus::Queued)? },
+                doc! {
+                    "$set": {
+                        "status": bson::to_bson(&TranscriptionJobStatus::Running)?,
+                        "started_at": now.clone(),
+                        "updated_at": now,
+                    },
+                    "$inc": { "attempts": 1 },
+                },
+            )
+            .sort(doc! { "created_at": 1 })
+            .return_document(ReturnDocument::After)
+            .await?)
+    }
+
+    async fn mark_succeeded(&self, job_id: &str, result: &TranscriptionResponse) -> Result<(), AppError> {
+        let now = bson::to_bson(&Utc::now())?;
+
+        self.jobs
+            .update_one(
+                doc! { "job_id": job_id },
+                doc! { "$set": {
+                    "status": bson::to_bson(&TranscriptionJobStatus::Succeeded)?,
+                    "result": bson::to_bson(result)?,
+                    "completed_at": now.clone(),
+                    "updated_at": now,
+                } },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(&self, job_id: &str, error: &str) -> Result<(), AppError> {
+        let now = bson::to_bson(&Utc::now())?;
+
+        self.jobs
+            .update_one(
+                doc! { "job_id": job_id },
+                doc! { "$set": {
+                    "status": bson::to_bson(&TranscriptionJobStatus::Failed)?,
+                    "error": error,
+                    "completed_at": now.clone(),
+                    "updated_at": now,
+                } },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn requeue_stale(&self, started_before: DateTime<Utc>, max_attempts: u32) -> Result<u64, AppError> {
+        let now = bson::to_bson(&Utc::now())?;
+        let running = bson::to_bson(&TranscriptionJobStatus::Running)?;
+        let cutoff = bson::to_bson(&started_before)?;
+
+        self.jobs
+            .update_many(
+                doc! {
+                    "status": running.clone(),
+                    "started_at": { "$lt": cutoff.clone() },
+                    "attempts": { "$gte": max_attempts },
+                },
+                doc! { "$set": {
+                    "status": bson::to_bson(&TranscriptionJobStatus::Failed)?,
+                    "error": "Job exceeded the maximum number of attempts",
+                    "completed_at": now.clone(),
+                    "updated_at": now.clone(),
+                } },
+            )
+            .await?;
+
+        let result = self
+            .jobs
+            .update_many(
+                doc! {
+                    "status": running,
+                    "started_at": { "$lt": cutoff },
+                },
+                doc! { "$set": {
+                    "status": bson::to_bson(&TranscriptionJobStatus::Queued)?,
+                    "updated_at": now,
+                } },
+            )
+            .await?;
+
+        Ok(result.modified_count)
+    }
+}
@@ -11,10 +11,14 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-use domain::services::{LlmApiKeyService, TranscriptionService};
+use domain::services::{
+    BatchService, ChatGuardrails, ImageService, LlmApiKeyService, ModerationService, RealtimeService,
+    SpeechService, TokenRateLimiter, TranscriptionJobService, TranscriptionService,
+};
 use infrastructure::{
-    connect_mongodb, MongoLlmApiKeyRepository, MongoProjectRepository,
-    MongoTranscriptionRepository, MongoUsageRepository,
+    connect_mongodb, MongoBatchRepository, MongoFileRepository, MongoGuardrailRepository,
+    MongoLlmApiKeyRepository, MongoProjectRepository, MongoResponseRepository,
+    MongoTranscriptionJobRepository, MongoTranscriptionRepository, MongoUsageRepository,
 };
 use shared::{Config, EncryptionService};
 
@@ -28,8 +32,21 @@ pub struct AppState {
     pub llm_key_repo: Arc<dyn domain::repositories::LlmApiKeyRepository>,
     pub transcription_repo: Arc<dyn domain::repositories::TranscriptionRepository>,
     pub usage_repo: Arc<dyn domain::repositories::UsageRepository>,
+    pub transcription_job_repo: Arc<dyn domain::repositories::TranscriptionJobRepository>,
+    pub response_repo: Arc<dyn domain::repositories::ResponseRepository>,
+    pub file_repo: Arc<dyn domain::repositories::FileRepository>,
+    pub batch_repo: Arc<dyn domain::repositories::BatchRepository>,
+    pub guardrail_repo: Arc<dyn domain::repositories::GuardrailRepository>,
     pub llm_key_service: Arc<LlmApiKeyService>,
     pub transcription_service: Arc<TranscriptionService>,
+    pub speech_service: Arc<SpeechService>,
+    pub image_service: Arc<ImageService>,
+    pub moderation_service: Arc<ModerationService>,
+    pub chat_guardrails: Arc<ChatGuardrails>,
+    pub token_rate_limiter: Arc<TokenRateLimiter>,
+    pub transcription_job_service: Arc<TranscriptionJobService>,
+    pub realtime_service: Arc<RealtimeService>,
+    pub batch_service: Arc<BatchService>,
 }
 
 fn create_trace_layer(
@@ -134,18 +151,81 @@ async fn main() -> anyhow::Result<()> {
     let llm_key_repo = Arc::new(MongoLlmApiKeyRepository::new(db.clone()));
     let transcription_repo = Arc::new(MongoTranscriptionRepository::new(db.clone()));
     let usage_repo = Arc::new(MongoUsageRepository::new(db.clone()));
+    let transcription_job_repo = Arc::new(MongoTranscriptionJobRepository::new(db.clone()));
+    let response_repo = Arc::new(MongoResponseRepository::new(db.clone()));
+    let file_repo = Arc::new(MongoFileRepository::new(db.clone()));
+    let batch_repo = Arc::new(MongoBatchRepository::new(db.clone()));
+    let guardrail_repo = Arc::new(MongoGuardrailRepository::new(db.clone()));
 
     // Initialize services
     let llm_key_service = Arc::new(LlmApiKeyService::new(
         llm_key_repo.clone(),
-        encryption,
+        encryption.clone(),
     ));
 
     let transcription_service = Arc::new(TranscriptionService::new(
         transcription_repo.clone(),
+        guardrail_repo.clone(),
+        llm_key_service.clone(),
+        config.transcription.clone(),
+        config.providers.clone(),
+    ));
+
+    let speech_service = Arc::new(SpeechService::new(
+        usage_repo.clone(),
         llm_key_service.clone(),
+        config.providers.speech_base_url.clone(),
     ));
 
+    let image_service = Arc::new(ImageService::new(
+        usage_repo.clone(),
+        llm_key_service.clone(),
+        config.providers.images_base_url.clone(),
+    ));
+
+    let moderation_service = Arc::new(ModerationService::new(
+        usage_repo.clone(),
+        llm_key_service.clone(),
+    ));
+
+    let realtime_service = Arc::new(RealtimeService::new(
+        usage_repo.clone(),
+        llm_key_service.clone(),
+        config.realtime.clone(),
+    ));
+
+    let transcription_job_service = Arc::new(TranscriptionJobService::new(
+        transcription_job_repo.clone(),
+        transcription_service.clone(),
+        encryption,
+        config.transcription.clone(),
+    ));
+    transcription_job_service.start_workers();
+    info!(
+        "✅ Started {} transcription job workers",
+        config.transcription.job_workers
+    );
+
+    let chat_guardrails = Arc::new(ChatGuardrails::new(
+        guardrail_repo.clone(),
+        usage_repo.clone(),
+        moderation_service.clone(),
+    ));
+    let token_rate_limiter = Arc::new(TokenRateLimiter::new());
+
+    let batch_service = Arc::new(BatchService::new(
+        file_repo.clone(),
+        batch_repo.clone(),
+        project_repo.clone(),
+        usage_repo.clone(),
+        chat_guardrails.clone(),
+        token_rate_limiter.clone(),
+        config.batch.clone(),
+        config.chat.clone(),
+    ));
+    batch_service.start_workers();
+    info!("✅ Started {} batch workers", config.batch.workers);
+
     // Create application state with all services
     let state = Arc::new(AppState {
         start_time: Instant::now(),
@@ -156,8 +236,21 @@ async fn main() -> anyhow::Result<()> {
         llm_key_repo: llm_key_repo.clone(),
         transcription_repo: transcription_repo.clone(),
         usage_repo: usage_repo.clone(),
+        transcription_job_repo: transcription_job_repo.clone(),
+        response_repo: response_repo.clone(),
+        file_repo: file_repo.clone(),
+        batch_repo: batch_repo.clone(),
+        guardrail_repo: guardrail_repo.clone(),
         llm_key_service: llm_key_service.clone(),
         transcription_service: transcription_service.clone(),
+        speech_service: speech_service.clone(),
+        image_service: image_service.clone(),
+        moderation_service: moderation_service.clone(),
+        chat_guardrails: chat_guardrails.clone(),
+        token_rate_limiter: token_rate_limiter.clone(),
+        transcription_job_service: transcription_job_service.clone(),
+        realtime_service: realtime_service.clone(),
+        batch_service: batch_service.clone(),
     });
 
     // Create routers
@@ -168,6 +261,67 @@ async fn main() -> anyhow::Result<()> {
             api::middleware::authenticate,
         ));
     let audio_routes = api::routers::audio_router()
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.project_repo.clone(),
+            api::middleware::authenticate,
+        ))
+        // Long recordings are chunked server-side; per-project limits are enforced in handlers
+        .layer(DefaultBodyLimit::max(
+            config.transcription.max_upload_size_mb as usize * 1024 * 1024,
+        ));
+
+    let images_routes = api::routers::images_router()
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.project_repo.clone(),
+            api::middleware::authenticate,
+        ));
+
+    let messages_routes = api::routers::messages_router()
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.project_repo.clone(),
+            api::middleware::authenticate_anthropic,
+        ));
+
+    let gemini_routes = api::routers::gemini_router()
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.project_repo.clone(),
+            api::middleware::authenticate_gemini,
+        ));
+
+    let responses_routes = api::routers::responses_router()
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.project_repo.clone(),
+            api::middleware::authenticate,
+        ));
+
+    let files_routes = api::routers::files_router()
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.project_repo.clone(),
+            api::middleware::authenticate,
+        ))
+        .layer(DefaultBodyLimit::max(
+            config.batch.max_file_size_mb as usize * 1024 * 1024,
+        ));
+
+    let batches_routes = api::routers::batches_router()
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.project_repo.clone(),
+            api::middleware::authenticate,
+        ));
+
+    let moderations_routes = api::routers::moderations_router()
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.project_repo.clone(),
+            api::middleware::authenticate,
+        ));
+
+    let realtime_routes = api::routers::realtime_router()
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.project_repo.clone(),
+            api::middleware::authenticate_realtime,
+        ));
+
+    let tokenize_routes = api::routers::tokenize_router()
         .route_layer(axum::middleware::from_fn_with_state(
             state.project_repo.clone(),
             api::middleware::authenticate,
@@ -179,7 +333,16 @@ async fn main() -> anyhow::Result<()> {
         .merge(health_routes)
         // API v1 routes
         .nest("/v1/chat", chat_routes)
+        .nest("/v1/messages", messages_routes)
+        .nest("/v1/responses", responses_routes)
+        .nest("/v1beta", gemini_routes)
         .nest("/v1/audio", audio_routes)
+        .nest("/v1/images", images_routes)
+        .nest("/v1/files", files_routes)
+        .nest("/v1/batches", batches_routes)
+        .nest("/v1/moderations", moderations_routes)
+        .nest("/v1/realtime", realtime_routes)
+        .nest("/v1/tokenize", tokenize_routes)
         // Add state
         .with_state(state.clone());
 
@@ -196,7 +359,7 @@ async fn main() -> anyhow::Result<()> {
     // Add middleware
     // Note: Order matters! CORS should be outermost, then tracing
     let app = app
-        .layer(DefaultBodyLimit::max(25 * 1024 * 1024)) // 25MB max body size for audio file uploads
+        .layer(DefaultBodyLimit::max(25 * 1024 * 1024)) // 25MB default max body size
         .layer(create_trace_layer())
         .layer(api::middleware::cors_layer())
         // Fallback handler
@@ -9,6 +9,10 @@ pub struct Config {
     pub database: DatabaseConfig,
     pub security: SecurityConfig,
     pub providers: ProvidersConfig,
+    pub transcription: TranscriptionConfig,
+    pub realtime: RealtimeConfig,
+    pub chat: ChatConfig,
+    pub batch: BatchConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -49,6 +53,58 @@ pub struct ProvidersConfig {
     pub anthropic_api_key: Option<String>,
     #[serde(skip_serializing)]
     pub google_api_key: Option<String>,
+    #[serde(skip_serializing)]
+    pub deepgram_api_key: Option<String>,
+    #[serde(skip_serializing)]
+    pub assemblyai_api_key: Option<String>,
+    #[serde(skip_serializing)]
+    pub azure_speech_key: Option<String>,
+    /// Azure AI Speech resource region, required for the `azure` transcription provider
+    pub azure_speech_region: Option<String>,
+    /// OpenAI-compatible TTS server base URL (defaults to OpenAI)
+    pub speech_base_url: Option<String>,
+    /// OpenAI-compatible image generation server base URL (defaults to OpenAI)
+    pub images_base_url: Option<String>,
+    /// Self-hosted OpenAI-compatible Whisper server, enables the `whisper_server` provider
+    pub whisper_server_url: Option<String>,
+    #[serde(skip_serializing)]
+    pub whisper_server_api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TranscriptionConfig {
+    pub max_upload_size_mb: u32,      // Request body limit; project limits apply below it
+    pub provider_upload_limit_mb: u32, // Larger files are split into chunks
+    pub chunk_overlap_seconds: f32,
+    pub max_parallel_chunks: usize,
+    pub max_duration_minutes: u32,     // Longer recordings are rejected before dispatch
+    pub job_workers: usize,            // Background workers for async transcription jobs
+    pub job_poll_interval_ms: u64,
+    pub job_stale_after_seconds: u64,  // Running jobs older than this are requeued on startup
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RealtimeConfig {
+    pub upstream_url: String,           // OpenAI Realtime WebSocket endpoint
+    pub default_model: String,
+    pub max_session_seconds: u64,       // Sessions are closed by the gateway after this
+    pub max_sessions_per_project: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChatConfig {
+    pub forward_unknown_fields: bool,   // Pass unrecognised request fields to the provider
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BatchConfig {
+    pub max_file_size_mb: u32,          // Request body limit for /v1/files uploads
+    pub max_requests: usize,            // Requests per batch input file
+    pub max_concurrency: usize,         // Per batch; the project's max_concurrent_requests is shared by its batches
+    pub cost_discount: f64,             // Fraction of the synchronous price charged for batch requests
+    pub workers: usize,                 // Background workers, each running one batch at a time
+    pub poll_interval_ms: u64,
+    pub stale_after_seconds: u64,       // In-progress batches older than this are requeued on startup
 }
 
 impl Config {
@@ -105,6 +161,30 @@ impl Config {
             .set_default("database.mongodb.connection_timeout_ms", 10000)?
             .set_default("database.mongodb.max_pool_size", 10)?
             .set_default("database.mongodb.min_pool_size", 1)?
+            // Transcription defaults
+            .set_default("transcription.max_upload_size_mb", 500)?
+            .set_default("transcription.provider_upload_limit_mb", 25)?
+            .set_default("transcription.chunk_overlap_seconds", 2.0)?
+            .set_default("transcription.max_parallel_chunks", 4)?
+            .set_default("transcription.max_duration_minutes", 240)?
+            .set_default("transcription.job_workers", 2)?
+            .set_default("transcription.job_poll_interval_ms", 1000)?
+            .set_default("transcription.job_stale_after_seconds", 3600)?
+            // Realtime defaults
+            .set_default("realtime.upstream_url", "wss://api.openai.com/v1/realtime")?
+            .set_default("realtime.default_model", "gpt-4o-realtime-preview")?
+            .set_default("realtime.max_session_seconds", 1800)?
+            .set_default("realtime.max_sessions_per_project", 10)?
+            // Chat defaults
+            .set_default("chat.forward_unknown_fields", false)?
+            // Batch defaults
+            .set_default("batch.max_file_size_mb", 200)?
+            .set_default("batch.max_requests", 50000)?
+            .set_default("batch.max_concurrency", 8)?
+            .set_default("batch.cost_discount", 0.5)?
+            .set_default("batch.workers", 2)?
+            .set_default("batch.poll_interval_ms", 1000)?
+            .set_default("batch.stale_after_seconds", 3600)?
             // Load configuration from TOML file
             .add_source(File::with_name("config").required(false))
             .add_source(File::with_name(&format!("config.{}", environment)).required(false))
@@ -185,6 +265,22 @@ impl Config {
             return Err("MongoDB min_pool_size cannot exceed max_pool_size".to_string());
         }
 
+        if self.transcription.provider_upload_limit_mb == 0 {
+            return Err("Transcription provider_upload_limit_mb must be greater than 0".to_string());
+        }
+
+        if self.transcription.max_parallel_chunks == 0 {
+            return Err("Transcription max_parallel_chunks must be greater than 0".to_string());
+        }
+
+        if self.transcription.max_duration_minutes == 0 {
+            return Err("Transcription max_duration_minutes must be greater than 0".to_string());
+        }
+
+        if self.realtime.max_session_seconds == 0 || self.realtime.max_sessions_per_project == 0 {
+            return Err("Realtime session limits must be greater than 0".to_string());
+        }
+
         // Validate server port
         if self.server.port == 0 {
             return Err("Server port must be greater than 0".to_string());
@@ -44,6 +44,18 @@ pub enum AppError {
 
     #[error("Service unavailable: {0}")]
     ServiceUnavailable(String),
+
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
+    #[error("Structured output error: {0}")]
+    StructuredOutputError(String),
+
+    #[error("Content policy violation: {0}")]
+    ContentPolicyViolation(String),
+
+    #[error("{0}")]
+    ContextLengthExceeded(String),
 }
 
 /// Error response DTO
@@ -70,6 +82,10 @@ impl IntoResponse for AppError {
             AppError::NotFound(_) => (StatusCode::NOT_FOUND, "NOT_FOUND"),
             AppError::RateLimitError(_) => (StatusCode::TOO_MANY_REQUESTS, "RATE_LIMIT_EXCEEDED"),
             AppError::ServiceUnavailable(_) => (StatusCode::SERVICE_UNAVAILABLE, "SERVICE_UNAVAILABLE"),
+            AppError::UnsupportedMediaType(_) => (StatusCode::UNSUPPORTED_MEDIA_TYPE, "UNSUPPORTED_MEDIA_TYPE"),
+            AppError::StructuredOutputError(_) => (StatusCode::UNPROCESSABLE_ENTITY, "STRUCTURED_OUTPUT_ERROR"),
+            AppError::ContentPolicyViolation(_) => (StatusCode::BAD_REQUEST, "CONTENT_POLICY_VIOLATION"),
+            AppError::ContextLengthExceeded(_) => (StatusCode::BAD_REQUEST, "CONTEXT_LENGTH_EXCEEDED"),
             AppError::ExternalApiError(_) => (StatusCode::BAD_GATEWAY, "EXTERNAL_API_ERROR"),
             AppError::DatabaseError(_)
             | AppError::ConfigError(_)
@@ -86,7 +86,7 @@ impl EncryptionService {
     pub fn generate_key() -> String {
         let mut key = [0u8; 32];
         OsRng.fill_bytes(&mut key);
-        BASE64.encode(&key)
+        BASE64.encode(key)
     }
 }
 
@@ -1,5 +1,7 @@
 pub mod encryption;
 pub mod objectid_as_string;
 pub mod string_or_objectid;
+pub mod upload_spool;
 
-pub use encryption::EncryptionService;
\ No newline at end of file
+pub use encryption::EncryptionService;
+pub use upload_spool::{SpooledUpload, UploadSpool};
\ No newline at end of file
@@ -22,7 +22,7 @@ where
 }
 
 /// Serialize String as-is
-pub fn serialize<S>(value: &String, serializer: S) -> Result<S::Ok, S::Error>
+pub fn serialize<S>(value: &str, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
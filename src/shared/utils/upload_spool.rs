@@ -0,0 +1,79 @@
+use bytes::Bytes;
+use memmap2::Mmap;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+
+use crate::shared::error::AppError;
+
+/// Upload received in pieces and spooled to an anonymous temp file, hashed
+/// and size-checked as the bytes arrive instead of buffered in memory
+pub struct UploadSpool {
+    file: tokio::fs::File,
+    hasher: Sha256,
+    len: usize,
+    max_bytes: usize,
+}
+
+/// Completed upload: the spooled file mapped read-only, and its SHA-256
+pub struct SpooledUpload {
+    pub data: Bytes,
+    pub sha256: String,
+}
+
+impl UploadSpool {
+    pub fn new(max_bytes: usize) -> Result<Self, AppError> {
+        // Unlinked on creation, so the OS reclaims it once the mapping is dropped
+        let file = tempfile::tempfile()
+            .map_err(|e| AppError::InternalError(format!("Failed to create upload spool: {}", e)))?;
+
+        Ok(Self {
+            file: tokio::fs::File::from_std(file),
+            hasher: Sha256::new(),
+            len: 0,
+            max_bytes,
+        })
+    }
+
+    /// Append a chunk, failing as soon as the upload exceeds the limit
+    pub async fn write(&mut self, chunk: &[u8]) -> Result<(), AppError> {
+        self.len += chunk.len();
+        if self.len > self.max_bytes {
+            return Err(AppError::BadRequest(format!(
+                "File size exceeds limit of {}MB",
+                self.max_bytes / (1024 * 1024)
+            )));
+        }
+
+        self.hasher.update(chunk);
+        self.file
+            .write_all(chunk)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Failed to spool upload: {}", e)))
+    }
+
+    pub async fn finish(mut self) -> Result<SpooledUpload, AppError> {
+        self.file
+            .flush()
+            .await
+            .map_err(|e| AppError::InternalError(format!("Failed to spool upload: {}", e)))?;
+        let sha256 = hex::encode(self.hasher.finalize());
+
+        if self.len == 0 {
+            return Ok(SpooledUpload {
+                data: Bytes::new(),
+                sha256,
+            });
+        }
+
+        let file = self.file.into_std().await;
+        // SAFETY: the temp file is unlinked and only reachable through this
+        // handle, so nothing can modify or truncate it while it is mapped
+        let mmap = unsafe { Mmap::map(&file) }
+            .map_err(|e| AppError::InternalError(format!("Failed to map upload spool: {}", e)))?;
+
+        Ok(SpooledUpload {
+            data: Bytes::from_owner(mmap),
+            sha256,
+        })
+    }
+}